@@ -380,6 +380,7 @@ rm -f "$GEN"
 # this line must be at the top of the file
 echo "#![allow(unreachable_code)]" >> $GEN
 echo "#![allow(clippy::absolute_paths)]" >> $GEN
+echo "use crate::ext::{MiddlewareChain, ResponseCache};" >> $GEN
 cat $TEMP/$LIB >> $GEN
 $SDF '///!' '/// !' $GEN
 $SDF '<span>' '' $GEN
@@ -394,6 +395,15 @@ $SDF "impl Client {" "impl RawClient {" $GEN
 $SDF "for Client {" "for RawClient {" $GEN
 $SDF "for &Client {" "for &RawClient {" $GEN
 $SDF "pub use super::Client;" "pub use super::RawClient;" $GEN
+# the middleware chain of the RawClient (see src/ext/middleware.rs)
+$SDF "    pub(crate) client: reqwest::Client," $'    pub(crate) client: reqwest::Client,\n    pub(crate) middleware: MiddlewareChain,' $GEN
+$SDF "            client," $'            client,\n            middleware: MiddlewareChain::default(),' $GEN
+# the response cache of the RawClient (see src/ext/response_cache.rs)
+$SDF "use progenitor_client::{ClientHooks, OperationInfo, RequestBuilderExt, encode_path};" $'use progenitor_client::{ClientHooks, OperationInfo, RequestBuilderExt, encode_path};\nuse std::sync::Arc;' $GEN
+$SDF "    pub(crate) middleware: MiddlewareChain," $'    pub(crate) middleware: MiddlewareChain,\n    pub(crate) cache: Option<Arc<ResponseCache>>,' $GEN
+$SDF "            middleware: MiddlewareChain::default()," $'            middleware: MiddlewareChain::default(),\n            cache: None,' $GEN
+# ClientHooks is implemented in src/ext (see `impl ClientHooks<()> for &RawClient`)
+$SDF $'impl ClientHooks<()> for &RawClient {}\n' '' $GEN
 
 # `fix` must be executed before `fix:type`
 mise run fix
@@ -0,0 +1,67 @@
+use clap::Parser;
+use coda_api::{ReceivedWebhook, WebhookReceiver};
+use errgonomic::{exit_result, handle};
+use std::io;
+use std::net::SocketAddr;
+use std::process::ExitCode;
+use thiserror::Error;
+use tokio::net::TcpListener;
+
+/// Receives the webhooks of Coda automations and prints them as JSON lines.
+///
+/// Try it locally: `curl -X POST localhost:3000/webhook -H 'x-coda-webhook-secret: <secret>' -d '{"event": "rowAdded", "docId": "d", "tableId": "t", "rowId": "r"}'`
+#[derive(Parser)]
+pub struct WebhookServerCli {
+    /// Shared secret that the automations send in the `x-coda-webhook-secret` header.
+    #[arg(long, short, env = "CODA_WEBHOOK_SECRET", hide_env_values = true)]
+    pub secret: String,
+
+    /// Address to listen on.
+    #[arg(long, short, default_value = "127.0.0.1:3000")]
+    pub addr: SocketAddr,
+
+    /// Path to receive the webhooks at.
+    #[arg(long, short, default_value = "/webhook")]
+    pub path: String,
+}
+
+impl WebhookServerCli {
+    pub async fn run(&self) -> Result<(), WebhookServerCliRunError> {
+        use WebhookServerCliRunError::*;
+        let router = WebhookReceiver::new(&self.secret)
+            .on_webhook(|webhook| async move {
+                let line = match webhook {
+                    ReceivedWebhook::Event(event) => serde_json::to_string(&event),
+                    ReceivedWebhook::Other(value) => serde_json::to_string(&value),
+                };
+                match line {
+                    Ok(line) => println!("{line}"),
+                    Err(error) => eprintln!("failed to serialize webhook: {error}"),
+                }
+            })
+            .into_router(&self.path);
+        let listener = handle!(TcpListener::bind(self.addr).await, BindFailed, addr: self.addr);
+        handle!(axum::serve(listener, router).await, ServeFailed);
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum WebhookServerCliRunError {
+    #[error("failed to listen on '{addr}'")]
+    BindFailed { source: io::Error, addr: SocketAddr },
+    #[error("failed to serve webhooks")]
+    ServeFailed { source: io::Error },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = WebhookServerCli::parse();
+    exit_result(cli.run().await)
+}
+
+#[test]
+fn verify_cli() {
+    use clap::CommandFactory;
+    WebhookServerCli::command().debug_assert();
+}
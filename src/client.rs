@@ -1,14 +1,18 @@
-use crate::types::{Column, Control, Formula, Row, Table, TableReference};
-use crate::{ClientTablesError, DocData, DocId, DocMetadata, Error, Limiter, RawClient, ResponseValue, RichRow, RowUpdateResultCorrect, RowsUpsertResultCorrect, TableId, types};
-use crate::{ItemsList, ValueFormatProvider, paginate_all};
+use crate::types::{Column, ColumnFormat, ColumnFormatType, Control, Formula, Row, Table, TableReference};
+use crate::{AgentSessionTimeline, AnalyticsPoint, AnalyticsQuery, AnalyticsSource, AnalyticsTimeSeries, ClientBuilder, ControlHelperTable, ControlHelperTableValidateError, DocAnalyticsCollectionCorrect, DocSharingConfig, DocSharingPlan, DocSharingState, DocTemplate, DocTemplateReport, DocTemplateReportEntry, DocTemplateStep, DocTemplateStepStatus, DocTree, DocTreeOptions, IngestionExecutionNode, IngestionRun, ItemsList, LimiterBucket, Middleware, PackAccessChange, PackAccessConfig, PackAccessPlan, PackAccessState, PackAssetFile, PackAssetsDir, PackImageAction, PageUpdateCorrect, PageUpdateResultCorrect, PushButtonResultCorrect, ResponseCache, RetryPolicy, SharingAuditOptions, SharingAuditReport, SharingChange, SharingChangeStatus, SharingPlan, SharingReport, SharingReportEntry, TemplateParams, TryNewWithKeyError, UploadToSignedUrlError, ValueFormatProvider, WatchTarget, added_principal_key, analytics_windows, image_mime_type, in_request_span, is_same_value, needs_pack_image_upload, pack_asset_id_from_path_name, pack_principal_key, paginate_all, plan_pack_images, record_retry, sha256_hex, substitute_params, upload_to_signed_url, with_limiter_gate};
+use crate::{ClientTablesError, DocData, DocId, DocMetadata, Error, Limiter, RawClient, ResponseValue, RichRow, RowGraph, RowId, RowKey, RowUpdateResultCorrect, RowsDeleteResultCorrect, RowsUpsertResultCorrect, TableId, WebhookTriggerResultCorrect, types};
 use chrono::{DateTime, NaiveDate, Utc};
-use errgonomic::{ErrVec, ItemError, handle, handle_iter};
+use errgonomic::{ErrVec, ItemError, handle, handle_bool, handle_iter, handle_opt};
 use futures_util::future::join_all;
 use serde::de::DeserializeOwned;
-use std::collections::{BTreeMap, HashMap};
-use std::num::NonZeroU64;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io;
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
+use tokio::fs::read;
 use tokio::time::sleep;
 use uuid::Uuid;
 
@@ -32,7 +36,8 @@ macro_rules! hydrate_references {
 
 pub struct Client {
     pub raw: RawClient,
-    pub limiter: Limiter,
+    pub limiter: Arc<Limiter>,
+    pub retry_policy: RetryPolicy,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -42,52 +47,107 @@ impl Client {
 
     pub fn new(baseurl: &str) -> Self {
         let raw = RawClient::new(baseurl);
-        let limiter = Limiter::default();
+        let limiter = Arc::new(Limiter::default());
         Self {
             raw,
             limiter,
+            retry_policy: RetryPolicy::none(),
         }
     }
 
     pub fn new_with_client(baseurl: &str, client: reqwest::Client) -> Self {
         let raw = RawClient::new_with_client(baseurl, client);
-        let limiter = Limiter::default();
+        let limiter = Arc::new(Limiter::default());
         Self {
             raw,
             limiter,
+            retry_policy: RetryPolicy::none(),
         }
     }
 
+    /// Panics if the `api_key` contains characters that are not allowed in a header (use [`Client::try_new_with_key`] or [`ClientBuilder`] to get an error instead)
     pub fn new_with_key(api_key: &str) -> reqwest::Result<Self> {
         let raw = RawClient::new_with_key(api_key)?;
-        let limiter = Limiter::default();
+        let limiter = Arc::new(Limiter::default());
 
         Ok(Self {
             raw,
             limiter,
+            retry_policy: RetryPolicy::none(),
         })
     }
 
+    /// Returns an error (instead of panicking) if the `api_key` contains characters that are not allowed in a header
+    pub fn try_new_with_key(api_key: &str) -> Result<Self, TryNewWithKeyError> {
+        let raw = RawClient::try_new_with_key(api_key)?;
+        let limiter = Arc::new(Limiter::default());
+
+        Ok(Self {
+            raw,
+            limiter,
+            retry_policy: RetryPolicy::none(),
+        })
+    }
+
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Adds the `middleware` to the end of the chain (see [`Middleware`])
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.raw = self.raw.with_middleware(middleware);
+        self
+    }
+
+    /// Caches the `GET` responses (see [`ResponseCache`]). If the store is shared by clients with different API keys, key the cache by the API key of this client with [`ResponseCache::with_authorization`].
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.raw = self.raw.with_cache(cache);
+        self
+    }
+
+    /// Sends the request built by `request`, waiting for the `bucket` of the [`Limiter`] right before the request is sent (so a fresh response from the [`ResponseCache`] doesn't take a token). Repeats the request while the [`RetryPolicy`] allows retrying the error.
+    async fn call<'a, T, E, F, Fut>(&'a self, bucket: LimiterBucket, request: F) -> Result<ResponseValue<T>, Error<E>>
+    where
+        F: Fn(&'a RawClient) -> Fut,
+        Fut: Future<Output = Result<ResponseValue<T>, Error<E>>>,
+    {
+        in_request_span(bucket, async move {
+            let mut retries = 0u32;
+            loop {
+                let error = match with_limiter_gate(self.limiter.clone(), bucket, request(&self.raw)).await {
+                    Ok(response) => return Ok(response),
+                    Err(error) => error,
+                };
+                let Some(delay) = self.retry_policy.delay(retries, &error) else {
+                    return Err(error);
+                };
+                // `retries` never exceeds `RetryPolicy::max_retries`, because `delay` returns `None` once it is reached
+                retries = retries.saturating_add(1);
+                record_retry(bucket, retries, delay);
+                sleep(delay).await;
+            }
+        })
+        .await
+    }
+
     pub async fn list_categories<'a>(&'a self) -> Result<ResponseValue<types::DocCategoryList>, Error<types::ListCategoriesResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.list_categories().await
+        self.call(LimiterBucket::Read, |raw| raw.list_categories())
+            .await
     }
 
     pub async fn list_docs<'a>(&'a self, folder_id: Option<&'a str>, in_gallery: Option<bool>, is_owner: Option<bool>, is_published: Option<bool>, is_starred: Option<bool>, limit: Option<NonZeroU64>, page_token: Option<&'a str>, query: Option<&'a str>, source_doc: Option<&'a str>, workspace_id: Option<&'a str>) -> Result<ResponseValue<types::DocList>, Error<types::ListDocsResponse>> {
-        self.limiter.list_docs.until_ready().await;
-        self.raw
-            .list_docs(folder_id, in_gallery, is_owner, is_published, is_starred, limit, page_token, query, source_doc, workspace_id)
+        self.call(LimiterBucket::ListDocs, |raw| raw.list_docs(folder_id, in_gallery, is_owner, is_published, is_starred, limit, page_token, query, source_doc, workspace_id))
             .await
     }
 
     pub async fn create_doc<'a>(&'a self, body: &'a types::DocCreate) -> Result<ResponseValue<types::DocumentCreationResult>, Error<types::CreateDocResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw.create_doc(body).await
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.create_doc(body))
+            .await
     }
 
     pub async fn get_doc<'a>(&'a self, doc_id: &'a str) -> Result<ResponseValue<types::Doc>, Error<types::GetDocResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_doc(doc_id).await
+        self.call(LimiterBucket::Read, |raw| raw.get_doc(doc_id))
+            .await
     }
 
     pub async fn get_doc_metadata(&self, doc_id: &str) -> Result<DocMetadata, ClientGetDocMetadataError> {
@@ -153,647 +213,617 @@ impl Client {
         })
     }
 
+    /// Builds a [`RowGraph`] by following the lookups from the `roots` up to `max_depth` hops away. Rows that are not found are recorded as missing, so the references to them are reported as dangling.
+    pub async fn row_graph(&self, doc_id: &DocId, roots: impl IntoIterator<Item = RowKey>, max_depth: usize) -> Result<RowGraph, ClientRowGraphError> {
+        use ClientRowGraphError::*;
+
+        let mut graph = RowGraph::new();
+        let mut visited = roots.into_iter().collect::<BTreeSet<_>>();
+        let mut frontier = visited.iter().cloned().collect::<Vec<_>>();
+        for depth in 0..=max_depth {
+            if frontier.is_empty() {
+                break;
+            }
+            let responses = join_all(frontier.drain(..).map(|key| async move {
+                let result = self
+                    .get_row_correct::<RichRow>(doc_id, &key.table_id, &key.row_id, Some(false))
+                    .await;
+                (result, key)
+            }))
+            .await;
+            let results = responses.into_iter().map(|(result, key)| match result {
+                Ok(response) => Ok((key, Some(response.into_inner()))),
+                Err(error) if matches!(error.status().map(|code| code.as_u16()), Some(404)) => Ok((key, None)),
+                Err(error) => Err(ItemError {
+                    item: key,
+                    source: Box::new(error),
+                }),
+            });
+            let rows = handle_iter!(results, GetRowFailed);
+            for (key, row_opt) in rows {
+                match row_opt {
+                    Some(row) => {
+                        let targets = graph.insert_row(key.table_id, row);
+                        if depth < max_depth {
+                            frontier.extend(
+                                targets
+                                    .into_iter()
+                                    .filter(|target| visited.insert(target.clone())),
+                            );
+                        }
+                    }
+                    None => graph.insert_missing(key),
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+
     pub async fn delete_doc<'a>(&'a self, doc_id: &'a str) -> Result<ResponseValue<types::DocDelete>, Error<types::DeleteDocResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw.delete_doc(doc_id).await
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.delete_doc(doc_id))
+            .await
     }
 
     pub async fn update_doc<'a>(&'a self, doc_id: &'a str, body: &'a types::DocUpdate) -> Result<ResponseValue<types::DocUpdateResult>, Error<types::UpdateDocResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw.update_doc(doc_id, body).await
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.update_doc(doc_id, body))
+            .await
     }
 
     pub async fn get_sharing_metadata<'a>(&'a self, doc_id: &'a str) -> Result<ResponseValue<types::AclMetadata>, Error<types::GetSharingMetadataResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_sharing_metadata(doc_id).await
+        self.call(LimiterBucket::Read, |raw| raw.get_sharing_metadata(doc_id))
+            .await
     }
 
     pub async fn get_permissions<'a>(&'a self, doc_id: &'a str, limit: Option<NonZeroU64>, page_token: Option<&'a str>) -> Result<ResponseValue<types::Acl>, Error<types::GetPermissionsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_permissions(doc_id, limit, page_token).await
+        self.call(LimiterBucket::Read, |raw| raw.get_permissions(doc_id, limit, page_token))
+            .await
     }
 
     pub async fn add_permission<'a>(&'a self, doc_id: &'a str, body: &'a types::AddPermissionRequest) -> Result<ResponseValue<types::AddPermissionResult>, Error<types::AddPermissionResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.add_permission(doc_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.add_permission(doc_id, body))
+            .await
     }
 
     pub async fn delete_permission<'a>(&'a self, doc_id: &'a str, permission_id: &'a str) -> Result<ResponseValue<types::DeletePermissionResult>, Error<types::DeletePermissionResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.delete_permission(doc_id, permission_id).await
+        self.call(LimiterBucket::Write, |raw| raw.delete_permission(doc_id, permission_id))
+            .await
     }
 
     pub async fn search_principals<'a>(&'a self, doc_id: &'a str, query: Option<&'a str>) -> Result<ResponseValue<types::SearchPrincipalsResponse>, Error<types::SearchPrincipalsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.search_principals(doc_id, query).await
+        self.call(LimiterBucket::Read, |raw| raw.search_principals(doc_id, query))
+            .await
     }
 
     pub async fn get_acl_settings<'a>(&'a self, doc_id: &'a str) -> Result<ResponseValue<types::AclSettings>, Error<types::GetAclSettingsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_acl_settings(doc_id).await
+        self.call(LimiterBucket::Read, |raw| raw.get_acl_settings(doc_id))
+            .await
     }
 
     pub async fn update_acl_settings<'a>(&'a self, doc_id: &'a str, body: &'a types::UpdateAclSettingsRequest) -> Result<ResponseValue<types::AclSettings>, Error<types::UpdateAclSettingsResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.update_acl_settings(doc_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.update_acl_settings(doc_id, body))
+            .await
     }
 
     pub async fn publish_doc<'a>(&'a self, doc_id: &'a str, body: &'a types::DocPublish) -> Result<ResponseValue<types::PublishResult>, Error<types::PublishDocResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw.publish_doc(doc_id, body).await
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.publish_doc(doc_id, body))
+            .await
     }
 
     pub async fn unpublish_doc<'a>(&'a self, doc_id: &'a str) -> Result<ResponseValue<types::UnpublishResult>, Error<types::UnpublishDocResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw.unpublish_doc(doc_id).await
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.unpublish_doc(doc_id))
+            .await
     }
 
     pub async fn list_pages<'a>(&'a self, doc_id: &'a str, limit: Option<NonZeroU64>, page_token: Option<&'a str>) -> Result<ResponseValue<types::PageList>, Error<types::ListPagesResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.list_pages(doc_id, limit, page_token).await
+        self.call(LimiterBucket::Read, |raw| raw.list_pages(doc_id, limit, page_token))
+            .await
     }
 
     pub async fn create_page<'a>(&'a self, doc_id: &'a str, body: &'a types::PageCreate) -> Result<ResponseValue<types::PageCreateResult>, Error<types::CreatePageResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw.create_page(doc_id, body).await
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.create_page(doc_id, body))
+            .await
     }
 
     pub async fn get_page<'a>(&'a self, doc_id: &'a str, page_id_or_name: &'a str) -> Result<ResponseValue<types::Page>, Error<types::GetPageResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_page(doc_id, page_id_or_name).await
+        self.call(LimiterBucket::Read, |raw| raw.get_page(doc_id, page_id_or_name))
+            .await
     }
 
     pub async fn update_page<'a>(&'a self, doc_id: &'a str, page_id_or_name: &'a str, body: &'a types::PageUpdate) -> Result<ResponseValue<types::PageUpdateResult>, Error<types::UpdatePageResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw.update_page(doc_id, page_id_or_name, body).await
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.update_page(doc_id, page_id_or_name, body))
+            .await
+    }
+
+    pub async fn update_page_correct<'a>(&'a self, doc_id: &'a str, page_id_or_name: &'a str, body: &'a PageUpdateCorrect) -> Result<ResponseValue<PageUpdateResultCorrect>, Error<types::UpdatePageResponse>> {
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.update_page_correct(doc_id, page_id_or_name, body))
+            .await
     }
 
     pub async fn delete_page<'a>(&'a self, doc_id: &'a str, page_id_or_name: &'a str) -> Result<ResponseValue<types::PageDeleteResult>, Error<types::DeletePageResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw.delete_page(doc_id, page_id_or_name).await
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.delete_page(doc_id, page_id_or_name))
+            .await
     }
 
     pub async fn begin_page_content_export<'a>(&'a self, doc_id: &'a str, page_id_or_name: &'a str, body: &'a types::BeginPageContentExportRequest) -> Result<ResponseValue<types::BeginPageContentExportResponse>, Error<types::BeginPageContentExportResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw
-            .begin_page_content_export(doc_id, page_id_or_name, body)
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.begin_page_content_export(doc_id, page_id_or_name, body))
             .await
     }
 
     pub async fn get_page_content_export_status<'a>(&'a self, doc_id: &'a str, page_id_or_name: &'a str, request_id: &'a str) -> Result<ResponseValue<types::PageContentExportStatusResponse>, Error<types::GetPageContentExportStatusResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .get_page_content_export_status(doc_id, page_id_or_name, request_id)
+        self.call(LimiterBucket::Read, |raw| raw.get_page_content_export_status(doc_id, page_id_or_name, request_id))
             .await
     }
 
     pub async fn list_tables<'a>(&'a self, doc_id: &'a str, limit: Option<NonZeroU64>, page_token: Option<&'a str>, sort_by: Option<types::SortBy>, table_types: Option<&'a Vec<types::TableTypeEnum>>) -> Result<ResponseValue<types::TableList>, Error<types::ListTablesResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_tables(doc_id, limit, page_token, sort_by, table_types)
+        self.call(LimiterBucket::Read, |raw| raw.list_tables(doc_id, limit, page_token, sort_by, table_types))
             .await
     }
 
     pub async fn get_table<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, use_updated_table_layouts: Option<bool>) -> Result<ResponseValue<types::Table>, Error<types::GetTableResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .get_table(doc_id, table_id_or_name, use_updated_table_layouts)
+        self.call(LimiterBucket::Read, |raw| raw.get_table(doc_id, table_id_or_name, use_updated_table_layouts))
             .await
     }
 
     pub async fn list_columns<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, limit: Option<NonZeroU64>, page_token: Option<&'a str>, visible_only: Option<bool>) -> Result<ResponseValue<types::ColumnList>, Error<types::ListColumnsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_columns(doc_id, table_id_or_name, limit, page_token, visible_only)
+        self.call(LimiterBucket::Read, |raw| raw.list_columns(doc_id, table_id_or_name, limit, page_token, visible_only))
             .await
     }
 
     pub async fn list_rows<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, limit: Option<NonZeroU64>, page_token: Option<&'a str>, query: Option<&'a str>, sort_by: Option<types::RowsSortBy>, sync_token: Option<&'a str>, use_column_names: Option<bool>, value_format: Option<types::ValueFormat>, visible_only: Option<bool>) -> Result<ResponseValue<types::RowList>, Error<types::ListRowsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_rows(doc_id, table_id_or_name, limit, page_token, query, sort_by, sync_token, use_column_names, value_format, visible_only)
+        self.call(LimiterBucket::Read, |raw| raw.list_rows(doc_id, table_id_or_name, limit, page_token, query, sort_by, sync_token, use_column_names, value_format, visible_only))
             .await
     }
 
     #[deprecated = "use upsert_rows_correct"]
     pub async fn upsert_rows<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, disable_parsing: Option<bool>, body: &'a types::RowsUpsert) -> Result<ResponseValue<types::RowsUpsertResult>, Error<types::UpsertRowsResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw
-            .upsert_rows(doc_id, table_id_or_name, disable_parsing, body)
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.upsert_rows(doc_id, table_id_or_name, disable_parsing, body))
             .await
     }
 
     pub async fn delete_rows<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, body: &'a types::RowsDelete) -> Result<ResponseValue<types::RowsDeleteResult>, Error<types::DeleteRowsResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw.delete_rows(doc_id, table_id_or_name, body).await
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.delete_rows(doc_id, table_id_or_name, body))
+            .await
+    }
+
+    pub async fn delete_rows_correct<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, body: &'a types::RowsDelete) -> Result<ResponseValue<RowsDeleteResultCorrect>, Error<types::DeleteRowsResponse>> {
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.delete_rows_correct(doc_id, table_id_or_name, body))
+            .await
     }
 
     #[deprecated = "use get_row_correct"]
     pub async fn get_row<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, row_id_or_name: &'a str, use_column_names: Option<bool>, value_format: Option<types::ValueFormat>) -> Result<ResponseValue<types::RowDetail>, Error<types::GetRowResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .get_row(doc_id, table_id_or_name, row_id_or_name, use_column_names, value_format)
+        self.call(LimiterBucket::Read, |raw| raw.get_row(doc_id, table_id_or_name, row_id_or_name, use_column_names, value_format))
             .await
     }
 
     pub async fn get_row_correct<'a, T: DeserializeOwned + ValueFormatProvider>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, row_id_or_name: &'a str, use_column_names: Option<bool>) -> Result<ResponseValue<T>, Error<types::GetRowResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .get_row_correct(doc_id, table_id_or_name, row_id_or_name, use_column_names)
+        self.call(LimiterBucket::Read, |raw| raw.get_row_correct(doc_id, table_id_or_name, row_id_or_name, use_column_names))
             .await
     }
 
     pub async fn update_row<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, row_id_or_name: &'a str, disable_parsing: Option<bool>, body: &'a types::RowUpdate) -> Result<ResponseValue<types::RowUpdateResult>, Error<types::UpdateRowResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw
-            .update_row(doc_id, table_id_or_name, row_id_or_name, disable_parsing, body)
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.update_row(doc_id, table_id_or_name, row_id_or_name, disable_parsing, body))
             .await
     }
 
     pub async fn delete_row<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, row_id_or_name: &'a str) -> Result<ResponseValue<types::RowDeleteResult>, Error<types::DeleteRowResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw
-            .delete_row(doc_id, table_id_or_name, row_id_or_name)
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.delete_row(doc_id, table_id_or_name, row_id_or_name))
             .await
     }
 
     pub async fn push_button<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, row_id_or_name: &'a str, column_id_or_name: &'a str) -> Result<ResponseValue<types::PushButtonResult>, Error<types::PushButtonResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw
-            .push_button(doc_id, table_id_or_name, row_id_or_name, column_id_or_name)
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.push_button(doc_id, table_id_or_name, row_id_or_name, column_id_or_name))
+            .await
+    }
+
+    pub async fn push_button_correct<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, row_id_or_name: &'a str, column_id_or_name: &'a str) -> Result<ResponseValue<PushButtonResultCorrect>, Error<types::PushButtonResponse>> {
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.push_button_correct(doc_id, table_id_or_name, row_id_or_name, column_id_or_name))
             .await
     }
 
     pub async fn get_column<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, column_id_or_name: &'a str) -> Result<ResponseValue<types::ColumnDetail>, Error<types::GetColumnResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .get_column(doc_id, table_id_or_name, column_id_or_name)
+        self.call(LimiterBucket::Read, |raw| raw.get_column(doc_id, table_id_or_name, column_id_or_name))
             .await
     }
 
     pub async fn list_formulas<'a>(&'a self, doc_id: &'a str, limit: Option<NonZeroU64>, page_token: Option<&'a str>, sort_by: Option<types::SortBy>) -> Result<ResponseValue<types::FormulaList>, Error<types::ListFormulasResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_formulas(doc_id, limit, page_token, sort_by)
+        self.call(LimiterBucket::Read, |raw| raw.list_formulas(doc_id, limit, page_token, sort_by))
             .await
     }
 
     pub async fn get_formula<'a>(&'a self, doc_id: &'a str, formula_id_or_name: &'a str) -> Result<ResponseValue<types::Formula>, Error<types::GetFormulaResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_formula(doc_id, formula_id_or_name).await
+        self.call(LimiterBucket::Read, |raw| raw.get_formula(doc_id, formula_id_or_name))
+            .await
     }
 
     pub async fn list_controls<'a>(&'a self, doc_id: &'a str, limit: Option<NonZeroU64>, page_token: Option<&'a str>, sort_by: Option<types::SortBy>) -> Result<ResponseValue<types::ControlList>, Error<types::ListControlsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_controls(doc_id, limit, page_token, sort_by)
+        self.call(LimiterBucket::Read, |raw| raw.list_controls(doc_id, limit, page_token, sort_by))
             .await
     }
 
     pub async fn get_control<'a>(&'a self, doc_id: &'a str, control_id_or_name: &'a str) -> Result<ResponseValue<types::Control>, Error<types::GetControlResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_control(doc_id, control_id_or_name).await
+        self.call(LimiterBucket::Read, |raw| raw.get_control(doc_id, control_id_or_name))
+            .await
     }
 
     pub async fn list_custom_doc_domains<'a>(&'a self, doc_id: &'a str) -> Result<ResponseValue<types::CustomDocDomainList>, Error<types::ListCustomDocDomainsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.list_custom_doc_domains(doc_id).await
+        self.call(LimiterBucket::Read, |raw| raw.list_custom_doc_domains(doc_id))
+            .await
     }
 
     pub async fn add_custom_doc_domain<'a>(&'a self, doc_id: &'a str, body: &'a types::AddCustomDocDomainRequest) -> Result<ResponseValue<types::AddCustomDocDomainResponse>, Error<types::AddCustomDocDomainResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.add_custom_doc_domain(doc_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.add_custom_doc_domain(doc_id, body))
+            .await
     }
 
     pub async fn delete_custom_doc_domain<'a>(&'a self, doc_id: &'a str, custom_doc_domain: &'a str) -> Result<ResponseValue<types::DeleteCustomDocDomainResponse>, Error<types::DeleteCustomDocDomainResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw
-            .delete_custom_doc_domain(doc_id, custom_doc_domain)
+        self.call(LimiterBucket::Write, |raw| raw.delete_custom_doc_domain(doc_id, custom_doc_domain))
             .await
     }
 
     pub async fn update_custom_doc_domain<'a>(&'a self, doc_id: &'a str, custom_doc_domain: &'a str, body: &'a types::UpdateCustomDocDomainRequest) -> Result<ResponseValue<types::UpdateCustomDocDomainResponse>, Error<types::UpdateCustomDocDomainResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw
-            .update_custom_doc_domain(doc_id, custom_doc_domain, body)
+        self.call(LimiterBucket::Write, |raw| raw.update_custom_doc_domain(doc_id, custom_doc_domain, body))
             .await
     }
 
     pub async fn get_custom_doc_domain_provider<'a>(&'a self, custom_doc_domain: &'a str) -> Result<ResponseValue<types::CustomDocDomainProviderResponse>, Error<types::GetCustomDocDomainProviderResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .get_custom_doc_domain_provider(custom_doc_domain)
+        self.call(LimiterBucket::Read, |raw| raw.get_custom_doc_domain_provider(custom_doc_domain))
             .await
     }
 
     pub async fn get_folder<'a>(&'a self, folder_id: &'a str) -> Result<ResponseValue<types::Folder>, Error<types::GetFolderResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_folder(folder_id).await
+        self.call(LimiterBucket::Read, |raw| raw.get_folder(folder_id))
+            .await
     }
 
     pub async fn whoami<'a>(&'a self) -> Result<ResponseValue<types::User>, Error<types::WhoamiResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.whoami().await
+        self.call(LimiterBucket::Read, |raw| raw.whoami()).await
     }
 
     pub async fn resolve_browser_link<'a>(&'a self, degrade_gracefully: Option<bool>, url: &'a str) -> Result<ResponseValue<types::ApiLink>, Error<types::ResolveBrowserLinkResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.resolve_browser_link(degrade_gracefully, url).await
+        self.call(LimiterBucket::Read, |raw| raw.resolve_browser_link(degrade_gracefully, url))
+            .await
     }
 
     pub async fn get_mutation_status<'a>(&'a self, request_id: &'a str) -> Result<ResponseValue<types::MutationStatus>, Error<types::GetMutationStatusResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_mutation_status(request_id).await
+        self.call(LimiterBucket::Read, |raw| raw.get_mutation_status(request_id))
+            .await
     }
 
     pub async fn trigger_webhook_automation<'a>(&'a self, doc_id: &'a str, rule_id: &'a str, body: &'a types::WebhookTriggerPayload) -> Result<ResponseValue<types::WebhookTriggerResult>, Error<types::TriggerWebhookAutomationResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw
-            .trigger_webhook_automation(doc_id, rule_id, body)
+        self.call(LimiterBucket::Write, |raw| raw.trigger_webhook_automation(doc_id, rule_id, body))
+            .await
+    }
+
+    pub async fn trigger_webhook_automation_correct<'a>(&'a self, doc_id: &'a str, rule_id: &'a str, body: &'a types::WebhookTriggerPayload) -> Result<ResponseValue<WebhookTriggerResultCorrect>, Error<types::TriggerWebhookAutomationResponse>> {
+        self.call(LimiterBucket::Write, |raw| raw.trigger_webhook_automation_correct(doc_id, rule_id, body))
             .await
     }
 
     pub async fn list_doc_analytics<'a>(&'a self, direction: Option<types::SortDirection>, doc_ids: Option<&'a Vec<String>>, is_published: Option<bool>, limit: Option<NonZeroU64>, order_by: Option<types::DocAnalyticsOrderBy>, page_token: Option<&'a str>, query: Option<&'a str>, scale: Option<types::AnalyticsScale>, since_date: Option<&'a NaiveDate>, until_date: Option<&'a NaiveDate>, workspace_id: Option<&'a str>) -> Result<ResponseValue<types::DocAnalyticsCollection>, Error<types::ListDocAnalyticsResponse>> {
-        self.limiter.read_analytics.until_ready().await;
-        self.raw
-            .list_doc_analytics(direction, doc_ids, is_published, limit, order_by, page_token, query, scale, since_date, until_date, workspace_id)
+        self.call(LimiterBucket::ReadAnalytics, |raw| raw.list_doc_analytics(direction, doc_ids, is_published, limit, order_by, page_token, query, scale, since_date, until_date, workspace_id))
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn list_doc_analytics_correct<'a>(&'a self, direction: Option<types::SortDirection>, doc_ids: Option<&'a Vec<String>>, is_published: Option<bool>, limit: Option<NonZeroU64>, order_by: Option<types::DocAnalyticsOrderBy>, page_token: Option<&'a str>, query: Option<&'a str>, scale: Option<types::AnalyticsScale>, since_date: Option<&'a NaiveDate>, until_date: Option<&'a NaiveDate>, workspace_id: Option<&'a str>) -> Result<ResponseValue<DocAnalyticsCollectionCorrect>, Error<types::ListDocAnalyticsResponse>> {
+        self.call(LimiterBucket::ReadAnalytics, |raw| raw.list_doc_analytics_correct(direction, doc_ids, is_published, limit, order_by, page_token, query, scale, since_date, until_date, workspace_id))
             .await
     }
 
     pub async fn list_page_analytics<'a>(&'a self, doc_id: &'a str, limit: Option<NonZeroU64>, page_token: Option<&'a str>, since_date: Option<&'a NaiveDate>, until_date: Option<&'a NaiveDate>) -> Result<ResponseValue<types::PageAnalyticsCollection>, Error<types::ListPageAnalyticsResponse>> {
-        self.limiter.read_analytics.until_ready().await;
-        self.raw
-            .list_page_analytics(doc_id, limit, page_token, since_date, until_date)
+        self.call(LimiterBucket::ReadAnalytics, |raw| raw.list_page_analytics(doc_id, limit, page_token, since_date, until_date))
             .await
     }
 
     pub async fn list_doc_analytics_summary<'a>(&'a self, is_published: Option<bool>, since_date: Option<&'a NaiveDate>, until_date: Option<&'a NaiveDate>, workspace_id: Option<&'a str>) -> Result<ResponseValue<types::DocAnalyticsSummary>, Error<types::ListDocAnalyticsSummaryResponse>> {
-        self.limiter.read_analytics.until_ready().await;
-        self.raw
-            .list_doc_analytics_summary(is_published, since_date, until_date, workspace_id)
+        self.call(LimiterBucket::ReadAnalytics, |raw| raw.list_doc_analytics_summary(is_published, since_date, until_date, workspace_id))
             .await
     }
 
     pub async fn list_pack_analytics<'a>(&'a self, direction: Option<types::SortDirection>, is_published: Option<bool>, limit: Option<NonZeroU64>, order_by: Option<types::PackAnalyticsOrderBy>, pack_ids: Option<&'a Vec<i64>>, page_token: Option<&'a str>, query: Option<&'a str>, scale: Option<types::AnalyticsScale>, since_date: Option<&'a NaiveDate>, until_date: Option<&'a NaiveDate>, workspace_id: Option<&'a str>) -> Result<ResponseValue<types::PackAnalyticsCollection>, Error<types::ListPackAnalyticsResponse>> {
-        self.limiter.read_analytics.until_ready().await;
-        self.raw
-            .list_pack_analytics(direction, is_published, limit, order_by, pack_ids, page_token, query, scale, since_date, until_date, workspace_id)
+        self.call(LimiterBucket::ReadAnalytics, |raw| raw.list_pack_analytics(direction, is_published, limit, order_by, pack_ids, page_token, query, scale, since_date, until_date, workspace_id))
             .await
     }
 
     pub async fn list_pack_analytics_summary<'a>(&'a self, is_published: Option<bool>, pack_ids: Option<&'a Vec<i64>>, since_date: Option<&'a NaiveDate>, until_date: Option<&'a NaiveDate>, workspace_id: Option<&'a str>) -> Result<ResponseValue<types::PackAnalyticsSummary>, Error<types::ListPackAnalyticsSummaryResponse>> {
-        self.limiter.read_analytics.until_ready().await;
-        self.raw
-            .list_pack_analytics_summary(is_published, pack_ids, since_date, until_date, workspace_id)
+        self.call(LimiterBucket::ReadAnalytics, |raw| raw.list_pack_analytics_summary(is_published, pack_ids, since_date, until_date, workspace_id))
             .await
     }
 
     pub async fn list_pack_formula_analytics<'a>(&'a self, pack_id: NonZeroU64, direction: Option<types::SortDirection>, limit: Option<NonZeroU64>, order_by: Option<types::PackFormulaAnalyticsOrderBy>, pack_formula_names: Option<&'a Vec<String>>, pack_formula_types: Option<&'a Vec<types::PackFormulaType>>, page_token: Option<&'a str>, scale: Option<types::AnalyticsScale>, since_date: Option<&'a NaiveDate>, until_date: Option<&'a NaiveDate>) -> Result<ResponseValue<types::PackFormulaAnalyticsCollection>, Error<types::ListPackFormulaAnalyticsResponse>> {
-        self.limiter.read_analytics.until_ready().await;
-        self.raw
-            .list_pack_formula_analytics(pack_id, direction, limit, order_by, pack_formula_names, pack_formula_types, page_token, scale, since_date, until_date)
+        self.call(LimiterBucket::ReadAnalytics, |raw| raw.list_pack_formula_analytics(pack_id, direction, limit, order_by, pack_formula_names, pack_formula_types, page_token, scale, since_date, until_date))
             .await
     }
 
     pub async fn get_analytics_last_updated<'a>(&'a self) -> Result<ResponseValue<types::AnalyticsLastUpdatedResponse>, Error<types::GetAnalyticsLastUpdatedResponse>> {
-        self.limiter.read_analytics.until_ready().await;
-        self.raw.get_analytics_last_updated().await
+        self.call(LimiterBucket::ReadAnalytics, |raw| raw.get_analytics_last_updated())
+            .await
     }
 
     pub async fn list_workspace_members<'a>(&'a self, workspace_id: &'a str, included_roles: Option<&'a Vec<types::WorkspaceUserRole>>, page_token: Option<&'a str>) -> Result<ResponseValue<types::WorkspaceMembersList>, Error<types::ListWorkspaceMembersResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_workspace_members(workspace_id, included_roles, page_token)
+        self.call(LimiterBucket::Read, |raw| raw.list_workspace_members(workspace_id, included_roles, page_token))
             .await
     }
 
     pub async fn change_user_role<'a>(&'a self, workspace_id: &'a str, body: &'a types::ChangeRole) -> Result<ResponseValue<types::ChangeRoleResult>, Error<types::ChangeUserRoleResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.change_user_role(workspace_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.change_user_role(workspace_id, body))
+            .await
     }
 
     pub async fn list_workspace_role_activity<'a>(&'a self, workspace_id: &'a str) -> Result<ResponseValue<types::GetWorkspaceRoleActivity>, Error<types::ListWorkspaceRoleActivityResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.list_workspace_role_activity(workspace_id).await
+        self.call(LimiterBucket::Read, |raw| raw.list_workspace_role_activity(workspace_id))
+            .await
     }
 
     pub async fn list_packs<'a>(&'a self, access_type: Option<types::PackAccessType>, access_types: Option<&'a Vec<types::PackAccessType>>, direction: Option<types::SortDirection>, exclude_public_packs: Option<bool>, limit: Option<NonZeroU64>, only_workspace_id: Option<&'a str>, pack_entrypoint: Option<types::PackEntrypoint>, page_token: Option<&'a str>, parent_workspace_ids: Option<&'a Vec<String>>, sort_by: Option<types::PacksSortBy>) -> Result<ResponseValue<types::PackSummaryList>, Error<types::ListPacksResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_packs(access_type, access_types, direction, exclude_public_packs, limit, only_workspace_id, pack_entrypoint, page_token, parent_workspace_ids, sort_by)
+        self.call(LimiterBucket::Read, |raw| raw.list_packs(access_type, access_types, direction, exclude_public_packs, limit, only_workspace_id, pack_entrypoint, page_token, parent_workspace_ids, sort_by))
             .await
     }
 
     pub async fn create_pack<'a>(&'a self, body: &'a types::CreatePackRequest) -> Result<ResponseValue<types::CreatePackResponse>, Error<types::CreatePackResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.create_pack(body).await
+        self.call(LimiterBucket::Write, |raw| raw.create_pack(body))
+            .await
     }
 
     pub async fn get_pack<'a>(&'a self, pack_id: NonZeroU64) -> Result<ResponseValue<types::Pack>, Error<types::GetPackResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_pack(pack_id).await
+        self.call(LimiterBucket::Read, |raw| raw.get_pack(pack_id))
+            .await
     }
 
     pub async fn delete_pack<'a>(&'a self, pack_id: NonZeroU64) -> Result<ResponseValue<types::DeletePackResponse>, Error<types::DeletePackResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.delete_pack(pack_id).await
+        self.call(LimiterBucket::Write, |raw| raw.delete_pack(pack_id))
+            .await
     }
 
     pub async fn update_pack<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::UpdatePackRequest) -> Result<ResponseValue<types::Pack>, Error<types::UpdatePackResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.update_pack(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.update_pack(pack_id, body))
+            .await
     }
 
     pub async fn get_pack_configuration_schema<'a>(&'a self, pack_id: NonZeroU64) -> Result<ResponseValue<types::GetPackConfigurationJsonSchemaResponse>, Error<types::GetPackConfigurationSchemaResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_pack_configuration_schema(pack_id).await
+        self.call(LimiterBucket::Read, |raw| raw.get_pack_configuration_schema(pack_id))
+            .await
     }
 
     pub async fn list_pack_versions<'a>(&'a self, pack_id: NonZeroU64, limit: Option<NonZeroU64>, page_token: Option<&'a str>) -> Result<ResponseValue<types::PackVersionList>, Error<types::ListPackVersionsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_pack_versions(pack_id, limit, page_token)
+        self.call(LimiterBucket::Read, |raw| raw.list_pack_versions(pack_id, limit, page_token))
             .await
     }
 
     pub async fn get_next_pack_version<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::GetNextPackVersionRequest) -> Result<ResponseValue<types::NextPackVersionInfo>, Error<types::GetNextPackVersionResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.get_next_pack_version(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.get_next_pack_version(pack_id, body))
+            .await
     }
 
     pub async fn get_pack_version_diffs<'a>(&'a self, pack_id: NonZeroU64, base_pack_version: &'a str, target_pack_version: &'a str) -> Result<ResponseValue<types::PackVersionDiffs>, Error<types::GetPackVersionDiffsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .get_pack_version_diffs(pack_id, base_pack_version, target_pack_version)
+        self.call(LimiterBucket::Read, |raw| raw.get_pack_version_diffs(pack_id, base_pack_version, target_pack_version))
             .await
     }
 
     pub async fn register_pack_version<'a>(&'a self, pack_id: NonZeroU64, pack_version: &'a str, body: &'a types::RegisterPackVersionRequest) -> Result<ResponseValue<types::PackVersionUploadInfo>, Error<types::RegisterPackVersionResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw
-            .register_pack_version(pack_id, pack_version, body)
+        self.call(LimiterBucket::Write, |raw| raw.register_pack_version(pack_id, pack_version, body))
             .await
     }
 
     pub async fn pack_version_upload_complete<'a>(&'a self, pack_id: NonZeroU64, pack_version: &'a str, body: &'a types::CreatePackVersionRequest) -> Result<ResponseValue<types::CreatePackVersionResponse>, Error<types::PackVersionUploadCompleteResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw
-            .pack_version_upload_complete(pack_id, pack_version, body)
+        self.call(LimiterBucket::Write, |raw| raw.pack_version_upload_complete(pack_id, pack_version, body))
             .await
     }
 
     pub async fn list_pack_releases<'a>(&'a self, pack_id: NonZeroU64, limit: Option<NonZeroU64>, page_token: Option<&'a str>) -> Result<ResponseValue<types::PackReleaseList>, Error<types::ListPackReleasesResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_pack_releases(pack_id, limit, page_token)
+        self.call(LimiterBucket::Read, |raw| raw.list_pack_releases(pack_id, limit, page_token))
             .await
     }
 
     pub async fn create_pack_release<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::CreatePackReleaseRequest) -> Result<ResponseValue<types::PackRelease>, Error<types::CreatePackReleaseResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.create_pack_release(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.create_pack_release(pack_id, body))
+            .await
     }
 
     pub async fn update_pack_release<'a>(&'a self, pack_id: NonZeroU64, pack_release_id: NonZeroU64, body: &'a types::UpdatePackReleaseRequest) -> Result<ResponseValue<types::PackRelease>, Error<types::UpdatePackReleaseResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw
-            .update_pack_release(pack_id, pack_release_id, body)
+        self.call(LimiterBucket::Write, |raw| raw.update_pack_release(pack_id, pack_release_id, body))
             .await
     }
 
     pub async fn get_pack_oauth_config<'a>(&'a self, pack_id: NonZeroU64) -> Result<ResponseValue<types::PackOauthConfigMetadata>, Error<types::GetPackOauthConfigResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_pack_oauth_config(pack_id).await
+        self.call(LimiterBucket::Read, |raw| raw.get_pack_oauth_config(pack_id))
+            .await
     }
 
     pub async fn set_pack_oauth_config<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::SetPackOauthConfigRequest) -> Result<ResponseValue<types::PackOauthConfigMetadata>, Error<types::SetPackOauthConfigResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.set_pack_oauth_config(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.set_pack_oauth_config(pack_id, body))
+            .await
     }
 
     pub async fn get_pack_system_connection<'a>(&'a self, pack_id: NonZeroU64) -> Result<ResponseValue<types::PackSystemConnectionMetadata>, Error<types::GetPackSystemConnectionResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_pack_system_connection(pack_id).await
+        self.call(LimiterBucket::Read, |raw| raw.get_pack_system_connection(pack_id))
+            .await
     }
 
     pub async fn set_pack_system_connection<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::SetPackSystemConnectionRequest) -> Result<ResponseValue<types::PackSystemConnectionMetadata>, Error<types::SetPackSystemConnectionResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.set_pack_system_connection(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.set_pack_system_connection(pack_id, body))
+            .await
     }
 
     pub async fn patch_pack_system_connection<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::PatchPackSystemConnectionRequest) -> Result<ResponseValue<types::PackSystemConnectionMetadata>, Error<types::PatchPackSystemConnectionResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.patch_pack_system_connection(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.patch_pack_system_connection(pack_id, body))
+            .await
     }
 
     pub async fn get_pack_permissions<'a>(&'a self, pack_id: NonZeroU64) -> Result<ResponseValue<types::PackPermissionList>, Error<types::GetPackPermissionsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_pack_permissions(pack_id).await
+        self.call(LimiterBucket::Read, |raw| raw.get_pack_permissions(pack_id))
+            .await
     }
 
     pub async fn add_pack_permission<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::AddPackPermissionRequest) -> Result<ResponseValue<types::AddPackPermissionResponse>, Error<types::AddPackPermissionResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.add_pack_permission(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.add_pack_permission(pack_id, body))
+            .await
     }
 
     pub async fn delete_pack_permission<'a>(&'a self, pack_id: NonZeroU64, permission_id: &'a str) -> Result<ResponseValue<types::DeletePackPermissionResponse>, Error<types::DeletePackPermissionResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw
-            .delete_pack_permission(pack_id, permission_id)
+        self.call(LimiterBucket::Write, |raw| raw.delete_pack_permission(pack_id, permission_id))
             .await
     }
 
     pub async fn list_user_pack_invitations<'a>(&'a self, limit: Option<NonZeroU64>, page_token: Option<&'a str>) -> Result<ResponseValue<types::PackInvitationWithPackList>, Error<types::ListUserPackInvitationsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.list_user_pack_invitations(limit, page_token).await
+        self.call(LimiterBucket::Read, |raw| raw.list_user_pack_invitations(limit, page_token))
+            .await
     }
 
     pub async fn list_pack_invitations<'a>(&'a self, pack_id: NonZeroU64, limit: Option<NonZeroU64>, page_token: Option<&'a str>) -> Result<ResponseValue<types::PackInvitationList>, Error<types::ListPackInvitationsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_pack_invitations(pack_id, limit, page_token)
+        self.call(LimiterBucket::Read, |raw| raw.list_pack_invitations(pack_id, limit, page_token))
             .await
     }
 
     pub async fn create_pack_invitation<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::CreatePackInvitationRequest) -> Result<ResponseValue<types::CreatePackInvitationResponse>, Error<types::CreatePackInvitationResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.create_pack_invitation(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.create_pack_invitation(pack_id, body))
+            .await
     }
 
     pub async fn update_pack_invitation<'a>(&'a self, pack_id: NonZeroU64, invitation_id: &'a Uuid, body: &'a types::UpdatePackInvitationRequest) -> Result<ResponseValue<types::UpdatePackInvitationResponse>, Error<types::UpdatePackInvitationResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw
-            .update_pack_invitation(pack_id, invitation_id, body)
+        self.call(LimiterBucket::Write, |raw| raw.update_pack_invitation(pack_id, invitation_id, body))
             .await
     }
 
     pub async fn delete_pack_invitation<'a>(&'a self, pack_id: NonZeroU64, invitation_id: &'a Uuid) -> Result<ResponseValue<types::DeletePackInvitationResponse>, Error<types::DeletePackInvitationResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw
-            .delete_pack_invitation(pack_id, invitation_id)
+        self.call(LimiterBucket::Write, |raw| raw.delete_pack_invitation(pack_id, invitation_id))
             .await
     }
 
     pub async fn reply_to_pack_invitation<'a>(&'a self, invitation_id: &'a Uuid, body: &'a types::HandlePackInvitationRequest) -> Result<ResponseValue<types::HandlePackInvitationResponse>, Error<types::ReplyToPackInvitationResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.reply_to_pack_invitation(invitation_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.reply_to_pack_invitation(invitation_id, body))
+            .await
     }
 
     pub async fn list_pack_makers<'a>(&'a self, pack_id: NonZeroU64) -> Result<ResponseValue<types::ListPackMakersResponse>, Error<types::ListPackMakersResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.list_pack_makers(pack_id).await
+        self.call(LimiterBucket::Read, |raw| raw.list_pack_makers(pack_id))
+            .await
     }
 
     pub async fn add_pack_maker<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::AddPackMakerRequest) -> Result<ResponseValue<types::AddPackMakerResponse>, Error<types::AddPackMakerResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.add_pack_maker(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.add_pack_maker(pack_id, body))
+            .await
     }
 
     pub async fn delete_pack_maker<'a>(&'a self, pack_id: NonZeroU64, login_id: &'a str) -> Result<ResponseValue<types::DeletePackMakerResponse>, Error<types::DeletePackMakerResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.delete_pack_maker(pack_id, login_id).await
+        self.call(LimiterBucket::Write, |raw| raw.delete_pack_maker(pack_id, login_id))
+            .await
     }
 
     pub async fn list_pack_categories<'a>(&'a self, pack_id: NonZeroU64) -> Result<ResponseValue<types::ListPackCategoriesResponse>, Error<types::ListPackCategoriesResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.list_pack_categories(pack_id).await
+        self.call(LimiterBucket::Read, |raw| raw.list_pack_categories(pack_id))
+            .await
     }
 
     pub async fn add_pack_category<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::AddPackCategoryRequest) -> Result<ResponseValue<types::AddPackCategoryResponse>, Error<types::AddPackCategoryResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.add_pack_category(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.add_pack_category(pack_id, body))
+            .await
     }
 
     pub async fn delete_pack_category<'a>(&'a self, pack_id: NonZeroU64, category_name: &'a str) -> Result<ResponseValue<types::DeletePackCategoryResponse>, Error<types::DeletePackCategoryResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.delete_pack_category(pack_id, category_name).await
+        self.call(LimiterBucket::Write, |raw| raw.delete_pack_category(pack_id, category_name))
+            .await
     }
 
     pub async fn upload_pack_asset<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::UploadPackAssetRequest) -> Result<ResponseValue<types::PackAssetUploadInfo>, Error<types::UploadPackAssetResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.upload_pack_asset(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.upload_pack_asset(pack_id, body))
+            .await
     }
 
     pub async fn upload_pack_source_code<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::UploadPackSourceCodeRequest) -> Result<ResponseValue<types::PackSourceCodeUploadInfo>, Error<types::UploadPackSourceCodeResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.upload_pack_source_code(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.upload_pack_source_code(pack_id, body))
+            .await
     }
 
     pub async fn pack_asset_upload_complete<'a>(&'a self, pack_id: NonZeroU64, pack_asset_id: &'a str, pack_asset_type: types::PackAssetType) -> Result<ResponseValue<types::PackAssetUploadCompleteResponse>, Error<types::PackAssetUploadCompleteResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw
-            .pack_asset_upload_complete(pack_id, pack_asset_id, pack_asset_type)
+        self.call(LimiterBucket::Write, |raw| raw.pack_asset_upload_complete(pack_id, pack_asset_id, pack_asset_type))
             .await
     }
 
     pub async fn pack_source_code_upload_complete<'a>(&'a self, pack_id: NonZeroU64, pack_version: &'a str, body: &'a types::PackSourceCodeUploadCompleteRequest) -> Result<ResponseValue<types::PackSourceCodeUploadCompleteResponse>, Error<types::PackSourceCodeUploadCompleteResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw
-            .pack_source_code_upload_complete(pack_id, pack_version, body)
+        self.call(LimiterBucket::Write, |raw| raw.pack_source_code_upload_complete(pack_id, pack_version, body))
             .await
     }
 
     pub async fn get_pack_source_code<'a>(&'a self, pack_id: NonZeroU64, pack_version: &'a str) -> Result<ResponseValue<types::PackSourceCodeInfo>, Error<types::GetPackSourceCodeResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.get_pack_source_code(pack_id, pack_version).await
+        self.call(LimiterBucket::Read, |raw| raw.get_pack_source_code(pack_id, pack_version))
+            .await
     }
 
     pub async fn list_pack_listings<'a>(&'a self, certified_agents_only: Option<bool>, direction: Option<types::SortDirection>, exclude_public_packs: Option<bool>, install_context: Option<types::PackListingInstallContextType>, limit: Option<NonZeroU64>, only_workspace_id: Option<&'a str>, order_by: Option<types::PackListingsSortBy>, pack_access_types: Option<&'a types::PackAccessTypes>, pack_categories: Option<&'a Vec<types::PackCategoryType>>, pack_entrypoint: Option<types::PackEntrypoint>, pack_ids: Option<&'a Vec<i64>>, page_token: Option<&'a str>, parent_workspace_ids: Option<&'a Vec<String>>, sort_by: Option<types::PackListingsSortBy>) -> Result<ResponseValue<types::PackListingList>, Error<types::ListPackListingsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_pack_listings(certified_agents_only, direction, exclude_public_packs, install_context, limit, only_workspace_id, order_by, pack_access_types, pack_categories, pack_entrypoint, pack_ids, page_token, parent_workspace_ids, sort_by)
+        self.call(LimiterBucket::Read, |raw| raw.list_pack_listings(certified_agents_only, direction, exclude_public_packs, install_context, limit, only_workspace_id, order_by, pack_access_types, pack_categories, pack_entrypoint, pack_ids, page_token, parent_workspace_ids, sort_by))
             .await
     }
 
     pub async fn get_pack_listing<'a>(&'a self, pack_id: NonZeroU64, doc_id: Option<&'a str>, ingestion_id: Option<&'a str>, install_context: Option<types::PackListingInstallContextType>, release_channel: Option<types::IngestionPackReleaseChannel>, workspace_id: Option<&'a str>) -> Result<ResponseValue<types::PackListingDetail>, Error<types::GetPackListingResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .get_pack_listing(pack_id, doc_id, ingestion_id, install_context, release_channel, workspace_id)
+        self.call(LimiterBucket::Read, |raw| raw.get_pack_listing(pack_id, doc_id, ingestion_id, install_context, release_channel, workspace_id))
             .await
     }
 
     pub async fn list_pack_logs<'a>(&'a self, pack_id: NonZeroU64, doc_id: &'a str, after_timestamp: Option<&'a DateTime<Utc>>, before_timestamp: Option<&'a DateTime<Utc>>, limit: Option<NonZeroU64>, log_types: Option<&'a Vec<types::PackLogType>>, order: Option<types::ListPackLogsOrder>, page_token: Option<&'a str>, q: Option<&'a str>, request_ids: Option<&'a Vec<String>>) -> Result<ResponseValue<types::PackLogsList>, Error<types::ListPackLogsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_pack_logs(pack_id, doc_id, after_timestamp, before_timestamp, limit, log_types, order, page_token, q, request_ids)
+        self.call(LimiterBucket::Read, |raw| raw.list_pack_logs(pack_id, doc_id, after_timestamp, before_timestamp, limit, log_types, order, page_token, q, request_ids))
             .await
     }
 
     pub async fn list_ingestion_logs<'a>(&'a self, pack_id: NonZeroU64, tenant_id: &'a str, root_ingestion_id: &'a Uuid, after_timestamp: Option<&'a DateTime<Utc>>, before_timestamp: Option<&'a DateTime<Utc>>, ingestion_execution_id: Option<&'a Uuid>, ingestion_status: Option<types::IngestionStatus>, limit: Option<NonZeroU64>, log_types: Option<&'a Vec<types::PackLogType>>, only_execution_completions: Option<bool>, order: Option<types::ListIngestionLogsOrder>, page_token: Option<&'a str>, q: Option<&'a str>, request_ids: Option<&'a Vec<String>>) -> Result<ResponseValue<types::PackLogsList>, Error<types::ListIngestionLogsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_ingestion_logs(pack_id, tenant_id, root_ingestion_id, after_timestamp, before_timestamp, ingestion_execution_id, ingestion_status, limit, log_types, only_execution_completions, order, page_token, q, request_ids)
+        self.call(LimiterBucket::Read, |raw| raw.list_ingestion_logs(pack_id, tenant_id, root_ingestion_id, after_timestamp, before_timestamp, ingestion_execution_id, ingestion_status, limit, log_types, only_execution_completions, order, page_token, q, request_ids))
             .await
     }
 
     pub async fn list_grouped_pack_logs<'a>(&'a self, pack_id: NonZeroU64, doc_id: &'a str, after_timestamp: Option<&'a DateTime<Utc>>, before_timestamp: Option<&'a DateTime<Utc>>, limit: Option<NonZeroU64>, order: Option<types::ListGroupedPackLogsOrder>, page_token: Option<&'a str>, q: Option<&'a str>) -> Result<ResponseValue<types::GroupedPackLogsList>, Error<types::ListGroupedPackLogsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_grouped_pack_logs(pack_id, doc_id, after_timestamp, before_timestamp, limit, order, page_token, q)
+        self.call(LimiterBucket::Read, |raw| raw.list_grouped_pack_logs(pack_id, doc_id, after_timestamp, before_timestamp, limit, order, page_token, q))
             .await
     }
 
     pub async fn list_grouped_ingestion_logs<'a>(&'a self, pack_id: NonZeroU64, tenant_id: &'a str, root_ingestion_id: &'a Uuid, after_timestamp: Option<&'a DateTime<Utc>>, before_timestamp: Option<&'a DateTime<Utc>>, ingestion_execution_id: Option<&'a Uuid>, limit: Option<NonZeroU64>, order: Option<types::ListGroupedIngestionLogsOrder>, page_token: Option<&'a str>, q: Option<&'a str>) -> Result<ResponseValue<types::GroupedPackLogsList>, Error<types::ListGroupedIngestionLogsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_grouped_ingestion_logs(pack_id, tenant_id, root_ingestion_id, after_timestamp, before_timestamp, ingestion_execution_id, limit, order, page_token, q)
+        self.call(LimiterBucket::Read, |raw| raw.list_grouped_ingestion_logs(pack_id, tenant_id, root_ingestion_id, after_timestamp, before_timestamp, ingestion_execution_id, limit, order, page_token, q))
             .await
     }
 
     pub async fn list_ingestion_batch_executions<'a>(&'a self, pack_id: NonZeroU64, tenant_id: &'a str, root_ingestion_id: &'a Uuid, datasource: Option<&'a str>, execution_type: Option<types::IngestionExecutionType>, include_deleted_ingestions: Option<bool>, ingestion_execution_id: Option<&'a str>, ingestion_id: Option<&'a str>, ingestion_status: Option<types::IngestionStatus>, limit: Option<NonZeroU64>, page_token: Option<&'a str>) -> Result<ResponseValue<types::IngestionBatchExecutionsList>, Error<types::ListIngestionBatchExecutionsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_ingestion_batch_executions(pack_id, tenant_id, root_ingestion_id, datasource, execution_type, include_deleted_ingestions, ingestion_execution_id, ingestion_id, ingestion_status, limit, page_token)
+        self.call(LimiterBucket::Read, |raw| raw.list_ingestion_batch_executions(pack_id, tenant_id, root_ingestion_id, datasource, execution_type, include_deleted_ingestions, ingestion_execution_id, ingestion_id, ingestion_status, limit, page_token))
             .await
     }
 
     pub async fn list_ingestion_parent_items<'a>(&'a self, pack_id: NonZeroU64, tenant_id: &'a str, root_ingestion_id: &'a Uuid, ingestion_execution_id: &'a Uuid, ingestion_id: &'a Uuid, ingestion_status: Option<types::IngestionStatus>, limit: Option<NonZeroU64>, page_token: Option<&'a str>) -> Result<ResponseValue<types::IngestionParentItemsList>, Error<types::ListIngestionParentItemsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_ingestion_parent_items(pack_id, tenant_id, root_ingestion_id, ingestion_execution_id, ingestion_id, ingestion_status, limit, page_token)
+        self.call(LimiterBucket::Read, |raw| raw.list_ingestion_parent_items(pack_id, tenant_id, root_ingestion_id, ingestion_execution_id, ingestion_id, ingestion_status, limit, page_token))
             .await
     }
 
     pub async fn get_pack_log_details<'a>(&'a self, pack_id: NonZeroU64, tenant_id: &'a str, root_ingestion_id: &'a Uuid, log_id: &'a str, details_key: &'a str) -> Result<ResponseValue<types::PackLogDetails>, Error<types::GetPackLogDetailsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .get_pack_log_details(pack_id, tenant_id, root_ingestion_id, log_id, details_key)
+        self.call(LimiterBucket::Read, |raw| raw.get_pack_log_details(pack_id, tenant_id, root_ingestion_id, log_id, details_key))
             .await
     }
 
     pub async fn list_pack_featured_docs<'a>(&'a self, pack_id: NonZeroU64) -> Result<ResponseValue<types::PackFeaturedDocsResponse>, Error<types::ListPackFeaturedDocsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw.list_pack_featured_docs(pack_id).await
+        self.call(LimiterBucket::Read, |raw| raw.list_pack_featured_docs(pack_id))
+            .await
     }
 
     pub async fn update_pack_featured_docs<'a>(&'a self, pack_id: NonZeroU64, body: &'a types::UpdatePackFeaturedDocsRequest) -> Result<ResponseValue<types::UpdatePackFeaturedDocsResponse>, Error<types::UpdatePackFeaturedDocsResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.update_pack_featured_docs(pack_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.update_pack_featured_docs(pack_id, body))
+            .await
     }
 
     pub async fn add_go_link<'a>(&'a self, organization_id: &'a str, body: &'a types::AddGoLinkRequest) -> Result<ResponseValue<types::AddGoLinkResult>, Error<types::AddGoLinkResponse>> {
-        self.limiter.write.until_ready().await;
-        self.raw.add_go_link(organization_id, body).await
+        self.call(LimiterBucket::Write, |raw| raw.add_go_link(organization_id, body))
+            .await
     }
 
     pub async fn list_agent_session_ids<'a>(&'a self, tenant_id: &'a str, agent_instance_id: &'a Uuid, after_timestamp: Option<&'a DateTime<Utc>>, agent_session_id: Option<&'a Uuid>, before_timestamp: Option<&'a DateTime<Utc>>, limit: Option<NonZeroU64>, log_types: Option<&'a Vec<types::PackLogType>>, order: Option<types::ListAgentSessionIdsOrder>, page_token: Option<&'a str>, q: Option<&'a str>, request_ids: Option<&'a Vec<String>>) -> Result<ResponseValue<types::PackLogsList>, Error<types::ListAgentSessionIdsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_agent_session_ids(tenant_id, agent_instance_id, after_timestamp, agent_session_id, before_timestamp, limit, log_types, order, page_token, q, request_ids)
+        self.call(LimiterBucket::Read, |raw| raw.list_agent_session_ids(tenant_id, agent_instance_id, after_timestamp, agent_session_id, before_timestamp, limit, log_types, order, page_token, q, request_ids))
             .await
     }
 
     pub async fn list_agent_logs<'a>(&'a self, tenant_id: &'a str, agent_instance_id: &'a Uuid, after_timestamp: Option<&'a DateTime<Utc>>, agent_session_id: Option<&'a Uuid>, before_timestamp: Option<&'a DateTime<Utc>>, limit: Option<NonZeroU64>, log_types: Option<&'a Vec<types::PackLogType>>, order: Option<types::ListAgentLogsOrder>, page_token: Option<&'a str>, q: Option<&'a str>, request_ids: Option<&'a Vec<String>>) -> Result<ResponseValue<types::PackLogsList>, Error<types::ListAgentLogsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_agent_logs(tenant_id, agent_instance_id, after_timestamp, agent_session_id, before_timestamp, limit, log_types, order, page_token, q, request_ids)
+        self.call(LimiterBucket::Read, |raw| raw.list_agent_logs(tenant_id, agent_instance_id, after_timestamp, agent_session_id, before_timestamp, limit, log_types, order, page_token, q, request_ids))
             .await
     }
 
     pub async fn get_agent_pack_log_details<'a>(&'a self, tenant_id: &'a str, agent_instance_id: &'a Uuid, log_id: &'a str, details_key: &'a str) -> Result<ResponseValue<types::PackLogDetails>, Error<types::GetAgentPackLogDetailsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .get_agent_pack_log_details(tenant_id, agent_instance_id, log_id, details_key)
+        self.call(LimiterBucket::Read, |raw| raw.get_agent_pack_log_details(tenant_id, agent_instance_id, log_id, details_key))
             .await
     }
     pub async fn table_refs(&self, doc_id: &str) -> Result<Vec<TableReference>, Error<types::ListTablesResponse>> {
@@ -878,9 +908,7 @@ impl Client {
 
     #[allow(clippy::too_many_arguments)]
     pub async fn list_rows_correct<'a, T: DeserializeOwned + ValueFormatProvider>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, limit: Option<NonZeroU64>, page_token: Option<&'a str>, query: Option<&'a str>, sort_by: Option<types::RowsSortBy>, sync_token: Option<&'a str>, use_column_names: Option<bool>, visible_only: Option<bool>) -> Result<ResponseValue<ItemsList<T>>, Error<types::ListRowsResponse>> {
-        self.limiter.read.until_ready().await;
-        self.raw
-            .list_rows_correct(doc_id, table_id_or_name, limit, page_token, query, sort_by, sync_token, use_column_names, visible_only)
+        self.call(LimiterBucket::Read, |raw| raw.list_rows_correct(doc_id, table_id_or_name, limit, page_token, query, sort_by, sync_token, use_column_names, visible_only))
             .await
     }
 
@@ -914,9 +942,7 @@ impl Client {
     }
 
     pub async fn upsert_rows_correct<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, disable_parsing: Option<bool>, body: &'a types::RowsUpsert) -> Result<ResponseValue<RowsUpsertResultCorrect>, Error<types::UpsertRowsResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw
-            .upsert_rows_correct(doc_id, table_id_or_name, disable_parsing, body)
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.upsert_rows_correct(doc_id, table_id_or_name, disable_parsing, body))
             .await
     }
 
@@ -1008,10 +1034,1155 @@ impl Client {
         Ok(rows.into_iter().flatten().collect())
     }
 
+    /// Uploads an image file as a Pack asset and returns the asset ID (which can be passed to [`Client::update_pack`])
+    pub async fn upload_pack_asset_file(&self, pack_id: NonZeroU64, kind: types::PackAssetType, path: impl AsRef<Path>) -> Result<PackAssetFile, UploadPackAssetFileError> {
+        self.upload_pack_asset_file_with(&reqwest::Client::new(), pack_id, kind, path.as_ref())
+            .await
+    }
+
+    async fn upload_pack_asset_file_with(&self, http: &reqwest::Client, pack_id: NonZeroU64, kind: types::PackAssetType, path: &Path) -> Result<PackAssetFile, UploadPackAssetFileError> {
+        use UploadPackAssetFileError::*;
+
+        let filename = handle_opt!(path.file_name().and_then(|name| name.to_str()), FilenameInvalid, path: path.to_path_buf()).to_string();
+        let mime_type = handle_opt!(image_mime_type(path), MimeTypeUnknown, path: path.to_path_buf()).to_string();
+        let content = handle!(read(path).await, ReadFailed, path: path.to_path_buf());
+        let body = types::UploadPackAssetRequest {
+            filename: filename.clone(),
+            image_hash: sha256_hex(&content),
+            mime_type: mime_type.clone(),
+            pack_asset_type: kind,
+        };
+        let upload_info = handle!(self.upload_pack_asset(pack_id, &body).await, UploadPackAssetFailed, path: path.to_path_buf()).into_inner();
+        handle!(upload_to_signed_url(http, &upload_info.upload_url, &upload_info.headers, content).await, UploadFailed, path: path.to_path_buf());
+        let pack_asset_id = handle_opt!(pack_asset_id_from_path_name(&upload_info.pack_asset_uploaded_path_name), PackAssetIdNotFound, path_name: upload_info.pack_asset_uploaded_path_name.clone());
+        let complete = handle!(self.pack_asset_upload_complete(pack_id, pack_asset_id, kind).await, PackAssetUploadCompleteFailed, path: path.to_path_buf()).into_inner();
+
+        Ok(PackAssetFile {
+            asset_id: complete.asset_id,
+            filename,
+            mime_type,
+        })
+    }
+
+    /// Reconciles the listing images of the Pack with a local [`PackAssetsDir`]: uploads the new example and agent images (see [`plan_pack_images`]), then updates the Pack to reference exactly the local images.
+    ///
+    /// The logo and the cover are uploaded if they are present locally and differ from the current ones by file name (see [`needs_pack_image_upload`]), or if `force` is set. They are left unchanged otherwise.
+    pub async fn sync_pack_assets(&self, pack_id: NonZeroU64, dir: &PackAssetsDir, force: bool) -> Result<SyncPackAssetsResult, SyncPackAssetsError> {
+        use SyncPackAssetsError::*;
+        use types::PackAssetType::*;
+
+        let http = reqwest::Client::new();
+        let pack = handle!(self.get_pack(pack_id).await, GetPackFailed).into_inner();
+        let mut uploaded = Vec::new();
+
+        let logo = match &dir.logo {
+            Some(path) if needs_pack_image_upload(path, pack.logo_url.as_deref(), force) => {
+                let file = handle!(
+                    self.upload_pack_asset_file_with(&http, pack_id, Logo, path)
+                        .await,
+                    UploadFailed
+                );
+                uploaded.push(file.clone());
+                Some(file.into())
+            }
+            _ => None,
+        };
+        let cover = match &dir.cover {
+            Some(path) if needs_pack_image_upload(path, pack.cover_url.as_deref(), force) => {
+                let file = handle!(
+                    self.upload_pack_asset_file_with(&http, pack_id, Cover, path)
+                        .await,
+                    UploadFailed
+                );
+                uploaded.push(file.clone());
+                Some(file.into())
+            }
+            _ => None,
+        };
+
+        let mut image_lists = Vec::with_capacity(2);
+        for (kind, local, current) in [
+            (ExampleImage, &dir.example_images, &pack.example_images),
+            (AgentImage, &dir.agent_images, &pack.agent_images),
+        ] {
+            let mut images = Vec::new();
+            for action in plan_pack_images(local, current, force) {
+                match action {
+                    PackImageAction::Keep(image) => images.push(types::ImageFileForUpdatePackRequest {
+                        asset_id: image.asset_id,
+                        filename: image.filename,
+                        mime_type: image.mime_type,
+                    }),
+                    PackImageAction::Upload(path) => {
+                        let file = handle!(
+                            self.upload_pack_asset_file_with(&http, pack_id, kind, &path)
+                                .await,
+                            UploadFailed
+                        );
+                        uploaded.push(file.clone());
+                        images.push(file.into());
+                    }
+                }
+            }
+            let changed = images.len() != current.len()
+                || images
+                    .iter()
+                    .zip(current.iter())
+                    .any(|(image, current)| image.asset_id != current.asset_id);
+            image_lists.push(changed.then_some(images));
+        }
+        let agent_images = image_lists.pop().flatten();
+        let example_images = image_lists.pop().flatten();
+
+        let body = types::UpdatePackRequest {
+            agent_description: None,
+            agent_images,
+            agent_short_description: None,
+            cover,
+            description: None,
+            example_images,
+            logo,
+            name: None,
+            overall_rate_limit: None,
+            pack_entrypoints: None,
+            per_connection_rate_limit: None,
+            privacy_policy_url: None,
+            short_description: None,
+            source_code_visibility: None,
+            support_email: None,
+            terms_of_service_url: None,
+        };
+        let is_noop = body.logo.is_none() && body.cover.is_none() && body.example_images.is_none() && body.agent_images.is_none();
+        let pack = if is_noop {
+            pack
+        } else {
+            handle!(self.update_pack(pack_id, &body).await, UpdatePackFailed).into_inner()
+        };
+
+        Ok(SyncPackAssetsResult {
+            pack,
+            uploaded,
+        })
+    }
+
+    /// Fetches the permissions, makers, categories and pending invitations of a Pack
+    pub async fn get_pack_access_state(&self, pack_id: NonZeroU64) -> Result<PackAccessState, GetPackAccessStateError> {
+        use GetPackAccessStateError::*;
+        let permissions = handle!(self.get_pack_permissions(pack_id).await, GetPackPermissionsFailed)
+            .into_inner()
+            .items;
+        let makers = handle!(self.list_pack_makers(pack_id).await, ListPackMakersFailed)
+            .into_inner()
+            .makers;
+        let categories = handle!(self.list_pack_categories(pack_id).await, ListPackCategoriesFailed)
+            .into_inner()
+            .categories;
+        let mut invitations = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let page = handle!(
+                self.list_pack_invitations(pack_id, None, page_token.as_deref())
+                    .await,
+                ListPackInvitationsFailed
+            )
+            .into_inner();
+            invitations.extend(page.items);
+            match page.next_page_token {
+                Some(next_page_token) => page_token = Some(next_page_token),
+                None => break,
+            }
+        }
+        Ok(PackAccessState {
+            permissions,
+            makers,
+            categories,
+            invitations,
+        })
+    }
+
+    /// Computes the changes that make the access of a Pack match the `desired` config (use [`Client::apply_pack_access_plan`] to apply them)
+    pub async fn plan_pack_access(&self, pack_id: NonZeroU64, desired: &PackAccessConfig) -> Result<PackAccessPlan, GetPackAccessStateError> {
+        let current = self.get_pack_access_state(pack_id).await?;
+        Ok(PackAccessPlan::new(pack_id, desired, &current))
+    }
+
+    /// Applies the changes in order. Stops at the first failed change; the changes before it remain applied, so the plan should be recomputed before retrying.
+    pub async fn apply_pack_access_plan(&self, plan: &PackAccessPlan) -> Result<(), ApplyPackAccessPlanError> {
+        use ApplyPackAccessPlanError::*;
+        use PackAccessChange::*;
+        let pack_id = plan.pack_id;
+        for change in &plan.changes {
+            match change {
+                SetPermission(body) => {
+                    handle!(self.add_pack_permission(pack_id, body).await, AddPackPermissionFailed, principal: pack_principal_key(&body.principal));
+                }
+                DeletePermission {
+                    permission_id,
+                    ..
+                } => {
+                    handle!(self.delete_pack_permission(pack_id, permission_id).await, DeletePackPermissionFailed, permission_id: permission_id.clone());
+                }
+                AddMaker {
+                    login_id,
+                } => {
+                    let body = types::AddPackMakerRequest {
+                        login_id: login_id.clone(),
+                    };
+                    handle!(self.add_pack_maker(pack_id, &body).await, AddPackMakerFailed, login_id: login_id.clone());
+                }
+                DeleteMaker {
+                    login_id,
+                } => {
+                    handle!(self.delete_pack_maker(pack_id, login_id).await, DeletePackMakerFailed, login_id: login_id.clone());
+                }
+                AddCategory {
+                    category_name,
+                } => {
+                    let body = types::AddPackCategoryRequest {
+                        category_name: category_name.clone(),
+                    };
+                    handle!(self.add_pack_category(pack_id, &body).await, AddPackCategoryFailed, category_name: category_name.clone());
+                }
+                DeleteCategory {
+                    category_name,
+                } => {
+                    handle!(self.delete_pack_category(pack_id, category_name).await, DeletePackCategoryFailed, category_name: category_name.clone());
+                }
+                CreateInvitation(body) => {
+                    handle!(self.create_pack_invitation(pack_id, body).await, CreatePackInvitationFailed, email: body.email.clone());
+                }
+                UpdateInvitation {
+                    invitation_id,
+                    access,
+                    ..
+                } => {
+                    let uuid = handle!(Uuid::parse_str(invitation_id), InvitationIdInvalid, invitation_id: invitation_id.clone());
+                    let body = types::UpdatePackInvitationRequest {
+                        access: *access,
+                    };
+                    handle!(self.update_pack_invitation(pack_id, &uuid, &body).await, UpdatePackInvitationFailed, invitation_id: uuid);
+                }
+                DeleteInvitation {
+                    invitation_id,
+                    ..
+                } => {
+                    let uuid = handle!(Uuid::parse_str(invitation_id), InvitationIdInvalid, invitation_id: invitation_id.clone());
+                    handle!(self.delete_pack_invitation(pack_id, &uuid).await, DeletePackInvitationFailed, invitation_id: uuid);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetches the permissions (all pages) and the ACL settings of a doc
+    pub async fn get_doc_sharing_state(&self, doc_id: &str) -> Result<DocSharingState, GetDocSharingStateError> {
+        use GetDocSharingStateError::*;
+        let permissions = handle!(
+            paginate_all(move |page_token| async move {
+                self.get_permissions(doc_id, None, page_token.as_deref())
+                    .await
+                    .map(|response| response.into_inner())
+            })
+            .await,
+            GetPermissionsFailed
+        );
+        let settings = handle!(self.get_acl_settings(doc_id).await, GetAclSettingsFailed).into_inner();
+        Ok(DocSharingState {
+            permissions,
+            settings,
+        })
+    }
+
+    /// Computes the changes that make the sharing of every doc match its config (use [`Client::apply_sharing_plan`] to apply them)
+    pub async fn plan_sharing(&self, desired: &BTreeMap<DocId, DocSharingConfig>) -> Result<SharingPlan, PlanSharingError> {
+        use PlanSharingError::*;
+        let responses = join_all(desired.iter().map(|(doc_id, config)| async move {
+            let result = self.get_doc_sharing_state(doc_id).await;
+            (result, doc_id, config)
+        }))
+        .await;
+        let results = responses.into_iter().map(|(result, doc_id, config)| {
+            let current = handle!(result, ItemError, item: doc_id.clone());
+            Ok(DocSharingPlan::new(doc_id.clone(), config, &current))
+        });
+        let docs = handle_iter!(results, GetDocSharingStateFailed);
+        Ok(SharingPlan {
+            docs,
+        })
+    }
+
+    /// Applies the plan and returns an audit report of every change. If `dry_run` is set, nothing is changed and every change is reported as planned.
+    ///
+    /// The docs are processed concurrently, and the changes of each doc in order. A failed change skips the remaining changes of its doc (but not of the other docs), and the error contains the report.
+    pub async fn apply_sharing_plan(&self, plan: &SharingPlan, dry_run: bool) -> Result<SharingReport, ApplySharingPlanError> {
+        use ApplySharingPlanError::*;
+        let responses = join_all(plan.docs.iter().map(|doc| async move {
+            let mut entries = Vec::with_capacity(doc.changes.len());
+            let mut error_opt = None;
+            for change in &doc.changes {
+                let status = if dry_run {
+                    SharingChangeStatus::Planned
+                } else if error_opt.is_some() {
+                    SharingChangeStatus::Skipped
+                } else {
+                    match self.apply_sharing_change(&doc.doc_id, change).await {
+                        Ok(()) => SharingChangeStatus::Applied,
+                        Err(error) => {
+                            error_opt = Some(error);
+                            SharingChangeStatus::Failed
+                        }
+                    }
+                };
+                entries.push(SharingReportEntry {
+                    doc_id: doc.doc_id.clone(),
+                    description: change.to_string(),
+                    change: change.clone(),
+                    status,
+                });
+            }
+            (
+                entries,
+                error_opt.map(|source| ItemError {
+                    item: doc.doc_id.clone(),
+                    source,
+                }),
+            )
+        }))
+        .await;
+        let mut entries = Vec::new();
+        let mut errors = Vec::new();
+        for (doc_entries, error_opt) in responses {
+            entries.extend(doc_entries);
+            errors.extend(error_opt);
+        }
+        let report = SharingReport {
+            dry_run,
+            entries,
+        };
+        if errors.is_empty() {
+            Ok(report)
+        } else {
+            Err(ApplySharingChangesFailed {
+                source: ErrVec::new(errors),
+                report,
+            })
+        }
+    }
+
+    async fn apply_sharing_change(&self, doc_id: &str, change: &SharingChange) -> Result<(), ApplySharingChangeError> {
+        use ApplySharingChangeError::*;
+        use SharingChange::*;
+        match change {
+            AddPermission(body) => {
+                handle!(self.add_permission(doc_id, body).await, AddPermissionFailed, principal: added_principal_key(&body.principal));
+            }
+            UpdatePermission {
+                request,
+                ..
+            } => {
+                handle!(self.add_permission(doc_id, request).await, AddPermissionFailed, principal: added_principal_key(&request.principal));
+            }
+            DeletePermission {
+                permission_id,
+                ..
+            } => {
+                handle!(self.delete_permission(doc_id, permission_id).await, DeletePermissionFailed, permission_id: permission_id.clone());
+            }
+            UpdateSettings(body) => {
+                handle!(self.update_acl_settings(doc_id, body).await, UpdateAclSettingsFailed);
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists every doc of the workspace and/or the folder (all workspaces and folders if both are `None`), and groups the docs by folder
+    pub async fn doc_tree(&self, workspace_id: Option<&str>, folder_id: Option<&str>, options: &DocTreeOptions) -> Result<DocTree, ClientDocTreeError> {
+        use ClientDocTreeError::*;
+        let is_owner = options.is_owner;
+        let is_published = options.is_published;
+        let query = options.query.as_deref();
+        let source_doc = options.source_doc.as_deref();
+        let docs = handle!(
+            paginate_all(move |page_token| async move {
+                self.list_docs(folder_id, None, is_owner, is_published, None, None, page_token.as_deref(), query, source_doc, workspace_id)
+                    .await
+                    .map(|response| response.into_inner())
+            })
+            .await,
+            ListDocsFailed
+        );
+        let mut tree = DocTree::new(workspace_id.map(ToOwned::to_owned), folder_id.map(ToOwned::to_owned), docs);
+        if options.with_folder_details {
+            let responses = join_all(tree.folders.iter().map(|folder| async move {
+                let result = self.get_folder(&folder.folder_id).await;
+                (result, folder.folder_id.clone())
+            }))
+            .await;
+            let results = responses.into_iter().map(|(result, folder_id)| {
+                let folder = handle!(result, ItemError, item: folder_id);
+                Ok(folder.into_inner())
+            });
+            let details = handle_iter!(results, GetFolderFailed);
+            for (folder, details) in tree.folders.iter_mut().zip(details) {
+                folder.details = Some(details);
+            }
+        }
+        Ok(tree)
+    }
+
+    /// Copies the template doc and applies the `params` to the copy (see [`DocTemplate`]).
+    ///
+    /// The steps are applied in order, and the first failed step skips the remaining ones. To resume a failed run, pass its report (from [`ApplyDocTemplateError::ApplyDocTemplateStepFailed`]) as `previous`: the applied steps are not repeated.
+    pub async fn apply_doc_template(&self, template: &DocTemplate, params: &TemplateParams, previous: Option<&DocTemplateReport>, options: &ApplyDocTemplateOptions) -> Result<DocTemplateReport, ApplyDocTemplateError> {
+        use ApplyDocTemplateError::*;
+        let mut doc_id = previous.and_then(|previous| previous.doc_id.clone());
+        let mut entries = Vec::new();
+        let mut failure_opt = None;
+        for step in template.steps() {
+            let mut error = None;
+            let status = if previous.is_some_and(|previous| previous.is_applied(&step)) {
+                DocTemplateStepStatus::Applied
+            } else if failure_opt.is_some() {
+                DocTemplateStepStatus::Skipped
+            } else {
+                match self
+                    .apply_doc_template_step(&mut doc_id, &step, template, params, options)
+                    .await
+                {
+                    Ok(()) => DocTemplateStepStatus::Applied,
+                    Err(source) => {
+                        error = Some(source.to_string());
+                        failure_opt = Some((step.clone(), source));
+                        DocTemplateStepStatus::Failed
+                    }
+                }
+            };
+            entries.push(DocTemplateReportEntry {
+                step,
+                status,
+                error,
+            });
+        }
+        let report = DocTemplateReport {
+            doc_id,
+            entries,
+        };
+        match failure_opt {
+            None => Ok(report),
+            Some((step, source)) => Err(ApplyDocTemplateStepFailed {
+                step,
+                source,
+                report,
+            }),
+        }
+    }
+
+    async fn apply_doc_template_step(&self, doc_id_opt: &mut Option<DocId>, step: &DocTemplateStep, template: &DocTemplate, params: &TemplateParams, options: &ApplyDocTemplateOptions) -> Result<(), ApplyDocTemplateStepError> {
+        use ApplyDocTemplateStepError::*;
+        use DocTemplateStep::*;
+        if let CopyDoc = step {
+            let body = types::DocCreate {
+                folder_id: template.folder_id.clone(),
+                initial_page: None,
+                source_doc: Some(template.source_doc.clone()),
+                timezone: template.timezone.clone(),
+                title: template
+                    .title
+                    .as_ref()
+                    .map(|title| substitute_params(title, params)),
+            };
+            let doc = handle!(self.create_doc(&body).await, CreateDocFailed, source_doc: template.source_doc.clone()).into_inner();
+            *doc_id_opt = Some(doc.id);
+            return Ok(());
+        }
+        let doc_id = handle_opt!(doc_id_opt.as_deref(), DocNotCopied);
+        let wait = |request_id: String| async move {
+            self.wait_for_mutation(&request_id, options.max_attempts, options.delay_secs)
+                .await
+        };
+        match step {
+            CopyDoc => {}
+            WaitForDoc => {
+                let delay = Duration::from_secs(options.delay_secs);
+                for _ in 0..options.max_attempts {
+                    match self.get_doc(doc_id).await {
+                        Ok(_) => return Ok(()),
+                        Err(error) if matches!(error.status().map(|code| code.as_u16()), Some(404)) => {}
+                        Err(error) => {
+                            return Err(GetDocFailed {
+                                doc_id: doc_id.to_owned(),
+                                source: Box::new(error),
+                            });
+                        }
+                    }
+                    sleep(delay).await;
+                }
+                return Err(DocNotAccessible {
+                    doc_id: doc_id.to_owned(),
+                    attempts: options.max_attempts,
+                });
+            }
+            UpdateTitle => {
+                let body = types::DocUpdate {
+                    icon_name: None,
+                    title: template
+                        .title
+                        .as_ref()
+                        .map(|title| substitute_params(title, params)),
+                };
+                handle!(self.update_doc(doc_id, &body).await, UpdateDocFailed, doc_id: doc_id.to_owned());
+            }
+            SubstitutePageNames => {
+                let pages = handle!(
+                    paginate_all(move |page_token| async move {
+                        self.list_pages(doc_id, None, page_token.as_deref())
+                            .await
+                            .map(|response| response.into_inner())
+                    })
+                    .await,
+                    ListPagesFailed,
+                    doc_id: doc_id.to_owned()
+                );
+                for page in pages {
+                    let name = substitute_params(&page.name, params);
+                    if name == page.name {
+                        continue;
+                    }
+                    let body = PageUpdateCorrect {
+                        name: Some(name),
+                        ..PageUpdateCorrect::default()
+                    };
+                    let result = handle!(self.update_page_correct(doc_id, &page.id, &body).await, UpdatePageFailed, page_id_or_name: page.id).into_inner();
+                    handle!(wait(result.request_id).await, WaitForMutationFailed);
+                }
+            }
+            UpdatePage {
+                page_id_or_name,
+            } => {
+                let page = handle_opt!(template.pages.iter().find(|page| &page.page_id_or_name == page_id_or_name), StepNotInTemplate, step: step.clone());
+                let substitute = |text: &Option<String>| text.as_ref().map(|text| substitute_params(text, params));
+                let body = PageUpdateCorrect {
+                    content_update: page
+                        .content
+                        .as_ref()
+                        .map(|content| types::PageContentUpdate {
+                            canvas_content: types::PageContent {
+                                content: substitute_params(&content.content, params),
+                                format: content.format,
+                            },
+                            element_id: None,
+                            insertion_mode: types::PageContentInsertionMode::Replace,
+                        }),
+                    name: substitute(&page.name),
+                    subtitle: substitute(&page.subtitle),
+                    ..PageUpdateCorrect::default()
+                };
+                let page_id_or_name = page.page_id_or_name(params);
+                let result = handle!(
+                    self.update_page_correct(doc_id, &page_id_or_name, &body)
+                        .await,
+                    UpdatePageFailed,
+                    page_id_or_name
+                )
+                .into_inner();
+                handle!(wait(result.request_id).await, WaitForMutationFailed);
+            }
+            SeedTable {
+                table_id_or_name,
+            } => {
+                let table = handle_opt!(template.tables.iter().find(|table| &table.table_id_or_name == table_id_or_name), StepNotInTemplate, step: step.clone());
+                if table.reset {
+                    let delete_options = RowsWhereOptions {
+                        allow_more_than_max_rows: true,
+                        max_attempts: options.max_attempts,
+                        delay_secs: options.delay_secs,
+                        ..RowsWhereOptions::default()
+                    };
+                    handle!(self.delete_rows_where(doc_id, table_id_or_name, |_| true, &delete_options).await, DeleteRowsWhereFailed, table_id_or_name: table_id_or_name.clone());
+                }
+                if !table.rows.is_empty() {
+                    let body = types::RowsUpsert {
+                        key_columns: table.key_columns.clone(),
+                        rows: table.rows(params),
+                    };
+                    let result = handle!(self.upsert_rows_correct(doc_id, table_id_or_name, None, &body).await, UpsertRowsFailed, table_id_or_name: table_id_or_name.clone()).into_inner();
+                    handle!(wait(result.request_id).await, WaitForMutationFailed);
+                }
+            }
+            AddPermission {
+                principal,
+            } => {
+                let body = handle_opt!(
+                    template
+                        .permissions
+                        .iter()
+                        .find(|permission| &added_principal_key(&permission.principal) == principal),
+                    StepNotInTemplate,
+                    step: step.clone()
+                );
+                handle!(self.add_permission(doc_id, body).await, AddPermissionFailed, principal: principal.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Walks every doc of the workspace and reports the docs that are published, shared with anyone with the link, or shared with external domains or users, along with the access of every user
+    pub async fn audit_workspace_sharing(&self, workspace_id: &str, options: &SharingAuditOptions) -> Result<SharingAuditReport, AuditWorkspaceSharingError> {
+        use AuditWorkspaceSharingError::*;
+        let docs = handle!(
+            paginate_all(move |page_token| async move {
+                self.list_docs(None, None, None, None, None, None, page_token.as_deref(), None, None, Some(workspace_id))
+                    .await
+                    .map(|response| response.into_inner())
+            })
+            .await,
+            ListDocsFailed
+        );
+        let members = handle!(
+            paginate_all(move |page_token| async move {
+                self.list_workspace_members(workspace_id, None, page_token.as_deref())
+                    .await
+                    .map(|response| response.into_inner())
+            })
+            .await,
+            ListWorkspaceMembersFailed
+        );
+        let responses = join_all(docs.into_iter().map(|doc| async move {
+            let result = self.audit_doc_sharing(&doc.id).await;
+            (result, doc)
+        }))
+        .await;
+        let results = responses.into_iter().map(|(result, doc)| {
+            let (permissions, metadata) = handle!(result, ItemError, item: doc.id.clone());
+            Ok((doc, permissions, metadata))
+        });
+        let docs = handle_iter!(results, AuditDocSharingFailed);
+        Ok(SharingAuditReport::new(workspace_id.to_string(), docs, members, options))
+    }
+
+    async fn audit_doc_sharing(&self, doc_id: &str) -> Result<(Vec<types::Permission>, types::AclMetadata), AuditDocSharingError> {
+        use AuditDocSharingError::*;
+        let permissions = handle!(
+            paginate_all(move |page_token| async move {
+                self.get_permissions(doc_id, None, page_token.as_deref())
+                    .await
+                    .map(|response| response.into_inner())
+            })
+            .await,
+            GetPermissionsFailed
+        );
+        let metadata = handle!(self.get_sharing_metadata(doc_id).await, GetSharingMetadataFailed).into_inner();
+        Ok((permissions, metadata))
+    }
+
+    /// Extracts a daily time series of every metric of the source. The date range is clamped to the days that have computed analytics, split into windows of [`AnalyticsQuery::window_days`], and every window is paginated.
+    pub async fn analytics_time_series(&self, query: &AnalyticsQuery) -> Result<AnalyticsTimeSeries, AnalyticsTimeSeriesError> {
+        use AnalyticsSource::*;
+        use AnalyticsTimeSeriesError::*;
+        use types::AnalyticsScale::Daily;
+
+        let last_updated = handle!(self.get_analytics_last_updated().await, GetAnalyticsLastUpdatedFailed).into_inner();
+        let until_date = query
+            .until_date
+            .min(query.source.last_updated(&last_updated));
+        let mut points = Vec::new();
+        for (since, until) in analytics_windows(query.since_date, until_date, query.window_days) {
+            let (since, until) = (&since, &until);
+            match &query.source {
+                Docs {
+                    workspace_id,
+                    doc_ids,
+                    is_published,
+                } => {
+                    let items = handle!(
+                        paginate_all(move |page_token| async move {
+                            self.list_doc_analytics_correct(None, doc_ids.as_ref(), *is_published, None, None, page_token.as_deref(), None, Some(Daily), Some(since), Some(until), workspace_id.as_deref())
+                                .await
+                                .map(|response| response.into_inner())
+                        })
+                        .await,
+                        ListDocAnalyticsFailed,
+                        since_date: *since,
+                        until_date: *until
+                    );
+                    points.extend(items.into_iter().flat_map(Vec::<AnalyticsPoint>::from));
+                }
+                Pages {
+                    doc_id,
+                } => {
+                    let items = handle!(
+                        paginate_all(move |page_token| async move {
+                            self.list_page_analytics(doc_id, None, page_token.as_deref(), Some(since), Some(until))
+                                .await
+                                .map(|response| response.into_inner())
+                        })
+                        .await,
+                        ListPageAnalyticsFailed,
+                        since_date: *since,
+                        until_date: *until
+                    );
+                    points.extend(items.into_iter().flat_map(Vec::<AnalyticsPoint>::from));
+                }
+                Packs {
+                    workspace_id,
+                    pack_ids,
+                    is_published,
+                } => {
+                    let items = handle!(
+                        paginate_all(move |page_token| async move {
+                            self.list_pack_analytics(None, *is_published, None, None, pack_ids.as_ref(), page_token.as_deref(), None, Some(Daily), Some(since), Some(until), workspace_id.as_deref())
+                                .await
+                                .map(|response| response.into_inner())
+                        })
+                        .await,
+                        ListPackAnalyticsFailed,
+                        since_date: *since,
+                        until_date: *until
+                    );
+                    points.extend(items.into_iter().flat_map(Vec::<AnalyticsPoint>::from));
+                }
+                PackFormulas {
+                    pack_id,
+                } => {
+                    let items = handle!(
+                        paginate_all(move |page_token| async move {
+                            self.list_pack_formula_analytics(*pack_id, None, None, None, None, None, page_token.as_deref(), Some(Daily), Some(since), Some(until))
+                                .await
+                                .map(|response| response.into_inner())
+                        })
+                        .await,
+                        ListPackFormulaAnalyticsFailed,
+                        since_date: *since,
+                        until_date: *until
+                    );
+                    points.extend(items.into_iter().flat_map(Vec::<AnalyticsPoint>::from));
+                }
+            }
+        }
+        points.sort_by(|a, b| (&a.entity_id, a.date, &a.metric).cmp(&(&b.entity_id, b.date, &b.metric)));
+        Ok(AnalyticsTimeSeries {
+            since_date: query.since_date,
+            until_date,
+            points,
+        })
+    }
+
+    /// Reconstructs an ingestion run: every batch execution of the root ingestion with its parent items and grouped logs
+    pub async fn inspect_ingestion_run(&self, pack_id: NonZeroU64, tenant_id: &str, root_ingestion_id: &Uuid) -> Result<IngestionRun, InspectIngestionRunError> {
+        use InspectIngestionRunError::*;
+
+        let executions = handle!(
+            paginate_all(move |page_token| async move {
+                self.list_ingestion_batch_executions(pack_id, tenant_id, root_ingestion_id, None, None, None, None, None, None, None, page_token.as_deref())
+                    .await
+                    .map(|response| response.into_inner())
+            })
+            .await,
+            ListIngestionBatchExecutionsFailed
+        );
+
+        let mut nodes = Vec::with_capacity(executions.len());
+        for execution in executions {
+            let ingestion_execution_id = handle!(Uuid::parse_str(&execution.ingestion_execution_id), ExecutionIdInvalid, id: execution.ingestion_execution_id.clone());
+            let ingestion_id = handle!(Uuid::parse_str(&execution.ingestion_id), IngestionIdInvalid, id: execution.ingestion_id.clone());
+            let parent_items = handle!(
+                paginate_all(move |page_token| async move {
+                    self.list_ingestion_parent_items(pack_id, tenant_id, root_ingestion_id, &ingestion_execution_id, &ingestion_id, None, None, page_token.as_deref())
+                        .await
+                        .map(|response| response.into_inner())
+                })
+                .await,
+                ListIngestionParentItemsFailed,
+                ingestion_execution_id
+            );
+            let logs = handle!(
+                paginate_all(move |page_token| async move {
+                    self.list_grouped_ingestion_logs(pack_id, tenant_id, root_ingestion_id, None, None, Some(&ingestion_execution_id), None, Some(types::ListGroupedIngestionLogsOrder::Asc), page_token.as_deref(), None)
+                        .await
+                        .map(|response| response.into_inner())
+                })
+                .await,
+                ListGroupedIngestionLogsFailed,
+                ingestion_execution_id
+            );
+            nodes.push(IngestionExecutionNode::new(execution, parent_items, logs));
+        }
+
+        Ok(IngestionRun {
+            root_ingestion_id: root_ingestion_id.to_string(),
+            executions: nodes,
+        })
+    }
+
+    /// Returns the IDs of the agent sessions that have logs in the time range
+    pub async fn agent_session_ids(&self, tenant_id: &str, agent_instance_id: &Uuid, after_timestamp: Option<&DateTime<Utc>>, before_timestamp: Option<&DateTime<Utc>>) -> Result<Vec<String>, Error<types::ListAgentSessionIdsResponse>> {
+        let logs = paginate_all(move |page_token| async move {
+            self.list_agent_session_ids(tenant_id, agent_instance_id, after_timestamp, None, before_timestamp, None, None, None, page_token.as_deref(), None, None)
+                .await
+                .map(|response| response.into_inner())
+        })
+        .await?;
+        let mut session_ids = Vec::new();
+        for session_id in logs
+            .iter()
+            .filter_map(|log| log.context().agent_session_id.clone())
+        {
+            if !session_ids.contains(&session_id) {
+                session_ids.push(session_id);
+            }
+        }
+        Ok(session_ids)
+    }
+
+    /// Reconstructs the timeline of an agent session. If `with_details` is set, fetches the details of every log that has them.
+    pub async fn inspect_agent_session(&self, tenant_id: &str, agent_instance_id: &Uuid, agent_session_id: &Uuid, with_details: bool) -> Result<AgentSessionTimeline, InspectAgentSessionError> {
+        use InspectAgentSessionError::*;
+
+        let logs = handle!(
+            paginate_all(move |page_token| async move {
+                self.list_agent_logs(tenant_id, agent_instance_id, None, Some(agent_session_id), None, None, None, Some(types::ListAgentLogsOrder::Asc), page_token.as_deref(), None, None)
+                    .await
+                    .map(|response| response.into_inner())
+            })
+            .await,
+            ListAgentLogsFailed
+        );
+
+        let details = if with_details {
+            let responses = join_all(
+                logs.iter()
+                    .filter(|log| log.has_details())
+                    .map(|log| async move {
+                        let context = log.context();
+                        let result = self
+                            .get_agent_pack_log_details(tenant_id, agent_instance_id, &context.log_id, &context.details_key)
+                            .await;
+                        (result, context.log_id.clone())
+                    }),
+            )
+            .await;
+            let results = responses.into_iter().map(|(result, log_id)| {
+                let details = handle!(result, ItemError, item: log_id.clone());
+                Ok((log_id, details.into_inner()))
+            });
+            handle_iter!(results, GetAgentPackLogDetailsFailed)
+        } else {
+            Vec::new()
+        };
+
+        Ok(AgentSessionTimeline::new(agent_session_id.to_string(), logs, details))
+    }
+
+    /// Polls the mutation status until the mutation is completed. Coda may return 404 for a mutation that has not been registered yet, so 404 is treated as "not completed".
+    pub async fn wait_for_mutation(&self, request_id: &str, max_attempts: usize, delay_secs: u64) -> Result<types::MutationStatus, WaitForMutationError> {
+        use WaitForMutationError::*;
+
+        let delay = Duration::from_secs(delay_secs);
+        for attempt in 1..=max_attempts {
+            match self.get_mutation_status(request_id).await {
+                Ok(status) if status.completed => return Ok(status.into_inner()),
+                Ok(_) => {}
+                Err(error) if matches!(error.status().map(|code| code.as_u16()), Some(404)) => {}
+                Err(error) => {
+                    return Err(RequestFailed {
+                        attempt,
+                        request_id: request_id.to_string(),
+                        source: Box::new(error),
+                    });
+                }
+            }
+            sleep(delay).await;
+        }
+
+        Err(MutationIncomplete {
+            attempts: max_attempts,
+            request_id: request_id.to_string(),
+        })
+    }
+
+    /// Deletes the rows that match the `predicate`, in chunks of `options.chunk_size`, waiting for every chunk to be completed before deleting the next one.
+    ///
+    /// Refuses to delete anything if more than `options.max_rows` rows match (unless `options.allow_more_than_max_rows` is set). If `options.dry_run` is set, returns the IDs of all matching rows without deleting them (even if there are more than `options.max_rows` of them). If a chunk fails, the error contains the rows that were deleted before the failure.
+    pub async fn delete_rows_where(&self, doc_id: &str, table_id: &str, predicate: impl Fn(&RichRow) -> bool, options: &RowsWhereOptions) -> Result<RowsWhereResult, DeleteRowsWhereError> {
+        use DeleteRowsWhereError::*;
+
+        let rows = handle!(
+            self.rows_correct::<RichRow>(doc_id, table_id, None, None, None, Some(false), Some(false))
+                .await,
+            RowsCorrectFailed
+        );
+        let row_ids = rows
+            .into_iter()
+            .filter(|row| predicate(row))
+            .map(|row| row.id)
+            .collect::<Vec<_>>();
+        if options.dry_run {
+            return Ok(RowsWhereResult {
+                row_ids,
+                request_ids: Vec::new(),
+                dry_run: true,
+            });
+        }
+
+        let max_rows = options.max_rows;
+        handle_bool!(row_ids.len() > max_rows && !options.allow_more_than_max_rows, TooManyRows, count: row_ids.len(), max_rows);
+
+        let mut completed = RowsWhereResult::default();
+        for chunk in row_ids.chunks(options.chunk_size.get()) {
+            let body = types::RowsDelete {
+                row_ids: chunk.to_vec(),
+            };
+            let result = handle!(self.delete_rows_correct(doc_id, table_id, &body).await, DeleteRowsFailed, row_ids: chunk.to_vec(), completed).into_inner();
+            handle!(self.wait_for_mutation(&result.request_id, options.max_attempts, options.delay_secs).await, WaitForMutationFailed, row_ids: chunk.to_vec(), completed);
+            completed.row_ids.extend_from_slice(chunk);
+            completed.request_ids.push(result.request_id);
+        }
+
+        Ok(completed)
+    }
+
+    /// Returns the column if it is a button that can be pushed.
+    ///
+    /// The button is considered disabled only if its `disableIf` formula is the constant `true`, because the formulas that depend on the row can't be evaluated on the client.
+    pub async fn get_button_column(&self, doc_id: &str, table_id: &str, column_id: &str) -> Result<types::ColumnDetail, GetButtonColumnError> {
+        use GetButtonColumnError::*;
+
+        let column = handle!(self.get_column(doc_id, table_id, column_id).await, GetColumnFailed).into_inner();
+        let format_type = column.format.format_type();
+        handle_bool!(format_type != ColumnFormatType::Button, ColumnNotButton, column_id: column.id, format_type);
+        if let ColumnFormat::ButtonColumnFormat(format) = &column.format {
+            let disable_if = format.disable_if.as_deref().map(str::trim);
+            handle_bool!(disable_if.is_some_and(|formula| formula.eq_ignore_ascii_case("true")), ButtonDisabled, column_id: column.id);
+        }
+        Ok(column)
+    }
+
+    /// Pushes the button in the `column_id` of the `row_id` and waits for the mutation to complete.
+    ///
+    /// If `options.read_row` is set, also reads the row before the push and after the mutation is completed, so that the caller can see what the button has changed.
+    pub async fn push_button_and_wait(&self, doc_id: &str, table_id: &str, row_id: &str, column_id: &str, options: &PushButtonOptions) -> Result<PushButtonOutcome, PushButtonAndWaitError> {
+        use PushButtonAndWaitError::*;
+
+        handle!(self.get_button_column(doc_id, table_id, column_id).await, GetButtonColumnFailed);
+        let before = if options.read_row {
+            let row = handle!(
+                self.get_row_correct::<RichRow>(doc_id, table_id, row_id, Some(false))
+                    .await,
+                GetRowFailed,
+                row_id
+            );
+            Some(row.into_inner())
+        } else {
+            None
+        };
+        let result = handle!(
+            self.push_button_correct(doc_id, table_id, row_id, column_id)
+                .await,
+            PushButtonFailed,
+            row_id
+        )
+        .into_inner();
+        let mutation_status = handle!(
+            self.wait_for_mutation(&result.request_id, options.max_attempts, options.delay_secs)
+                .await,
+            WaitForMutationFailed,
+            row_id
+        );
+        let after = if options.read_row {
+            let row = handle!(
+                self.get_row_correct::<RichRow>(doc_id, table_id, row_id, Some(false))
+                    .await,
+                GetRowFailed,
+                row_id
+            );
+            Some(row.into_inner())
+        } else {
+            None
+        };
+
+        Ok(PushButtonOutcome {
+            result,
+            mutation_status,
+            before,
+            after,
+        })
+    }
+
+    /// Pushes the button in the `column_id` of every row that matches the `predicate`, in chunks of `options.chunk_size`, waiting for every push of a chunk to complete before pushing the next chunk. Every push goes through the [`Limiter`], so a large table is processed at the rate allowed by Coda.
+    ///
+    /// Refuses to push anything if more than `options.max_rows` rows match (unless `options.allow_more_than_max_rows` is set). If `options.dry_run` is set, returns the IDs of all matching rows without pushing the button (even if there are more than `options.max_rows` of them). If a push fails, the pushes that were already sent are awaited, and the error contains the rows whose pushes were completed before the failure.
+    pub async fn push_button_where(&self, doc_id: &str, table_id: &str, column_id: &str, predicate: impl Fn(&RichRow) -> bool, options: &RowsWhereOptions) -> Result<RowsWhereResult, PushButtonWhereError> {
+        use PushButtonWhereError::*;
+
+        handle!(self.get_button_column(doc_id, table_id, column_id).await, GetButtonColumnFailed);
+        let rows = handle!(
+            self.rows_correct::<RichRow>(doc_id, table_id, None, None, None, Some(false), Some(false))
+                .await,
+            RowsCorrectFailed
+        );
+        let row_ids = rows
+            .into_iter()
+            .filter(|row| predicate(row))
+            .map(|row| row.id)
+            .collect::<Vec<_>>();
+        if options.dry_run {
+            return Ok(RowsWhereResult {
+                row_ids,
+                request_ids: Vec::new(),
+                dry_run: true,
+            });
+        }
+
+        let max_rows = options.max_rows;
+        handle_bool!(row_ids.len() > max_rows && !options.allow_more_than_max_rows, TooManyRows, count: row_ids.len(), max_rows);
+
+        let mut completed = RowsWhereResult::default();
+        for chunk in row_ids.chunks(options.chunk_size.get()) {
+            let mut pushed = Vec::with_capacity(chunk.len());
+            let mut push_error_opt = None;
+            for row_id in chunk {
+                match self
+                    .push_button_correct(doc_id, table_id, row_id, column_id)
+                    .await
+                {
+                    Ok(result) => pushed.push((row_id.clone(), result.into_inner().request_id)),
+                    Err(source) => {
+                        push_error_opt = Some((row_id.clone(), source));
+                        break;
+                    }
+                }
+            }
+            for (row_id, request_id) in pushed {
+                handle!(
+                    self.wait_for_mutation(&request_id, options.max_attempts, options.delay_secs)
+                        .await,
+                    WaitForMutationFailed,
+                    row_id,
+                    completed
+                );
+                completed.row_ids.push(row_id);
+                completed.request_ids.push(request_id);
+            }
+            if let Some((row_id, source)) = push_error_opt {
+                return Err(PushButtonFailed {
+                    row_id,
+                    source: Box::new(source),
+                    completed,
+                });
+            }
+        }
+
+        Ok(completed)
+    }
+
+    /// Checks that the helper table exists and has the key column and the value column (the API can't create tables, so a missing table must be created in the doc)
+    pub async fn validate_control_helper_table(&self, doc_id: &str, helper: &ControlHelperTable) -> Result<ControlHelperTable, ValidateControlHelperTableError> {
+        use ValidateControlHelperTableError::*;
+
+        let table_id = helper.table_id_or_name.as_str();
+        let columns = handle!(
+            paginate_all(move |page_token| async move {
+                self.list_columns(doc_id, table_id, None, page_token.as_deref(), None)
+                    .await
+                    .map(|response| response.into_inner())
+            })
+            .await,
+            ListColumnsFailed,
+            table_id_or_name: helper.table_id_or_name.clone()
+        );
+        let helper = handle!(helper.validate(&columns), ValidateFailed);
+        Ok(helper)
+    }
+
+    /// Returns the value of the `name` from the helper table (`None` if the table has no row for the `name`).
+    ///
+    /// The rows are listed with the column names, so the value column of the `helper` must be a name (use the helper returned by [`Client::validate_control_helper_table`] if it is an ID).
+    pub async fn get_control_like(&self, doc_id: &str, helper: &ControlHelperTable, name: &str) -> Result<Option<types::Value>, GetControlLikeError> {
+        use GetControlLikeError::*;
+
+        let query = helper.query(name);
+        let rows = handle!(
+            self.rows(doc_id, &helper.table_id_or_name, Some(&query), None, None, Some(true), Some(types::ValueFormat::Simple))
+                .await,
+            RowsFailed,
+            name: name.to_owned()
+        );
+        let Some(mut row) = rows.into_iter().next() else {
+            return Ok(None);
+        };
+        let value_opt = row.values.remove(&helper.value_column);
+        match value_opt {
+            None => Err(ValueColumnNotFound {
+                name: name.to_owned(),
+                value_column: helper.value_column.clone(),
+            }),
+            Some(types::CellValue::Value(value)) => Ok(Some(value)),
+            Some(cell_value) => Err(UnexpectedCellValue {
+                name: name.to_owned(),
+                cell_value,
+            }),
+        }
+    }
+
+    /// Writes the `value` of the `name` to the helper table (see [`ControlHelperTable`]), waits for the mutation to complete, and reads the value back.
+    ///
+    /// The value is read back from `options.verify` (usually the named formula or the control that reads the helper table), or from the helper table itself if `options.verify` is `None`. The value is written with `disableParsing`, so that the read-back value can be compared with the written one.
+    pub async fn set_control_like(&self, doc_id: &str, name: &str, value: impl Into<types::Value>, options: &SetControlLikeOptions) -> Result<SetControlLikeResult, SetControlLikeError> {
+        use SetControlLikeError::*;
+
+        let value = value.into();
+        let helper = if options.validate {
+            &handle!(
+                self.validate_control_helper_table(doc_id, &options.helper)
+                    .await,
+                ValidateControlHelperTableFailed
+            )
+        } else {
+            &options.helper
+        };
+        let body = helper.upsert(name, value.clone());
+        let result = handle!(self.upsert_rows_correct(doc_id, &helper.table_id_or_name, Some(true), &body).await, UpsertRowsFailed, name: name.to_owned()).into_inner();
+        handle!(self.wait_for_mutation(&result.request_id, options.max_attempts.get(), options.delay_secs).await, WaitForMutationFailed, name: name.to_owned());
+
+        let delay = Duration::from_secs(options.delay_secs);
+        let max_attempts = options.max_attempts.get();
+        let mut actual = None;
+        for attempt in 1..=max_attempts {
+            actual = match &options.verify {
+                None => handle!(self.get_control_like(doc_id, helper, name).await, GetControlLikeFailed, name: name.to_owned()),
+                Some(WatchTarget::Formula(formula_id_or_name)) => {
+                    let formula = handle!(self.get_formula(doc_id, formula_id_or_name).await, GetFormulaFailed, formula_id_or_name: formula_id_or_name.clone());
+                    Some(formula.into_inner().value)
+                }
+                Some(WatchTarget::Control(control_id_or_name)) => {
+                    let control = handle!(self.get_control(doc_id, control_id_or_name).await, GetControlFailed, control_id_or_name: control_id_or_name.clone());
+                    Some(control.into_inner().value)
+                }
+            };
+            if actual
+                .as_ref()
+                .is_some_and(|actual| is_same_value(actual, &value))
+            {
+                return Ok(SetControlLikeResult {
+                    request_id: result.request_id,
+                    value,
+                    attempts: attempt,
+                });
+            }
+            if attempt < max_attempts {
+                sleep(delay).await;
+            }
+        }
+
+        Err(VerificationFailed {
+            name: name.to_owned(),
+            expected: value,
+            actual,
+        })
+    }
+
     pub async fn update_row_correct<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, row_id_or_name: &'a str, disable_parsing: Option<bool>, body: &'a types::RowUpdate) -> Result<ResponseValue<RowUpdateResultCorrect>, Error<types::UpdateRowResponse>> {
-        self.limiter.write_doc_content.until_ready().await;
-        self.raw
-            .update_row_correct(doc_id, table_id_or_name, row_id_or_name, disable_parsing, body)
+        self.call(LimiterBucket::WriteDocContent, |raw| raw.update_row_correct(doc_id, table_id_or_name, row_id_or_name, disable_parsing, body))
             .await
     }
 }
@@ -1022,6 +2193,138 @@ pub struct UpsertRowsConclusivelyResult<T> {
     pub items: Vec<T>,
 }
 
+/// The options of [`Client::delete_rows_where`] and [`Client::push_button_where`]
+#[derive(Clone, Debug)]
+pub struct RowsWhereOptions {
+    /// The maximum number of matching rows that may be processed at once
+    pub max_rows: usize,
+    /// Process the matching rows even if there are more than `max_rows` of them
+    pub allow_more_than_max_rows: bool,
+    /// Only return the IDs of the matching rows (regardless of `max_rows`)
+    pub dry_run: bool,
+    /// The number of rows whose mutations are sent before waiting for them to complete
+    pub chunk_size: NonZeroUsize,
+    /// The number of mutation status checks per mutation
+    pub max_attempts: usize,
+    pub delay_secs: u64,
+}
+
+impl RowsWhereOptions {
+    pub const DEFAULT_CHUNK_SIZE: NonZeroUsize = NonZeroUsize::new(100).expect("100 is not zero");
+}
+
+impl Default for RowsWhereOptions {
+    fn default() -> Self {
+        Self {
+            max_rows: 100,
+            allow_more_than_max_rows: false,
+            dry_run: false,
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+            max_attempts: 30,
+            delay_secs: 1,
+        }
+    }
+}
+
+/// The result of [`Client::delete_rows_where`] and [`Client::push_button_where`]
+#[derive(Debug, Default)]
+pub struct RowsWhereResult {
+    /// The IDs of the processed rows (or the rows that would be processed, if `dry_run` is set)
+    pub row_ids: Vec<RowId>,
+    /// The request IDs of the completed mutations (one per chunk for the deletions, one per row for the button pushes)
+    pub request_ids: Vec<String>,
+    pub dry_run: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct PushButtonOptions {
+    /// Read the row before the push and after the mutation is completed
+    pub read_row: bool,
+    /// The number of mutation status checks
+    pub max_attempts: usize,
+    pub delay_secs: u64,
+}
+
+impl Default for PushButtonOptions {
+    fn default() -> Self {
+        Self {
+            read_row: true,
+            max_attempts: 30,
+            delay_secs: 1,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PushButtonOutcome {
+    pub result: PushButtonResultCorrect,
+    pub mutation_status: types::MutationStatus,
+    /// The row before the push (`None` if `read_row` is not set)
+    pub before: Option<RichRow>,
+    /// The row after the mutation is completed (`None` if `read_row` is not set)
+    pub after: Option<RichRow>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SetControlLikeOptions {
+    pub helper: ControlHelperTable,
+    /// Validate the helper table before writing the value (also resolves the columns referenced by ID to their names, which the read-back from the helper table needs)
+    pub validate: bool,
+    /// The formula or the control to read the value back from (the helper table if `None`)
+    pub verify: Option<WatchTarget>,
+    /// The number of mutation status checks (and the number of read-back checks)
+    pub max_attempts: NonZeroUsize,
+    pub delay_secs: u64,
+}
+
+impl SetControlLikeOptions {
+    pub const DEFAULT_MAX_ATTEMPTS: NonZeroUsize = NonZeroUsize::new(30).expect("30 is not zero");
+}
+
+impl Default for SetControlLikeOptions {
+    fn default() -> Self {
+        Self {
+            helper: ControlHelperTable::default(),
+            validate: true,
+            verify: None,
+            max_attempts: Self::DEFAULT_MAX_ATTEMPTS,
+            delay_secs: 1,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SetControlLikeResult {
+    pub request_id: String,
+    /// The value that was written and read back
+    pub value: types::Value,
+    /// The number of read-back checks
+    pub attempts: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct ApplyDocTemplateOptions {
+    /// The number of checks while waiting for the copy to become accessible and for every mutation to complete
+    pub max_attempts: usize,
+    pub delay_secs: u64,
+}
+
+impl Default for ApplyDocTemplateOptions {
+    fn default() -> Self {
+        Self {
+            max_attempts: 30,
+            delay_secs: 1,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SyncPackAssetsResult {
+    /// The Pack after the update
+    pub pack: types::Pack,
+    pub uploaded: Vec<PackAssetFile>,
+}
+
 #[derive(Error, Debug)]
 pub enum ClientGetDocDataError {
     #[error("failed to get metadata")]
@@ -1030,6 +2333,12 @@ pub enum ClientGetDocDataError {
     RowsCorrectFailed { source: ErrVec<ItemError<TableId, Box<Error<types::ListRowsResponse>>>> },
 }
 
+#[derive(Error, Debug)]
+pub enum ClientRowGraphError {
+    #[error("failed to get rows")]
+    GetRowFailed { source: ErrVec<ItemError<RowKey, Box<Error<types::GetRowResponse>>>> },
+}
+
 #[derive(Error, Debug)]
 pub enum ClientGetDocMetadataError {
     #[error("failed to get doc")]
@@ -1085,6 +2394,288 @@ pub enum UpsertRowsConclusivelyRichError {
     // EnsureRowsVisibleFailed { request_id: String, row_ids: Vec<String>, source: WaitForRowsError },
 }
 
+#[derive(Error, Debug)]
+pub enum DeleteRowsWhereError {
+    #[error("failed to list rows")]
+    RowsCorrectFailed { source: Box<Error<types::ListRowsResponse>> },
+    #[error("refusing to delete {count} rows because the limit is {max_rows} rows")]
+    TooManyRows { count: usize, max_rows: usize },
+    #[error("failed to delete rows '{row_ids:?}'")]
+    DeleteRowsFailed { row_ids: Vec<RowId>, source: Box<Error<types::DeleteRowsResponse>>, completed: RowsWhereResult },
+    #[error("failed to wait for the deletion of rows '{row_ids:?}'")]
+    WaitForMutationFailed { row_ids: Vec<RowId>, source: WaitForMutationError, completed: RowsWhereResult },
+}
+
+#[derive(Error, Debug)]
+pub enum GetButtonColumnError {
+    #[error("failed to get column")]
+    GetColumnFailed { source: Box<Error<types::GetColumnResponse>> },
+    #[error("column '{column_id}' is not a button (format type is '{format_type}')")]
+    ColumnNotButton { column_id: String, format_type: ColumnFormatType },
+    #[error("button in column '{column_id}' is disabled")]
+    ButtonDisabled { column_id: String },
+}
+
+#[derive(Error, Debug)]
+pub enum PushButtonAndWaitError {
+    #[error("failed to get button column")]
+    GetButtonColumnFailed { source: GetButtonColumnError },
+    #[error("failed to get row '{row_id}'")]
+    GetRowFailed { row_id: String, source: Box<Error<types::GetRowResponse>> },
+    #[error("failed to push button in row '{row_id}'")]
+    PushButtonFailed { row_id: String, source: Box<Error<types::PushButtonResponse>> },
+    #[error("failed to wait for the button push in row '{row_id}'")]
+    WaitForMutationFailed { row_id: String, source: WaitForMutationError },
+}
+
+#[derive(Error, Debug)]
+pub enum PushButtonWhereError {
+    #[error("failed to get button column")]
+    GetButtonColumnFailed { source: GetButtonColumnError },
+    #[error("failed to list rows")]
+    RowsCorrectFailed { source: Box<Error<types::ListRowsResponse>> },
+    #[error("refusing to push button in {count} rows because the limit is {max_rows} rows")]
+    TooManyRows { count: usize, max_rows: usize },
+    #[error("failed to push button in row '{row_id}'")]
+    PushButtonFailed { row_id: RowId, source: Box<Error<types::PushButtonResponse>>, completed: RowsWhereResult },
+    #[error("failed to wait for the button push in row '{row_id}'")]
+    WaitForMutationFailed { row_id: RowId, source: WaitForMutationError, completed: RowsWhereResult },
+}
+
+#[derive(Error, Debug)]
+pub enum ValidateControlHelperTableError {
+    #[error("failed to list columns of table '{table_id_or_name}'")]
+    ListColumnsFailed { table_id_or_name: String, source: Box<Error<types::ListColumnsResponse>> },
+    #[error("failed to validate helper table")]
+    ValidateFailed { source: ControlHelperTableValidateError },
+}
+
+#[derive(Error, Debug)]
+pub enum GetControlLikeError {
+    #[error("failed to list helper rows for '{name}'")]
+    RowsFailed { name: String, source: Box<Error<types::ListRowsResponse>> },
+    #[error("helper row of '{name}' has no value column named '{value_column}'")]
+    ValueColumnNotFound { name: String, value_column: String },
+    #[error("helper value of '{name}' is not a simple value")]
+    UnexpectedCellValue { name: String, cell_value: types::CellValue },
+}
+
+#[derive(Error, Debug)]
+pub enum SetControlLikeError {
+    #[error("failed to validate helper table")]
+    ValidateControlHelperTableFailed { source: ValidateControlHelperTableError },
+    #[error("failed to upsert helper row for '{name}'")]
+    UpsertRowsFailed { name: String, source: Box<Error<types::UpsertRowsResponse>> },
+    #[error("failed to wait for the update of '{name}'")]
+    WaitForMutationFailed { name: String, source: WaitForMutationError },
+    #[error("failed to read back '{name}' from helper table")]
+    GetControlLikeFailed { name: String, source: Box<GetControlLikeError> },
+    #[error("failed to get formula '{formula_id_or_name}'")]
+    GetFormulaFailed { formula_id_or_name: String, source: Box<Error<types::GetFormulaResponse>> },
+    #[error("failed to get control '{control_id_or_name}'")]
+    GetControlFailed { control_id_or_name: String, source: Box<Error<types::GetControlResponse>> },
+    #[error("value of '{name}' was not updated: expected '{expected:?}', got '{actual:?}'")]
+    VerificationFailed { name: String, expected: types::Value, actual: Option<types::Value> },
+}
+
+#[derive(Error, Debug)]
+pub enum ClientDocTreeError {
+    #[error("failed to list docs")]
+    ListDocsFailed { source: Box<Error<types::ListDocsResponse>> },
+    #[error("failed to get folders")]
+    GetFolderFailed { source: ErrVec<ItemError<String, Box<Error<types::GetFolderResponse>>>> },
+}
+
+#[derive(Error, Debug)]
+pub enum ApplyDocTemplateError {
+    #[error("failed to apply step '{step}' of doc template")]
+    ApplyDocTemplateStepFailed { step: DocTemplateStep, source: ApplyDocTemplateStepError, report: DocTemplateReport },
+}
+
+#[derive(Error, Debug)]
+pub enum ApplyDocTemplateStepError {
+    #[error("failed to copy doc '{source_doc}'")]
+    CreateDocFailed { source_doc: DocId, source: Box<Error<types::CreateDocResponse>> },
+    #[error("doc has not been copied")]
+    DocNotCopied,
+    #[error("failed to get doc '{doc_id}'")]
+    GetDocFailed { doc_id: DocId, source: Box<Error<types::GetDocResponse>> },
+    #[error("doc '{doc_id}' is not accessible after {attempts} attempts")]
+    DocNotAccessible { doc_id: DocId, attempts: usize },
+    #[error("failed to update doc '{doc_id}'")]
+    UpdateDocFailed { doc_id: DocId, source: Box<Error<types::UpdateDocResponse>> },
+    #[error("failed to list pages of doc '{doc_id}'")]
+    ListPagesFailed { doc_id: DocId, source: Box<Error<types::ListPagesResponse>> },
+    #[error("failed to update page '{page_id_or_name}'")]
+    UpdatePageFailed { page_id_or_name: String, source: Box<Error<types::UpdatePageResponse>> },
+    #[error("failed to reset table '{table_id_or_name}'")]
+    DeleteRowsWhereFailed { table_id_or_name: String, source: Box<DeleteRowsWhereError> },
+    #[error("failed to seed table '{table_id_or_name}'")]
+    UpsertRowsFailed { table_id_or_name: String, source: Box<Error<types::UpsertRowsResponse>> },
+    #[error("failed to add permission for '{principal}'")]
+    AddPermissionFailed { principal: String, source: Box<Error<types::AddPermissionResponse>> },
+    #[error("failed to wait for the mutation")]
+    WaitForMutationFailed { source: WaitForMutationError },
+    #[error("step '{step}' is not in the template")]
+    StepNotInTemplate { step: DocTemplateStep },
+}
+
+#[derive(Error, Debug)]
+pub enum UploadPackAssetFileError {
+    #[error("file name of '{path}' is not valid UTF-8")]
+    FilenameInvalid { path: PathBuf },
+    #[error("unknown image type of '{path}'")]
+    MimeTypeUnknown { path: PathBuf },
+    #[error("failed to read '{path}'")]
+    ReadFailed { source: io::Error, path: PathBuf },
+    #[error("failed to request upload of '{path}'")]
+    UploadPackAssetFailed { path: PathBuf, source: Box<Error<types::UploadPackAssetResponse>> },
+    #[error("failed to upload '{path}'")]
+    UploadFailed { path: PathBuf, source: UploadToSignedUrlError },
+    #[error("failed to find pack asset ID in '{path_name}'")]
+    PackAssetIdNotFound { path_name: String },
+    #[error("failed to complete upload of '{path}'")]
+    PackAssetUploadCompleteFailed { path: PathBuf, source: Box<Error<types::PackAssetUploadCompleteResponse>> },
+}
+
+#[derive(Error, Debug)]
+pub enum SyncPackAssetsError {
+    #[error("failed to get pack")]
+    GetPackFailed { source: Box<Error<types::GetPackResponse>> },
+    #[error("failed to upload pack asset")]
+    UploadFailed { source: UploadPackAssetFileError },
+    #[error("failed to update pack")]
+    UpdatePackFailed { source: Box<Error<types::UpdatePackResponse>> },
+}
+
+#[derive(Error, Debug)]
+pub enum GetPackAccessStateError {
+    #[error("failed to get pack permissions")]
+    GetPackPermissionsFailed { source: Box<Error<types::GetPackPermissionsResponse>> },
+    #[error("failed to list pack makers")]
+    ListPackMakersFailed { source: Box<Error<types::ListPackMakersResponse>> },
+    #[error("failed to list pack categories")]
+    ListPackCategoriesFailed { source: Box<Error<types::ListPackCategoriesResponse>> },
+    #[error("failed to list pack invitations")]
+    ListPackInvitationsFailed { source: Box<Error<types::ListPackInvitationsResponse>> },
+}
+
+#[derive(Error, Debug)]
+pub enum ApplyPackAccessPlanError {
+    #[error("failed to set permission of '{principal}'")]
+    AddPackPermissionFailed { principal: String, source: Box<Error<types::AddPackPermissionResponse>> },
+    #[error("failed to delete permission '{permission_id}'")]
+    DeletePackPermissionFailed { permission_id: String, source: Box<Error<types::DeletePackPermissionResponse>> },
+    #[error("failed to add maker '{login_id}'")]
+    AddPackMakerFailed { login_id: String, source: Box<Error<types::AddPackMakerResponse>> },
+    #[error("failed to delete maker '{login_id}'")]
+    DeletePackMakerFailed { login_id: String, source: Box<Error<types::DeletePackMakerResponse>> },
+    #[error("failed to add category '{category_name}'")]
+    AddPackCategoryFailed { category_name: String, source: Box<Error<types::AddPackCategoryResponse>> },
+    #[error("failed to delete category '{category_name}'")]
+    DeletePackCategoryFailed { category_name: String, source: Box<Error<types::DeletePackCategoryResponse>> },
+    #[error("failed to invite '{email}'")]
+    CreatePackInvitationFailed { email: String, source: Box<Error<types::CreatePackInvitationResponse>> },
+    #[error("invitation ID '{invitation_id}' is not a UUID")]
+    InvitationIdInvalid { source: uuid::Error, invitation_id: String },
+    #[error("failed to update invitation '{invitation_id}'")]
+    UpdatePackInvitationFailed { invitation_id: Uuid, source: Box<Error<types::UpdatePackInvitationResponse>> },
+    #[error("failed to delete invitation '{invitation_id}'")]
+    DeletePackInvitationFailed { invitation_id: Uuid, source: Box<Error<types::DeletePackInvitationResponse>> },
+}
+
+#[derive(Error, Debug)]
+pub enum GetDocSharingStateError {
+    #[error("failed to get permissions")]
+    GetPermissionsFailed { source: Box<Error<types::GetPermissionsResponse>> },
+    #[error("failed to get ACL settings")]
+    GetAclSettingsFailed { source: Box<Error<types::GetAclSettingsResponse>> },
+}
+
+#[derive(Error, Debug)]
+pub enum PlanSharingError {
+    #[error("failed to get sharing state of {} docs", source.len())]
+    GetDocSharingStateFailed { source: ErrVec<ItemError<DocId, GetDocSharingStateError>> },
+}
+
+#[derive(Error, Debug)]
+pub enum ApplySharingPlanError {
+    #[error("failed to apply sharing changes to {} docs", source.len())]
+    ApplySharingChangesFailed { source: ErrVec<ItemError<DocId, ApplySharingChangeError>>, report: SharingReport },
+}
+
+#[derive(Error, Debug)]
+pub enum ApplySharingChangeError {
+    #[error("failed to add permission for '{principal}'")]
+    AddPermissionFailed { principal: String, source: Box<Error<types::AddPermissionResponse>> },
+    #[error("failed to delete permission '{permission_id}'")]
+    DeletePermissionFailed { permission_id: String, source: Box<Error<types::DeletePermissionResponse>> },
+    #[error("failed to update ACL settings")]
+    UpdateAclSettingsFailed { source: Box<Error<types::UpdateAclSettingsResponse>> },
+}
+
+#[derive(Error, Debug)]
+pub enum AuditWorkspaceSharingError {
+    #[error("failed to list docs")]
+    ListDocsFailed { source: Box<Error<types::ListDocsResponse>> },
+    #[error("failed to list workspace members")]
+    ListWorkspaceMembersFailed { source: Box<Error<types::ListWorkspaceMembersResponse>> },
+    #[error("failed to audit sharing of {} docs", source.len())]
+    AuditDocSharingFailed { source: ErrVec<ItemError<DocId, AuditDocSharingError>> },
+}
+
+#[derive(Error, Debug)]
+pub enum AuditDocSharingError {
+    #[error("failed to get permissions")]
+    GetPermissionsFailed { source: Box<Error<types::GetPermissionsResponse>> },
+    #[error("failed to get sharing metadata")]
+    GetSharingMetadataFailed { source: Box<Error<types::GetSharingMetadataResponse>> },
+}
+
+#[derive(Error, Debug)]
+pub enum AnalyticsTimeSeriesError {
+    #[error("failed to get analytics last updated dates")]
+    GetAnalyticsLastUpdatedFailed { source: Box<Error<types::GetAnalyticsLastUpdatedResponse>> },
+    #[error("failed to list doc analytics from '{since_date}' to '{until_date}'")]
+    ListDocAnalyticsFailed { since_date: NaiveDate, until_date: NaiveDate, source: Box<Error<types::ListDocAnalyticsResponse>> },
+    #[error("failed to list page analytics from '{since_date}' to '{until_date}'")]
+    ListPageAnalyticsFailed { since_date: NaiveDate, until_date: NaiveDate, source: Box<Error<types::ListPageAnalyticsResponse>> },
+    #[error("failed to list pack analytics from '{since_date}' to '{until_date}'")]
+    ListPackAnalyticsFailed { since_date: NaiveDate, until_date: NaiveDate, source: Box<Error<types::ListPackAnalyticsResponse>> },
+    #[error("failed to list pack formula analytics from '{since_date}' to '{until_date}'")]
+    ListPackFormulaAnalyticsFailed { since_date: NaiveDate, until_date: NaiveDate, source: Box<Error<types::ListPackFormulaAnalyticsResponse>> },
+}
+
+#[derive(Error, Debug)]
+pub enum InspectIngestionRunError {
+    #[error("failed to list ingestion batch executions")]
+    ListIngestionBatchExecutionsFailed { source: Box<Error<types::ListIngestionBatchExecutionsResponse>> },
+    #[error("ingestion execution ID '{id}' is not a UUID")]
+    ExecutionIdInvalid { source: uuid::Error, id: String },
+    #[error("ingestion ID '{id}' is not a UUID")]
+    IngestionIdInvalid { source: uuid::Error, id: String },
+    #[error("failed to list parent items of ingestion execution '{ingestion_execution_id}'")]
+    ListIngestionParentItemsFailed { ingestion_execution_id: Uuid, source: Box<Error<types::ListIngestionParentItemsResponse>> },
+    #[error("failed to list logs of ingestion execution '{ingestion_execution_id}'")]
+    ListGroupedIngestionLogsFailed { ingestion_execution_id: Uuid, source: Box<Error<types::ListGroupedIngestionLogsResponse>> },
+}
+
+#[derive(Error, Debug)]
+pub enum InspectAgentSessionError {
+    #[error("failed to list agent logs")]
+    ListAgentLogsFailed { source: Box<Error<types::ListAgentLogsResponse>> },
+    #[error("failed to get agent log details")]
+    GetAgentPackLogDetailsFailed { source: ErrVec<ItemError<String, Box<Error<types::GetAgentPackLogDetailsResponse>>>> },
+}
+
+#[derive(Error, Debug)]
+pub enum WaitForMutationError {
+    #[error("get mutation status '{request_id}' attempt {attempt} failed")]
+    RequestFailed { attempt: usize, request_id: String, source: Box<Error<types::GetMutationStatusResponse>> },
+    #[error("mutation '{request_id}' did not complete after {attempts} attempts")]
+    MutationIncomplete { attempts: usize, request_id: String },
+}
+
 #[derive(Error, Debug)]
 pub enum WaitForRowsError {
     #[error("get row '{row_id}' attempt {attempt} failed")]
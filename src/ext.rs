@@ -1,30 +1,96 @@
-use crate::types::{Column, ColumnList, ControlList, ControlReference, Doc, DocList, FormulaList, FormulaReference, GetTableResponse, ListTablesResponse, NextPageToken, Page, PageList, Row, RowList, TableList, TableReference};
+use crate::types::{Acl, Column, ColumnList, ControlList, ControlReference, Doc, DocAnalyticsMetrics, DocList, FormulaList, FormulaReference, GetTableResponse, GroupedPackLog, GroupedPackLogsList, IngestionBatchExecution, IngestionBatchExecutionsList, IngestionParentItem, IngestionParentItemsList, ListTablesResponse, NextPageToken, PackAnalyticsCollection, PackAnalyticsItem, PackFormulaAnalyticsCollection, PackFormulaAnalyticsItem, PackLog, PackLogsList, Page, PageAnalyticsCollection, PageAnalyticsItem, PageList, Permission, Row, RowList, TableList, TableReference, WorkspaceMembersList, WorkspaceUser};
 use crate::{Error, RawClient, types};
+use chrono::{DateTime, NaiveDate, Utc};
+use errgonomic::handle;
 use progenitor_client::{ClientHooks, ClientInfo, OperationInfo, ResponseValue, encode_path};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::num::NonZeroU64;
+use std::sync::Arc;
 use thiserror::Error;
+mod analytics_series;
 mod build_query_param;
-#[cfg(feature = "time")]
+#[cfg(feature = "chrono")]
+mod chrono_value_parser;
+mod client_builder;
+mod column_format;
+mod control_helper;
+mod display_value_parser;
+mod doc_template;
+mod doc_tree;
 mod duration_value_parser;
 mod impl_from_for_value;
+mod is_same_value;
 mod items_list;
+#[cfg(feature = "jiff")]
+mod jiff_value_parser;
+mod key_pool;
+mod middleware;
+mod pack_access_plan;
+mod pack_assets;
+mod pack_log_tail;
+mod pack_publisher;
 mod parse_cell_value;
 mod parse_rich_value;
+mod push_csv_line;
+mod response_cache;
+mod retry_policy;
 mod rich_rows;
 mod row;
+mod row_graph;
+mod run_inspector;
+mod sharing_audit;
+mod sharing_plan;
+mod signed_upload;
 mod string_or_f64;
+mod telemetry;
+mod typed_row;
+mod typed_value;
 mod value_format_provider;
+mod watcher;
+#[cfg(feature = "webhook-server")]
+mod webhook_server;
+mod webhook_trigger;
+pub use analytics_series::*;
 pub use build_query_param::*;
-#[cfg(feature = "time")]
+#[cfg(feature = "chrono")]
+pub use chrono_value_parser::*;
+pub use client_builder::*;
+pub use control_helper::*;
+pub use display_value_parser::*;
+pub use doc_template::*;
+pub use doc_tree::*;
 pub use duration_value_parser::*;
+pub use is_same_value::*;
 pub use items_list::*;
+#[cfg(feature = "jiff")]
+pub use jiff_value_parser::*;
+pub use key_pool::*;
+pub use middleware::*;
+pub use pack_access_plan::*;
+pub use pack_assets::*;
+pub use pack_log_tail::*;
+pub use pack_publisher::*;
 pub use parse_cell_value::*;
 pub use parse_rich_value::*;
+pub use push_csv_line::*;
+pub use response_cache::*;
+pub use retry_policy::*;
 pub use rich_rows::*;
+pub use row_graph::*;
+pub use run_inspector::*;
+pub use sharing_audit::*;
+pub use sharing_plan::*;
+pub use signed_upload::*;
 pub(crate) use string_or_f64::*;
+pub use telemetry::*;
+pub use typed_row::*;
+pub use typed_value::*;
 pub use value_format_provider::*;
+pub use watcher::*;
+#[cfg(feature = "webhook-server")]
+pub use webhook_server::*;
+pub use webhook_trigger::*;
 pub type DocId = String;
 pub type TableId = String;
 pub type RowId = String;
@@ -57,6 +123,72 @@ impl Default for PaginationState {
         Self::new()
     }
 }
+impl PaginatedResponse<Permission> for Acl {
+    fn items(&self) -> &Vec<Permission> {
+        &self.items
+    }
+    fn next_page_token(&self) -> Option<&NextPageToken> {
+        self.next_page_token.as_ref()
+    }
+    fn into_items(self) -> Vec<Permission> {
+        self.items
+    }
+}
+impl PaginatedResponse<WorkspaceUser> for WorkspaceMembersList {
+    fn items(&self) -> &Vec<WorkspaceUser> {
+        &self.items
+    }
+    fn next_page_token(&self) -> Option<&NextPageToken> {
+        self.next_page_token.as_ref()
+    }
+    fn into_items(self) -> Vec<WorkspaceUser> {
+        self.items
+    }
+}
+impl PaginatedResponse<DocAnalyticsItemCorrect> for DocAnalyticsCollectionCorrect {
+    fn items(&self) -> &Vec<DocAnalyticsItemCorrect> {
+        &self.items
+    }
+    fn next_page_token(&self) -> Option<&NextPageToken> {
+        self.next_page_token.as_ref()
+    }
+    fn into_items(self) -> Vec<DocAnalyticsItemCorrect> {
+        self.items
+    }
+}
+impl PaginatedResponse<PageAnalyticsItem> for PageAnalyticsCollection {
+    fn items(&self) -> &Vec<PageAnalyticsItem> {
+        &self.items
+    }
+    fn next_page_token(&self) -> Option<&NextPageToken> {
+        self.next_page_token.as_ref()
+    }
+    fn into_items(self) -> Vec<PageAnalyticsItem> {
+        self.items
+    }
+}
+impl PaginatedResponse<PackAnalyticsItem> for PackAnalyticsCollection {
+    fn items(&self) -> &Vec<PackAnalyticsItem> {
+        &self.items
+    }
+    fn next_page_token(&self) -> Option<&NextPageToken> {
+        self.next_page_token.as_ref()
+    }
+    fn into_items(self) -> Vec<PackAnalyticsItem> {
+        self.items
+    }
+}
+impl PaginatedResponse<PackFormulaAnalyticsItem> for PackFormulaAnalyticsCollection {
+    fn items(&self) -> &Vec<PackFormulaAnalyticsItem> {
+        &self.items
+    }
+    fn next_page_token(&self) -> Option<&NextPageToken> {
+        self.next_page_token.as_ref()
+    }
+    fn into_items(self) -> Vec<PackFormulaAnalyticsItem> {
+        self.items
+    }
+}
 impl PaginatedResponse<TableReference> for TableList {
     fn items(&self) -> &Vec<TableReference> {
         &self.items
@@ -134,6 +266,50 @@ impl PaginatedResponse<Row> for RowList {
         self.items
     }
 }
+impl PaginatedResponse<PackLog> for PackLogsList {
+    fn items(&self) -> &Vec<PackLog> {
+        &self.items
+    }
+    fn next_page_token(&self) -> Option<&NextPageToken> {
+        self.next_page_token.as_ref()
+    }
+    fn into_items(self) -> Vec<PackLog> {
+        self.items
+    }
+}
+impl PaginatedResponse<GroupedPackLog> for GroupedPackLogsList {
+    fn items(&self) -> &Vec<GroupedPackLog> {
+        &self.items
+    }
+    fn next_page_token(&self) -> Option<&NextPageToken> {
+        self.next_page_token.as_ref()
+    }
+    fn into_items(self) -> Vec<GroupedPackLog> {
+        self.items
+    }
+}
+impl PaginatedResponse<IngestionBatchExecution> for IngestionBatchExecutionsList {
+    fn items(&self) -> &Vec<IngestionBatchExecution> {
+        &self.items
+    }
+    fn next_page_token(&self) -> Option<&NextPageToken> {
+        self.next_page_token.as_ref()
+    }
+    fn into_items(self) -> Vec<IngestionBatchExecution> {
+        self.items
+    }
+}
+impl PaginatedResponse<IngestionParentItem> for IngestionParentItemsList {
+    fn items(&self) -> &Vec<IngestionParentItem> {
+        &self.items
+    }
+    fn next_page_token(&self) -> Option<&NextPageToken> {
+        self.next_page_token.as_ref()
+    }
+    fn into_items(self) -> Vec<IngestionParentItem> {
+        self.items
+    }
+}
 impl<T> PaginatedResponse<T> for ItemsList<T> {
     fn items(&self) -> &Vec<T> {
         &self.items
@@ -147,6 +323,7 @@ impl<T> PaginatedResponse<T> for ItemsList<T> {
 }
 impl RawClient {
     pub const BASE_URL: &'static str = "https://coda.io/apis/v1";
+    /// Panics if the `api_key` contains characters that are not allowed in a header (use [`RawClient::try_new_with_key`] to get an error instead)
     pub fn new_with_key(api_key: &str) -> reqwest::Result<Self> {
         let authorization_header = format!("Bearer {api_key}")
             .parse()
@@ -159,6 +336,31 @@ impl RawClient {
         let client = Self::new_with_client(Self::BASE_URL, client_with_custom_defaults);
         Ok(client)
     }
+    /// Returns an error (instead of panicking) if the `api_key` contains characters that are not allowed in a header
+    pub fn try_new_with_key(api_key: &str) -> Result<Self, TryNewWithKeyError> {
+        use TryNewWithKeyError::*;
+        let authorization_header = handle!(ApiKeySource::Value(api_key.to_owned()).authorization(), AuthorizationFailed);
+        let mut headers = reqwest::header::HeaderMap::with_capacity(1);
+        headers.insert(reqwest::header::AUTHORIZATION, authorization_header);
+        let client_with_custom_defaults = handle!(
+            reqwest::ClientBuilder::new()
+                .default_headers(headers)
+                .build(),
+            BuildFailed
+        );
+        let client = Self::new_with_client(Self::BASE_URL, client_with_custom_defaults);
+        Ok(client)
+    }
+    /// Adds the `middleware` to the end of the chain (see [`Middleware`])
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+    /// Caches the `GET` responses (see [`ResponseCache`]). If the store is shared by clients with different API keys, key the cache by the API key of this client with [`ResponseCache::with_authorization`].
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
     #[allow(clippy::too_many_arguments)]
     pub async fn list_rows_correct<'a, T: DeserializeOwned + ValueFormatProvider>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, limit: Option<NonZeroU64>, page_token: Option<&'a str>, query: Option<&'a str>, sort_by: Option<types::RowsSortBy>, sync_token: Option<&'a str>, use_column_names: Option<bool>, visible_only: Option<bool>) -> Result<ResponseValue<ItemsList<T>>, Error<types::ListRowsResponse>> {
         let url = format!("{}/docs/{}/tables/{}/rows", self.baseurl, encode_path(doc_id), encode_path(table_id_or_name),);
@@ -301,6 +503,132 @@ impl RawClient {
             _ => Err(Error::UnexpectedResponse(response)),
         }
     }
+    ///Delete multiple rows
+    ///
+    ///Deletes the specified rows from the table or view. This endpoint will
+    /// always return a 202. Row deletions are generally processed within
+    /// several seconds.
+    ///
+    ///Sends a `DELETE` request to `/docs/{docId}/tables/{tableIdOrName}/rows`
+    ///
+    ///Arguments:
+    /// - `doc_id`: ID of the doc.
+    /// - `table_id_or_name`: ID or name of the table. Names are discouraged
+    ///   because they're easily prone to being changed by users. If you're
+    ///   using a name, be sure to URI-encode it.
+    /// - `body`: Rows to delete.
+    pub async fn delete_rows_correct<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, body: &'a types::RowsDelete) -> Result<ResponseValue<RowsDeleteResultCorrect>, Error<types::DeleteRowsResponse>> {
+        let url = format!("{}/docs/{}/tables/{}/rows", self.baseurl, encode_path(doc_id), encode_path(table_id_or_name),);
+        let mut header_map = ::reqwest::header::HeaderMap::with_capacity(1usize);
+        header_map.append(::reqwest::header::HeaderName::from_static("api-version"), ::reqwest::header::HeaderValue::from_static(Self::api_version()));
+        #[allow(unused_mut)]
+        let mut request = self
+            .client
+            .delete(url)
+            .header(::reqwest::header::ACCEPT, ::reqwest::header::HeaderValue::from_static("application/json"))
+            .json(&body)
+            .headers(header_map)
+            .build()?;
+        let info = OperationInfo {
+            operation_id: "delete_rows",
+        };
+        self.pre(&mut request, &info).await?;
+        let result = self.exec(request, &info).await;
+        self.post(&result, &info).await?;
+        let response = result?;
+        match response.status().as_u16() {
+            202u16 => ResponseValue::from_response(response).await,
+            400u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            401u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            429u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+    ///List doc analytics
+    ///
+    ///Same as [`RawClient::list_doc_analytics`], but returns
+    /// [`DocAnalyticsCollectionCorrect`], because the generated
+    /// [`types::DocAnalyticsDetails`] is an empty enum that can't be
+    /// deserialized.
+    ///
+    ///Sends a `GET` request to `/analytics/docs`
+    #[allow(clippy::too_many_arguments)]
+    pub async fn list_doc_analytics_correct<'a>(&'a self, direction: Option<types::SortDirection>, doc_ids: Option<&'a Vec<String>>, is_published: Option<bool>, limit: Option<NonZeroU64>, order_by: Option<types::DocAnalyticsOrderBy>, page_token: Option<&'a str>, query: Option<&'a str>, scale: Option<types::AnalyticsScale>, since_date: Option<&'a NaiveDate>, until_date: Option<&'a NaiveDate>, workspace_id: Option<&'a str>) -> Result<ResponseValue<DocAnalyticsCollectionCorrect>, Error<types::ListDocAnalyticsResponse>> {
+        let url = format!("{}/analytics/docs", self.baseurl,);
+        let mut header_map = ::reqwest::header::HeaderMap::with_capacity(1usize);
+        header_map.append(::reqwest::header::HeaderName::from_static("api-version"), ::reqwest::header::HeaderValue::from_static(Self::api_version()));
+        #[allow(unused_mut)]
+        let mut request = self
+            .client
+            .get(url)
+            .header(::reqwest::header::ACCEPT, ::reqwest::header::HeaderValue::from_static("application/json"))
+            .query(&progenitor_client::QueryParam::new("direction", &direction))
+            .query(&progenitor_client::QueryParam::new("docIds", &doc_ids))
+            .query(&progenitor_client::QueryParam::new("isPublished", &is_published))
+            .query(&progenitor_client::QueryParam::new("limit", &limit))
+            .query(&progenitor_client::QueryParam::new("orderBy", &order_by))
+            .query(&progenitor_client::QueryParam::new("pageToken", &page_token))
+            .query(&progenitor_client::QueryParam::new("query", &query))
+            .query(&progenitor_client::QueryParam::new("scale", &scale))
+            .query(&progenitor_client::QueryParam::new("sinceDate", &since_date))
+            .query(&progenitor_client::QueryParam::new("untilDate", &until_date))
+            .query(&progenitor_client::QueryParam::new("workspaceId", &workspace_id))
+            .headers(header_map)
+            .build()?;
+        let info = OperationInfo {
+            operation_id: "list_doc_analytics",
+        };
+        self.pre(&mut request, &info).await?;
+        let result = self.exec(request, &info).await;
+        self.post(&result, &info).await?;
+        let response = result?;
+        match response.status().as_u16() {
+            200u16 => ResponseValue::from_response(response).await,
+            401u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            429u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+    ///Trigger automation
+    ///
+    ///Same as [`RawClient::trigger_webhook_automation`], but returns
+    /// [`WebhookTriggerResultCorrect`], because the generated
+    /// [`types::WebhookTriggerResult`] is an empty enum that can't be
+    /// deserialized.
+    ///
+    ///Sends a `POST` request to `/docs/{docId}/hooks/automation/{ruleId}`
+    pub async fn trigger_webhook_automation_correct<'a>(&'a self, doc_id: &'a str, rule_id: &'a str, body: &'a types::WebhookTriggerPayload) -> Result<ResponseValue<WebhookTriggerResultCorrect>, Error<types::TriggerWebhookAutomationResponse>> {
+        let url = format!("{}/docs/{}/hooks/automation/{}", self.baseurl, encode_path(doc_id), encode_path(rule_id),);
+        let mut header_map = ::reqwest::header::HeaderMap::with_capacity(1usize);
+        header_map.append(::reqwest::header::HeaderName::from_static("api-version"), ::reqwest::header::HeaderValue::from_static(Self::api_version()));
+        #[allow(unused_mut)]
+        let mut request = self
+            .client
+            .post(url)
+            .header(::reqwest::header::ACCEPT, ::reqwest::header::HeaderValue::from_static("application/json"))
+            .json(&body)
+            .headers(header_map)
+            .build()?;
+        let info = OperationInfo {
+            operation_id: "trigger_webhook_automation",
+        };
+        self.pre(&mut request, &info).await?;
+        let result = self.exec(request, &info).await;
+        self.post(&result, &info).await?;
+        let response = result?;
+        match response.status().as_u16() {
+            202u16 => ResponseValue::from_response(response).await,
+            400u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            401u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            422u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            429u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
     ///Update row
     ///
     ///Updates the specified row in the table. This endpoint will always return
@@ -340,7 +668,85 @@ impl RawClient {
             .query(&progenitor_client::QueryParam::new("disableParsing", &disable_parsing))
             .headers(header_map)
             .build()?;
-        let result = self.client.execute(request).await;
+        let info = OperationInfo {
+            operation_id: "update_row",
+        };
+        self.pre(&mut request, &info).await?;
+        let result = self.exec(request, &info).await;
+        self.post(&result, &info).await?;
+        let response = result?;
+        match response.status().as_u16() {
+            202u16 => ResponseValue::from_response(response).await,
+            400u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            401u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            429u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+    ///Update a page
+    ///
+    ///Same as [`RawClient::update_page`], but accepts
+    /// [`PageUpdateCorrect`] and returns [`PageUpdateResultCorrect`], because
+    /// the generated content update and result types have no variants.
+    ///
+    ///Sends a `PUT` request to `/docs/{docId}/pages/{pageIdOrName}`
+    pub async fn update_page_correct<'a>(&'a self, doc_id: &'a str, page_id_or_name: &'a str, body: &'a PageUpdateCorrect) -> Result<ResponseValue<PageUpdateResultCorrect>, Error<types::UpdatePageResponse>> {
+        let url = format!("{}/docs/{}/pages/{}", self.baseurl, encode_path(doc_id), encode_path(page_id_or_name),);
+        let mut header_map = ::reqwest::header::HeaderMap::with_capacity(1usize);
+        header_map.append(::reqwest::header::HeaderName::from_static("api-version"), ::reqwest::header::HeaderValue::from_static(Self::api_version()));
+        #[allow(unused_mut)]
+        let mut request = self
+            .client
+            .put(url)
+            .header(::reqwest::header::ACCEPT, ::reqwest::header::HeaderValue::from_static("application/json"))
+            .json(&body)
+            .headers(header_map)
+            .build()?;
+        let info = OperationInfo {
+            operation_id: "update_page",
+        };
+        self.pre(&mut request, &info).await?;
+        let result = self.exec(request, &info).await;
+        self.post(&result, &info).await?;
+        let response = result?;
+        match response.status().as_u16() {
+            202u16 => ResponseValue::from_response(response).await,
+            400u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            401u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            403u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            404u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            429u16 => Err(Error::ErrorResponse(ResponseValue::from_response(response).await?)),
+            _ => Err(Error::UnexpectedResponse(response)),
+        }
+    }
+
+    ///Push a button
+    ///
+    ///Same as [`RawClient::push_button`], but returns
+    /// [`PushButtonResultCorrect`], because the fields of the generated
+    /// [`types::PushButtonResult`] are private.
+    ///
+    ///Sends a `POST` request to
+    /// `/docs/{docId}/tables/{tableIdOrName}/rows/{rowIdOrName}/buttons/{columnIdOrName}`
+    pub async fn push_button_correct<'a>(&'a self, doc_id: &'a str, table_id_or_name: &'a str, row_id_or_name: &'a str, column_id_or_name: &'a str) -> Result<ResponseValue<PushButtonResultCorrect>, Error<types::PushButtonResponse>> {
+        let url = format!("{}/docs/{}/tables/{}/rows/{}/buttons/{}", self.baseurl, encode_path(doc_id), encode_path(table_id_or_name), encode_path(row_id_or_name), encode_path(column_id_or_name),);
+        let mut header_map = ::reqwest::header::HeaderMap::with_capacity(1usize);
+        header_map.append(::reqwest::header::HeaderName::from_static("api-version"), ::reqwest::header::HeaderValue::from_static(Self::api_version()));
+        #[allow(unused_mut)]
+        let mut request = self
+            .client
+            .post(url)
+            .header(::reqwest::header::ACCEPT, ::reqwest::header::HeaderValue::from_static("application/json"))
+            .headers(header_map)
+            .build()?;
+        let info = OperationInfo {
+            operation_id: "push_button",
+        };
+        self.pre(&mut request, &info).await?;
+        let result = self.exec(request, &info).await;
+        self.post(&result, &info).await?;
         let response = result?;
         match response.status().as_u16() {
             202u16 => ResponseValue::from_response(response).await,
@@ -406,6 +812,77 @@ pub struct RowUpdateResultCorrect {
     #[serde(rename = "requestId")]
     pub request_id: String,
 }
+/// Same as [`types::PageUpdate`], but with the content update (the generated [`types::PageUpdateContentUpdate`] has no variants)
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct PageUpdateCorrect {
+    #[serde(rename = "contentUpdate", default, skip_serializing_if = "Option::is_none")]
+    pub content_update: Option<types::PageContentUpdate>,
+    #[serde(rename = "iconName", default, skip_serializing_if = "Option::is_none")]
+    pub icon_name: Option<String>,
+    #[serde(rename = "imageUrl", default, skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    #[serde(rename = "isHidden", default, skip_serializing_if = "Option::is_none")]
+    pub is_hidden: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+}
+
+/// Same as [`types::PageUpdateResult`], but with the fields (the generated type has no variants)
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(deny_unknown_fields)]
+pub struct PageUpdateResultCorrect {
+    pub id: String,
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+}
+
+///`PushButtonResult`
+///
+/// <details><summary>JSON schema</summary>
+///
+/// ```json
+///{
+///  "description": "The result of a push button.",
+///  "allOf": [
+///    {
+///      "$ref": "#/components/schemas/DocumentMutateResponse"
+///    },
+///    {
+///      "type": "object",
+///      "required": [
+///        "columnId",
+///        "rowId"
+///      ],
+///      "properties": {
+///        "columnId": {
+///          "description": "ID of the column where the button exists.",
+///          "type": "string"
+///        },
+///        "rowId": {
+///          "description": "ID of the row where the button exists.",
+///          "type": "string"
+///        }
+///      },
+///      "additionalProperties": false
+///    }
+///  ],
+///  "x-schema-name": "PushButtonResult"
+///}
+/// ```
+/// </details>
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(deny_unknown_fields)]
+pub struct PushButtonResultCorrect {
+    #[serde(rename = "rowId")]
+    pub row_id: RowId,
+    #[serde(rename = "columnId")]
+    pub column_id: String,
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+}
 ///`RowsUpsertResult`
 ///
 /// <details><summary>JSON schema</summary>
@@ -450,6 +927,103 @@ pub struct RowsUpsertResultCorrect {
     #[serde(rename = "requestId")]
     pub request_id: String,
 }
+///`RowsDeleteResult`
+///
+/// <details><summary>JSON schema</summary>
+///
+/// ```json
+///{
+///  "description": "The result of a rows delete operation.",
+///  "allOf": [
+///    {
+///      "$ref": "#/components/schemas/DocumentMutateResponse"
+///    },
+///    {
+///      "type": "object",
+///      "required": [
+///        "rowIds"
+///      ],
+///      "properties": {
+///        "rowIds": {
+///          "description": "Row IDs to delete.",
+///          "type": "array",
+///          "items": {
+///            "type": "string"
+///          }
+///        }
+///      },
+///      "additionalProperties": false
+///    }
+///  ],
+///  "x-schema-name": "RowsDeleteResult"
+///}
+/// ```
+/// </details>
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(deny_unknown_fields)]
+pub struct RowsDeleteResultCorrect {
+    #[serde(rename = "rowIds")]
+    pub row_ids: Vec<String>,
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+}
+///`WebhookTriggerResult`
+///
+/// <details><summary>JSON schema</summary>
+///
+/// ```json
+///{
+///  "description": "The result of triggering a webhook",
+///  "allOf": [
+///    {
+///      "$ref": "#/components/schemas/DocumentMutateResponse"
+///    },
+///    {
+///      "type": "object",
+///      "additionalProperties": false
+///    }
+///  ],
+///  "x-schema-name": "WebhookTriggerResult"
+///}
+/// ```
+/// </details>
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookTriggerResultCorrect {
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+}
+/// Same as [`types::DocAnalyticsCollection`], but with [`DocAnalyticsItemCorrect`] items
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DocAnalyticsCollectionCorrect {
+    pub items: Vec<DocAnalyticsItemCorrect>,
+    #[serde(rename = "nextPageLink", default, skip_serializing_if = "Option::is_none")]
+    pub next_page_link: Option<types::NextPageLink>,
+    #[serde(rename = "nextPageToken", default, skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<NextPageToken>,
+}
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DocAnalyticsItemCorrect {
+    pub doc: DocAnalyticsDetailsCorrect,
+    pub metrics: Vec<DocAnalyticsMetrics>,
+}
+/// The `DocAnalyticsDetails` schema: the fields of `DocReference` and the analytics metadata of the doc
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DocAnalyticsDetailsCorrect {
+    #[serde(rename = "browserLink")]
+    pub browser_link: String,
+    pub href: String,
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_: types::DocReferenceType,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<types::Icon>,
+    #[serde(rename = "publishedAt", default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<DateTime<Utc>>,
+    pub title: String,
+}
 pub fn format_row_url(doc_id: &str, table_id: &str, row_id: &str) -> String {
     format!("https://coda.io/d/_d{doc_id}#_tu{table_id}/_ru{row_id}")
 }
@@ -0,0 +1,210 @@
+use crate::types::{AnalyticsLastUpdatedResponse, PackAnalyticsItem, PackFormulaAnalyticsItem, PageAnalyticsItem};
+use crate::{DocAnalyticsItemCorrect, DocId, push_csv_line};
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::num::{NonZeroU32, NonZeroU64};
+
+/// The entities to extract the analytics of
+#[derive(Clone, Debug)]
+pub enum AnalyticsSource {
+    /// [`Client::list_doc_analytics_correct`](crate::Client::list_doc_analytics_correct)
+    Docs { workspace_id: Option<String>, doc_ids: Option<Vec<String>>, is_published: Option<bool> },
+    /// [`Client::list_page_analytics`](crate::Client::list_page_analytics)
+    Pages { doc_id: DocId },
+    /// [`Client::list_pack_analytics`](crate::Client::list_pack_analytics)
+    Packs { workspace_id: Option<String>, pack_ids: Option<Vec<i64>>, is_published: Option<bool> },
+    /// [`Client::list_pack_formula_analytics`](crate::Client::list_pack_formula_analytics)
+    PackFormulas { pack_id: NonZeroU64 },
+}
+
+impl AnalyticsSource {
+    /// Returns the last day that has computed analytics for this source
+    pub fn last_updated(&self, last_updated: &AnalyticsLastUpdatedResponse) -> NaiveDate {
+        use AnalyticsSource::*;
+        match self {
+            Docs {
+                ..
+            }
+            | Pages {
+                ..
+            } => last_updated.doc_analytics_last_updated,
+            Packs {
+                ..
+            } => last_updated.pack_analytics_last_updated,
+            PackFormulas {
+                ..
+            } => last_updated.pack_formula_analytics_last_updated,
+        }
+    }
+}
+
+/// A request for a daily time series (see [`Client::analytics_time_series`](crate::Client::analytics_time_series))
+#[derive(Clone, Debug)]
+pub struct AnalyticsQuery {
+    pub source: AnalyticsSource,
+    pub since_date: NaiveDate,
+    /// Inclusive. Clamped to the last day that has computed analytics.
+    pub until_date: NaiveDate,
+    /// The number of days requested per call
+    pub window_days: NonZeroU32,
+}
+
+impl AnalyticsQuery {
+    pub const DEFAULT_WINDOW_DAYS: NonZeroU32 = NonZeroU32::new(30).expect("30 is not zero");
+
+    pub fn new(source: AnalyticsSource, since_date: NaiveDate, until_date: NaiveDate) -> Self {
+        Self {
+            source,
+            since_date,
+            until_date,
+            window_days: Self::DEFAULT_WINDOW_DAYS,
+        }
+    }
+
+    pub fn with_window_days(mut self, window_days: NonZeroU32) -> Self {
+        self.window_days = window_days;
+        self
+    }
+}
+
+/// A single metric value of an entity (doc, page, Pack or Pack formula) on a day
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct AnalyticsPoint {
+    pub entity_id: String,
+    pub entity_name: String,
+    pub date: NaiveDate,
+    /// The metric name as returned by the API (e.g. "views", "totalSessions")
+    pub metric: String,
+    pub value: f64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AnalyticsTimeSeries {
+    pub since_date: NaiveDate,
+    /// The requested `until_date` clamped to the last day that has computed analytics
+    pub until_date: NaiveDate,
+    /// Sorted by entity ID, date and metric
+    pub points: Vec<AnalyticsPoint>,
+}
+
+impl AnalyticsTimeSeries {
+    /// Returns a CSV with one line per point: `entity_id,entity_name,date,metric,value`
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("entity_id,entity_name,date,metric,value\n");
+        for point in &self.points {
+            push_csv_line(
+                &mut csv,
+                &[
+                    &point.entity_id,
+                    &point.entity_name,
+                    &point.date.to_string(),
+                    &point.metric,
+                    &point.value.to_string(),
+                ],
+            );
+        }
+        csv
+    }
+}
+
+impl From<DocAnalyticsItemCorrect> for Vec<AnalyticsPoint> {
+    fn from(item: DocAnalyticsItemCorrect) -> Self {
+        item.metrics
+            .iter()
+            .flat_map(|metrics| metric_points(&item.doc.id, &item.doc.title, metrics.date, metrics))
+            .collect()
+    }
+}
+
+impl From<PageAnalyticsItem> for Vec<AnalyticsPoint> {
+    fn from(item: PageAnalyticsItem) -> Self {
+        item.metrics
+            .iter()
+            .flat_map(|metrics| metric_points(&item.page.id, &item.page.name, metrics.date, metrics))
+            .collect()
+    }
+}
+
+impl From<PackAnalyticsItem> for Vec<AnalyticsPoint> {
+    fn from(item: PackAnalyticsItem) -> Self {
+        let pack_id = item.pack.id.to_string();
+        item.metrics
+            .iter()
+            .flat_map(|metrics| metric_points(&pack_id, &item.pack.name, metrics.date, metrics))
+            .collect()
+    }
+}
+
+impl From<PackFormulaAnalyticsItem> for Vec<AnalyticsPoint> {
+    fn from(item: PackFormulaAnalyticsItem) -> Self {
+        let formula_id = format!("{}:{}", item.formula.type_, item.formula.name);
+        item.metrics
+            .iter()
+            .flat_map(|metrics| metric_points(&formula_id, &item.formula.name, metrics.date, metrics))
+            .collect()
+    }
+}
+
+/// Splits the inclusive date range into inclusive windows of at most `window_days` days
+pub fn analytics_windows(since_date: NaiveDate, until_date: NaiveDate, window_days: NonZeroU32) -> Vec<(NaiveDate, NaiveDate)> {
+    let extra_days = Days::new(u64::from(window_days.get().saturating_sub(1)));
+    let mut windows = Vec::new();
+    let mut start = since_date;
+    while start <= until_date {
+        let end = start
+            .checked_add_days(extra_days)
+            .map_or(until_date, |end| end.min(until_date));
+        windows.push((start, end));
+        match end.succ_opt() {
+            Some(next) => start = next,
+            None => break,
+        }
+    }
+    windows
+}
+
+/// Converts every numeric field of the metrics (and every string field that contains a number, like `revenueUsd`) into a point
+fn metric_points(entity_id: &str, entity_name: &str, date: NaiveDate, metrics: &impl Serialize) -> Vec<AnalyticsPoint> {
+    let Ok(Value::Object(fields)) = serde_json::to_value(metrics) else {
+        return Vec::new();
+    };
+    fields
+        .into_iter()
+        .filter(|(metric, _)| metric != "date")
+        .filter_map(|(metric, value)| {
+            let value = match value {
+                Value::Number(number) => number.as_f64(),
+                Value::String(string) => string.parse().ok(),
+                _ => None,
+            }?;
+            Some(AnalyticsPoint {
+                entity_id: entity_id.to_string(),
+                entity_name: entity_name.to_string(),
+                date,
+                metric,
+                value,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn must_split_range_into_windows() {
+        let date = |day| NaiveDate::from_ymd_opt(2024, 1, day).unwrap();
+        let windows = analytics_windows(date(1), date(25), NonZeroU32::new(10).unwrap());
+        assert_eq!(
+            windows,
+            vec![
+                (date(1), date(10)),
+                (date(11), date(20)),
+                (date(21), date(25))
+            ]
+        );
+        assert!(analytics_windows(date(2), date(1), NonZeroU32::MIN).is_empty());
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{DurationValueParserError, parse_duration_seconds};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeDelta};
+use errgonomic::{handle, handle_opt};
+use thiserror::Error;
+
+pub fn parse_chrono_date_time(text: &str) -> Result<DateTime<FixedOffset>, chrono::ParseError> {
+    DateTime::parse_from_rfc3339(text)
+}
+
+/// Accepts both RFC3339 timestamps (Coda returns dates at midnight in the doc timezone) and ISO 8601 dates
+pub fn parse_chrono_naive_date(text: &str) -> Result<NaiveDate, chrono::ParseError> {
+    match DateTime::parse_from_rfc3339(text) {
+        Ok(date_time) => Ok(date_time.date_naive()),
+        Err(_) => text.parse(),
+    }
+}
+
+/// Accepts both RFC3339 timestamps (Coda returns times with a placeholder date) and ISO 8601 times
+pub fn parse_chrono_naive_time(text: &str) -> Result<NaiveTime, chrono::ParseError> {
+    match DateTime::parse_from_rfc3339(text) {
+        Ok(date_time) => Ok(date_time.time()),
+        Err(_) => text.parse(),
+    }
+}
+
+pub fn parse_chrono_time_delta(text: &str) -> Result<TimeDelta, ParseChronoTimeDeltaError> {
+    use ParseChronoTimeDeltaError::*;
+    let seconds_opt = handle!(parse_duration_seconds(&text), DurationParseFailed);
+    let seconds = handle_opt!(seconds_opt, DurationEmptyInvalid);
+    Ok(handle_opt!(TimeDelta::try_seconds(seconds), DurationOutOfRange, seconds))
+}
+
+#[derive(Debug, Error)]
+pub enum ParseChronoTimeDeltaError {
+    #[error("failed to parse duration value")]
+    DurationParseFailed { source: DurationValueParserError },
+    #[error("duration value is empty")]
+    DurationEmptyInvalid,
+    #[error("duration of '{seconds}' seconds is out of range")]
+    DurationOutOfRange { seconds: i64 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RichValue;
+
+    #[test]
+    fn must_convert_rich_value_to_chrono_types() {
+        let rich_value = serde_json::from_value::<RichValue>(serde_json::json!("2026-10-18T00:00:00.000-07:00")).unwrap();
+        let date = Option::<NaiveDate>::try_from(&rich_value).unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(2026, 10, 18));
+        let empty = serde_json::from_value::<RichValue>(serde_json::json!("")).unwrap();
+        assert_eq!(Option::<TimeDelta>::try_from(empty).unwrap(), None);
+    }
+}
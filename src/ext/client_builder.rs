@@ -0,0 +1,260 @@
+use crate::{Client, Limiter, Middleware, MiddlewareChain, RawClient, ResponseCache, RetryPolicy};
+use errgonomic::handle;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue, InvalidHeaderValue};
+use reqwest::{Proxy, header};
+use std::env::VarError;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use std::{env, fs, io};
+use thiserror::Error;
+
+/// Where [`ClientBuilder`] reads the API key from
+#[derive(Clone, Debug)]
+pub enum ApiKeySource {
+    Value(String),
+    /// The name of an environment variable
+    Env(String),
+    /// A file that contains the key (surrounding whitespace is ignored)
+    File(PathBuf),
+}
+
+impl ApiKeySource {
+    pub fn resolve(&self) -> Result<String, ApiKeySourceResolveError> {
+        use ApiKeySourceResolveError::*;
+        match self {
+            ApiKeySource::Value(api_key) => Ok(api_key.clone()),
+            ApiKeySource::Env(name) => Ok(handle!(env::var(name), EnvVarFailed, name: name.clone())),
+            ApiKeySource::File(path) => {
+                let contents = handle!(fs::read_to_string(path), ReadFileFailed, path: path.clone());
+                Ok(contents.trim().to_owned())
+            }
+        }
+    }
+
+    /// Returns the `Authorization` header value for this key
+    pub fn authorization(&self) -> Result<HeaderValue, ApiKeySourceAuthorizationError> {
+        use ApiKeySourceAuthorizationError::*;
+        let api_key = handle!(self.resolve(), ResolveFailed);
+        let mut value = handle!(HeaderValue::from_str(&format!("Bearer {api_key}")), InvalidHeaderValue);
+        value.set_sensitive(true);
+        Ok(value)
+    }
+}
+
+/// Configures a [`Client`]. Unlike [`Client::new_with_key`], [`ClientBuilder::build`] returns an error (instead of panicking) if the API key is invalid.
+///
+/// Like [`Client::new_with_key`], the built client has no timeouts unless they are set with [`ClientBuilder::with_timeout`] and [`ClientBuilder::with_connect_timeout`].
+///
+/// ```no_run
+/// # use coda_api::{ClientBuilder, RetryPolicy};
+/// # use std::time::Duration;
+/// let client = ClientBuilder::new()
+///     .with_api_key_from_env("CODA_API_KEY")
+///     .with_timeout(Duration::from_secs(30))
+///     .with_retry_policy(RetryPolicy::new(3))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ClientBuilder {
+    base_url: String,
+    api_key: Option<ApiKeySource>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: String,
+    proxy: Option<String>,
+    limiter: Limiter,
+    retry_policy: RetryPolicy,
+    middleware: MiddlewareChain,
+    cache: Option<ResponseCache>,
+}
+
+impl ClientBuilder {
+    pub const DEFAULT_USER_AGENT: &'static str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+    pub fn new() -> Self {
+        Self {
+            base_url: Client::BASE_URL.to_owned(),
+            api_key: None,
+            timeout: None,
+            connect_timeout: None,
+            user_agent: Self::DEFAULT_USER_AGENT.to_owned(),
+            proxy: None,
+            limiter: Limiter::default(),
+            retry_policy: RetryPolicy::default(),
+            middleware: MiddlewareChain::default(),
+            cache: None,
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_api_key_source(mut self, api_key: ApiKeySource) -> Self {
+        self.api_key = Some(api_key);
+        self
+    }
+
+    pub fn with_api_key(self, api_key: impl Into<String>) -> Self {
+        self.with_api_key_source(ApiKeySource::Value(api_key.into()))
+    }
+
+    pub fn with_api_key_from_env(self, name: impl Into<String>) -> Self {
+        self.with_api_key_source(ApiKeySource::Env(name.into()))
+    }
+
+    pub fn with_api_key_from_file(self, path: impl Into<PathBuf>) -> Self {
+        self.with_api_key_source(ApiKeySource::File(path.into()))
+    }
+
+    /// The timeout of the whole request (including reading the response body)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Sends every request through the proxy at `proxy_url` (e.g. `http://localhost:8080`)
+    pub fn with_proxy(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy = Some(proxy_url.into());
+        self
+    }
+
+    pub fn with_limiter(mut self, limiter: Limiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Adds the `middleware` to the end of the chain (see [`Middleware`])
+    pub fn with_middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Caches the `GET` responses (see [`ResponseCache`])
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn build(self) -> Result<Client, ClientBuilderBuildError> {
+        use ClientBuilderBuildError::*;
+        let mut headers = HeaderMap::new();
+        let mut cache_opt = self.cache;
+        if let Some(api_key) = &self.api_key {
+            let authorization = handle!(api_key.authorization(), AuthorizationFailed);
+            cache_opt = cache_opt.map(|cache| cache.with_authorization(&authorization));
+            headers.insert(AUTHORIZATION, authorization);
+        }
+        let user_agent = handle!(HeaderValue::from_str(&self.user_agent), UserAgentInvalid, user_agent: self.user_agent);
+        headers.insert(header::USER_AGENT, user_agent);
+        let mut builder = reqwest::ClientBuilder::new().default_headers(headers);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(proxy_url) = self.proxy {
+            let proxy = handle!(Proxy::all(&proxy_url), ProxyInvalid, proxy_url);
+            builder = builder.proxy(proxy);
+        }
+        let client = handle!(builder.build(), BuildFailed);
+        let mut raw = RawClient::new_with_client(&self.base_url, client);
+        raw.middleware = self.middleware;
+        raw.cache = cache_opt.map(Arc::new);
+        Ok(Client {
+            raw,
+            limiter: Arc::new(self.limiter),
+            retry_policy: self.retry_policy,
+        })
+    }
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ApiKeySourceResolveError {
+    #[error("failed to read the API key from the environment variable '{name}'")]
+    EnvVarFailed { source: VarError, name: String },
+    #[error("failed to read the API key from '{path}'")]
+    ReadFileFailed { source: io::Error, path: PathBuf },
+}
+
+#[derive(Error, Debug)]
+pub enum ApiKeySourceAuthorizationError {
+    #[error("failed to resolve the API key")]
+    ResolveFailed { source: ApiKeySourceResolveError },
+    #[error("API key contains characters that are not allowed in a header")]
+    InvalidHeaderValue { source: InvalidHeaderValue },
+}
+
+#[derive(Error, Debug)]
+pub enum TryNewWithKeyError {
+    #[error("failed to build the authorization header")]
+    AuthorizationFailed { source: ApiKeySourceAuthorizationError },
+    #[error("failed to build the HTTP client")]
+    BuildFailed { source: reqwest::Error },
+}
+
+#[derive(Error, Debug)]
+pub enum ClientBuilderBuildError {
+    #[error("failed to build the authorization header")]
+    AuthorizationFailed { source: ApiKeySourceAuthorizationError },
+    #[error("user agent '{user_agent}' is invalid")]
+    UserAgentInvalid { source: InvalidHeaderValue, user_agent: String },
+    #[error("proxy URL '{proxy_url}' is invalid")]
+    ProxyInvalid { source: reqwest::Error, proxy_url: String },
+    #[error("failed to build the HTTP client")]
+    BuildFailed { source: reqwest::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn must_return_error_for_invalid_api_key() {
+        let result = ClientBuilder::new().with_api_key("invalid\nkey").build();
+        assert!(matches!(
+            result,
+            Err(ClientBuilderBuildError::AuthorizationFailed {
+                source: ApiKeySourceAuthorizationError::InvalidHeaderValue { .. }
+            })
+        ));
+    }
+
+    #[test]
+    fn must_return_error_for_missing_env_var() {
+        let result = ClientBuilder::new()
+            .with_api_key_from_env("CODA_API_TEST_MISSING_ENV_VAR")
+            .build();
+        assert!(matches!(
+            result,
+            Err(ClientBuilderBuildError::AuthorizationFailed {
+                source: ApiKeySourceAuthorizationError::ResolveFailed { .. }
+            })
+        ));
+    }
+}
@@ -0,0 +1,62 @@
+use crate::types::{ColumnFormat, ColumnFormatType, NumericColumnFormatType, ReferenceColumnFormatType, SimpleColumnFormatType};
+
+impl ColumnFormat {
+    /// Returns the declared format type regardless of which untagged variant the format was deserialized into
+    pub fn format_type(&self) -> ColumnFormatType {
+        use ColumnFormat::*;
+        match self {
+            ButtonColumnFormat(_) => ColumnFormatType::Button,
+            CheckboxColumnFormat(_) => ColumnFormatType::Checkbox,
+            DateColumnFormat(_) => ColumnFormatType::Date,
+            DateTimeColumnFormat(_) => ColumnFormatType::DateTime,
+            DurationColumnFormat(_) => ColumnFormatType::Duration,
+            EmailColumnFormat(_) => ColumnFormatType::Email,
+            LinkColumnFormat(_) => ColumnFormatType::Link,
+            CurrencyColumnFormat(_) => ColumnFormatType::Currency,
+            ImageReferenceColumnFormat(_) => ColumnFormatType::ImageReference,
+            NumericColumnFormat(format) => match format.type_ {
+                NumericColumnFormatType::Number => ColumnFormatType::Number,
+                NumericColumnFormatType::Percent => ColumnFormatType::Percent,
+            },
+            ReferenceColumnFormat(format) => match format.type_ {
+                ReferenceColumnFormatType::Person => ColumnFormatType::Person,
+                ReferenceColumnFormatType::Lookup => ColumnFormatType::Lookup,
+            },
+            SelectColumnFormat(_) => ColumnFormatType::Select,
+            SimpleColumnFormat(format) => match format.type_ {
+                SimpleColumnFormatType::Text => ColumnFormatType::Text,
+                SimpleColumnFormatType::Image => ColumnFormatType::Image,
+                SimpleColumnFormatType::Attachments => ColumnFormatType::Attachments,
+                SimpleColumnFormatType::PackObject => ColumnFormatType::PackObject,
+                SimpleColumnFormatType::Reaction => ColumnFormatType::Reaction,
+                SimpleColumnFormatType::Canvas => ColumnFormatType::Canvas,
+                SimpleColumnFormatType::Other => ColumnFormatType::Other,
+            },
+            ScaleColumnFormat(_) => ColumnFormatType::Scale,
+            SliderColumnFormat(_) => ColumnFormatType::Slider,
+            TimeColumnFormat(_) => ColumnFormatType::Time,
+        }
+    }
+
+    pub fn is_array(&self) -> bool {
+        use ColumnFormat::*;
+        match self {
+            ButtonColumnFormat(format) => format.is_array,
+            CheckboxColumnFormat(format) => format.is_array,
+            DateColumnFormat(format) => format.is_array,
+            DateTimeColumnFormat(format) => format.is_array,
+            DurationColumnFormat(format) => format.is_array,
+            EmailColumnFormat(format) => format.is_array,
+            LinkColumnFormat(format) => format.is_array,
+            CurrencyColumnFormat(format) => format.is_array,
+            ImageReferenceColumnFormat(format) => format.is_array,
+            NumericColumnFormat(format) => format.is_array,
+            ReferenceColumnFormat(format) => format.is_array,
+            SelectColumnFormat(format) => format.is_array,
+            SimpleColumnFormat(format) => format.is_array,
+            ScaleColumnFormat(format) => format.is_array,
+            SliderColumnFormat(format) => format.is_array,
+            TimeColumnFormat(format) => format.is_array,
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::types::{CellEdit, Column, RowEdit, RowsUpsert, Value};
+use errgonomic::handle_opt;
+use thiserror::Error;
+
+/// A table that stores the values of "control-like" variables, because the Coda API can't set the values of the controls.
+///
+/// The table must have a key column and a value column (by default, `Key` and `Value`), and is usually hidden from the readers of the doc. The API can't create tables, so the table must be created in the doc once (use [`Client::validate_control_helper_table`](crate::Client::validate_control_helper_table) to check it).
+///
+/// The columns may be referenced by ID or by name, but [`Client::get_control_like`](crate::Client::get_control_like) needs the value column name, so the columns referenced by ID must be resolved with [`ControlHelperTable::validate`] first.
+///
+/// The doc reads the value with a named formula (or the default value of a control) that uses the pattern returned by [`ControlHelperTable::formula`]:
+///
+/// ```text
+/// ControlValues.Filter(Key = "Status").First().Value
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ControlHelperTable {
+    pub table_id_or_name: String,
+    pub key_column: String,
+    pub value_column: String,
+}
+
+impl ControlHelperTable {
+    pub const DEFAULT_TABLE_NAME: &'static str = "ControlValues";
+    pub const DEFAULT_KEY_COLUMN: &'static str = "Key";
+    pub const DEFAULT_VALUE_COLUMN: &'static str = "Value";
+
+    pub fn new(table_id_or_name: impl Into<String>) -> Self {
+        Self {
+            table_id_or_name: table_id_or_name.into(),
+            key_column: Self::DEFAULT_KEY_COLUMN.to_owned(),
+            value_column: Self::DEFAULT_VALUE_COLUMN.to_owned(),
+        }
+    }
+
+    pub fn with_key_column(mut self, key_column: impl Into<String>) -> Self {
+        self.key_column = key_column.into();
+        self
+    }
+
+    pub fn with_value_column(mut self, value_column: impl Into<String>) -> Self {
+        self.value_column = value_column.into();
+        self
+    }
+
+    /// Returns the Coda formula that reads the value of the `name`
+    pub fn formula(&self, name: &str) -> String {
+        format!("{}.Filter({} = {}).First().{}", self.table_id_or_name, self.key_column, quote(name), self.value_column)
+    }
+
+    /// Returns the `list_rows` query that matches the row of the `name`
+    pub fn query(&self, name: &str) -> String {
+        format!("{}:{}", quote(&self.key_column), quote(name))
+    }
+
+    /// Returns the upsert that inserts or updates the row of the `name`
+    pub fn upsert(&self, name: &str, value: Value) -> RowsUpsert {
+        RowsUpsert {
+            key_columns: vec![self.key_column.clone()],
+            rows: vec![RowEdit {
+                cells: vec![
+                    CellEdit {
+                        column: self.key_column.clone(),
+                        value: Value::from(name.to_owned()),
+                    },
+                    CellEdit {
+                        column: self.value_column.clone(),
+                        value,
+                    },
+                ],
+            }],
+        }
+    }
+
+    /// Checks that the `columns` of the table contain the key column and the value column (by ID or name), and returns a copy with both columns referenced by name (the rows returned with `useColumnNames` are keyed by the column names)
+    pub fn validate(&self, columns: &[Column]) -> Result<Self, ControlHelperTableValidateError> {
+        use ControlHelperTableValidateError::*;
+        let find_column_name = |id_or_name: &str| {
+            columns
+                .iter()
+                .find(|column| column.id == id_or_name || column.name == id_or_name)
+                .map(|column| column.name.clone())
+        };
+        let key_column = handle_opt!(find_column_name(&self.key_column), KeyColumnNotFound, table_id_or_name: self.table_id_or_name.clone(), key_column: self.key_column.clone());
+        let value_column = handle_opt!(find_column_name(&self.value_column), ValueColumnNotFound, table_id_or_name: self.table_id_or_name.clone(), value_column: self.value_column.clone());
+        Ok(Self {
+            table_id_or_name: self.table_id_or_name.clone(),
+            key_column,
+            value_column,
+        })
+    }
+}
+
+impl Default for ControlHelperTable {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TABLE_NAME)
+    }
+}
+
+/// Quotes the string for a Coda formula or a `list_rows` query (the JSON string syntax is compatible with both)
+fn quote(value: &str) -> String {
+    serde_json::Value::String(value.to_owned()).to_string()
+}
+
+#[derive(Error, Debug)]
+pub enum ControlHelperTableValidateError {
+    #[error("key column '{key_column}' not found in table '{table_id_or_name}'")]
+    KeyColumnNotFound { table_id_or_name: String, key_column: String },
+    #[error("value column '{value_column}' not found in table '{table_id_or_name}'")]
+    ValueColumnNotFound { table_id_or_name: String, value_column: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn must_quote_names_in_formula_and_query() {
+        let helper = ControlHelperTable::default();
+        assert_eq!(helper.formula("Say \"hi\""), r#"ControlValues.Filter(Key = "Say \"hi\"").First().Value"#);
+        assert_eq!(helper.query("Status"), r#""Key":"Status""#);
+        let upsert = helper.upsert("Status", Value::from(true));
+        assert_eq!(upsert.key_columns, ["Key"]);
+        assert_eq!(upsert.rows[0].cells.len(), 2);
+    }
+
+    #[test]
+    fn must_resolve_column_ids_to_names() {
+        let columns: Vec<Column> = serde_json::from_value(serde_json::json!([
+            {"id": "c-key", "type": "column", "href": "https://coda.io/apis/v1/docs/d/tables/t/columns/c-key", "name": "Key", "format": {"type": "text", "isArray": false}},
+            {"id": "c-value", "type": "column", "href": "https://coda.io/apis/v1/docs/d/tables/t/columns/c-value", "name": "Value", "format": {"type": "text", "isArray": false}}
+        ]))
+        .unwrap();
+        let helper = ControlHelperTable::default().with_value_column("c-value");
+        let resolved = helper.validate(&columns).unwrap();
+        assert_eq!(resolved.key_column, "Key");
+        assert_eq!(resolved.value_column, "Value");
+        assert!(
+            helper
+                .with_key_column("Missing")
+                .validate(&columns)
+                .is_err()
+        );
+    }
+}
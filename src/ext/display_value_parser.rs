@@ -0,0 +1,416 @@
+use crate::types::{ColumnFormat, ColumnFormatType, ValueVariant0};
+use crate::{DurationValueParserError, TypedCurrency, TypedValue, format_duration_seconds, parse_duration_seconds};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use errgonomic::{handle, handle_opt};
+use std::num::ParseFloatError;
+use thiserror::Error;
+
+/// Date formats that Coda offers in the column settings, in the order of preference (used when the column format has no hint)
+const DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%m/%d/%Y",
+    "%m/%d/%y",
+    "%d.%m.%Y",
+    "%B %d, %Y",
+    "%b %d, %Y",
+    "%A, %B %d, %Y",
+    "%a, %b %d, %Y",
+    "%d %B %Y",
+    "%d %b %Y",
+];
+
+/// Time formats that Coda offers in the column settings, in the order of preference (used when the column format has no hint)
+const TIME_FORMATS: &[&str] = &["%I:%M:%S %p", "%I:%M %p", "%H:%M:%S%.f", "%H:%M"];
+
+/// Currency symbols that Coda uses in the display strings of currency columns
+const CURRENCY_SYMBOLS: &[(&str, &str)] = &[
+    ("A$", "AUD"),
+    ("C$", "CAD"),
+    ("R$", "BRL"),
+    ("$", "USD"),
+    ("€", "EUR"),
+    ("£", "GBP"),
+    ("¥", "JPY"),
+    ("₹", "INR"),
+    ("₽", "RUB"),
+    ("₩", "KRW"),
+    ("₺", "TRY"),
+    ("₪", "ILS"),
+];
+
+/// Decodes a value returned with [`ValueFormat::Simple`](crate::types::ValueFormat::Simple), using the column format to interpret the display string
+pub fn decode_simple_value(value: &ValueVariant0, format: &ColumnFormat) -> Result<TypedValue, DecodeSimpleValueError> {
+    use ColumnFormatType::*;
+    use DecodeSimpleValueError::*;
+    let format_type = format.format_type();
+    let text = match value {
+        ValueVariant0::Variant0(text) => text.trim(),
+        ValueVariant0::Variant1(number) => {
+            return Ok(match format_type {
+                Percent => TypedValue::Percent(*number),
+                Currency => TypedValue::Currency(TypedCurrency {
+                    amount: *number,
+                    currency: currency_code_hint(format),
+                }),
+                _ => TypedValue::Number(*number),
+            });
+        }
+        ValueVariant0::Variant2(boolean) => return Ok(TypedValue::Boolean(*boolean)),
+    };
+    if text.is_empty() {
+        return Ok(TypedValue::Null);
+    }
+    let typed_value = match format_type {
+        Number | Slider | Scale => TypedValue::Number(handle!(parse_localized_number(text), ParseNumberFailed)),
+        Percent => TypedValue::Percent(handle!(parse_percent_display(text), ParsePercentFailed)),
+        Currency => TypedValue::Currency(handle!(parse_currency_display(text, Some(format)), ParseCurrencyFailed)),
+        Date => TypedValue::Date(handle!(parse_date_display(text, Some(format)), ParseDateFailed)),
+        DateTime => TypedValue::LocalDateTime(handle!(parse_date_time_display(text, Some(format)), ParseDateTimeFailed)),
+        Time => TypedValue::Time(handle!(parse_time_display(text, Some(format)), ParseTimeFailed)),
+        Duration => {
+            let seconds_opt = handle!(parse_duration_seconds(&text), ParseDurationFailed, value: text);
+            match seconds_opt {
+                Some(seconds) => TypedValue::Duration(handle_opt!(TimeDelta::try_seconds(seconds), DurationOutOfRange, seconds)),
+                None => TypedValue::Null,
+            }
+        }
+        Checkbox => match text {
+            "true" => TypedValue::Boolean(true),
+            "false" => TypedValue::Boolean(false),
+            _ => {
+                return Err(CheckboxInvalid {
+                    value: text.to_owned(),
+                });
+            }
+        },
+        _ => TypedValue::Text(text.to_owned()),
+    };
+    Ok(typed_value)
+}
+
+/// Parses numbers like "1,234.50", "1.234,50", "1 234,5" or "1'234.50"
+pub fn parse_localized_number(text: &str) -> Result<f64, ParseLocalizedNumberError> {
+    use ParseLocalizedNumberError::*;
+    let compact = text
+        .chars()
+        .filter(|char| !char.is_whitespace() && *char != '\'')
+        .collect::<String>();
+    let last_dot = compact.rfind('.');
+    let last_comma = compact.rfind(',');
+    let normalized = match (last_dot, last_comma) {
+        (Some(dot), Some(comma)) if comma > dot => compact.replace('.', "").replace(',', "."),
+        (Some(_), Some(_)) => compact.replace(',', ""),
+        (None, Some(comma)) => {
+            let digits_after_comma = compact.len().saturating_sub(comma).saturating_sub(1);
+            if compact.matches(',').count() == 1 && digits_after_comma != 3 {
+                compact.replace(',', ".")
+            } else {
+                compact.replace(',', "")
+            }
+        }
+        (Some(_), None) if compact.matches('.').count() > 1 => compact.replace('.', ""),
+        _ => compact,
+    };
+    Ok(handle!(normalized.parse::<f64>(), ParseFloatFailed, value: text))
+}
+
+/// Parses percents like "12%" or "12.5 %" into fractions (`0.12`, `0.125`). Values without the percent sign are treated as fractions already.
+pub fn parse_percent_display(text: &str) -> Result<f64, ParsePercentDisplayError> {
+    use ParsePercentDisplayError::*;
+    let trimmed = text.trim();
+    match trimmed.strip_suffix('%') {
+        Some(number_str) => {
+            let number = handle!(parse_localized_number(number_str), ParseNumberFailed);
+            Ok(number / 100.0)
+        }
+        None => Ok(handle!(parse_localized_number(trimmed), ParseNumberFailed)),
+    }
+}
+
+/// Parses amounts like "$1,234.50", "-$5.00", "($5.00)", "1.234,50 €" or "USD 1,234.50". The currency is taken from the symbol or code in the text, falling back to the currency code of the column format.
+pub fn parse_currency_display(text: &str, format: Option<&ColumnFormat>) -> Result<TypedCurrency, ParseCurrencyDisplayError> {
+    use ParseCurrencyDisplayError::*;
+    let trimmed = text.trim();
+    let first_digit = handle_opt!(trimmed.find(|char: char| char.is_ascii_digit()), DigitsNotFound, value: trimmed);
+    let last_digit = handle_opt!(trimmed.rfind(|char: char| char.is_ascii_digit()), DigitsNotFound, value: trimmed);
+    let (prefix, rest) = trimmed.split_at(first_digit);
+    let (number_str, suffix) = rest.split_at(last_digit.saturating_sub(first_digit).saturating_add(1));
+    let is_negative = prefix.contains('-') || prefix.contains('(');
+    let symbol = format!("{prefix}{suffix}")
+        .chars()
+        .filter(|char| !char.is_whitespace() && !matches!(char, '-' | '(' | ')'))
+        .collect::<String>();
+    let currency = if symbol.is_empty() {
+        format.and_then(currency_code_hint)
+    } else if symbol.len() == 3 && symbol.chars().all(|char| char.is_ascii_uppercase()) {
+        Some(symbol)
+    } else {
+        let code = handle_opt!(
+            CURRENCY_SYMBOLS
+                .iter()
+                .find(|(candidate, _)| *candidate == symbol),
+            CurrencySymbolUnknown,
+            symbol
+        );
+        Some(code.1.to_owned())
+    };
+    let amount = handle!(parse_localized_number(number_str), ParseNumberFailed);
+    Ok(TypedCurrency {
+        amount: if is_negative { -amount } else { amount },
+        currency,
+    })
+}
+
+/// Parses dates like "10/18/2026" or "October 18, 2026" using the date format of the column (if available) or the common Coda date formats
+pub fn parse_date_display(text: &str, format: Option<&ColumnFormat>) -> Result<NaiveDate, ParseDisplayDateTimeError> {
+    use ParseDisplayDateTimeError::*;
+    let trimmed = text.trim();
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(trimmed) {
+        return Ok(date_time.date_naive());
+    }
+    let hint = format
+        .and_then(date_format_hint)
+        .map(coda_format_to_strftime);
+    let date_opt = hint
+        .iter()
+        .map(String::as_str)
+        .chain(DATE_FORMATS.iter().copied())
+        .find_map(|format| NaiveDate::parse_from_str(trimmed, format).ok());
+    Ok(handle_opt!(date_opt, FormatNotRecognized, value: trimmed))
+}
+
+/// Parses times like "3:04 PM" or "15:04:05" using the time format of the column (if available) or the common Coda time formats
+pub fn parse_time_display(text: &str, format: Option<&ColumnFormat>) -> Result<NaiveTime, ParseDisplayDateTimeError> {
+    use ParseDisplayDateTimeError::*;
+    let trimmed = text.trim();
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(trimmed) {
+        return Ok(date_time.time());
+    }
+    let hint = format
+        .and_then(time_format_hint)
+        .map(coda_format_to_strftime);
+    let time_opt = hint
+        .iter()
+        .map(String::as_str)
+        .chain(TIME_FORMATS.iter().copied())
+        .find_map(|format| NaiveTime::parse_from_str(trimmed, format).ok());
+    Ok(handle_opt!(time_opt, FormatNotRecognized, value: trimmed))
+}
+
+/// Parses date-times like "10/18/2026 3:04 PM". The result has no offset because Coda displays date-times in the doc timezone without specifying it.
+pub fn parse_date_time_display(text: &str, format: Option<&ColumnFormat>) -> Result<NaiveDateTime, ParseDisplayDateTimeError> {
+    use ParseDisplayDateTimeError::*;
+    let trimmed = text.trim();
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(trimmed) {
+        return Ok(date_time.naive_local());
+    }
+    let hint = format.and_then(|format| match format {
+        ColumnFormat::DateTimeColumnFormat(format) => {
+            let date_format = format.date_format.as_deref().map(coda_format_to_strftime)?;
+            let time_format = format.time_format.as_deref().map(coda_format_to_strftime)?;
+            Some(format!("{date_format} {time_format}"))
+        }
+        _ => None,
+    });
+    let defaults = DATE_FORMATS.iter().flat_map(|date_format| {
+        TIME_FORMATS
+            .iter()
+            .map(move |time_format| format!("{date_format} {time_format}"))
+    });
+    let date_time_opt = hint
+        .into_iter()
+        .chain(defaults)
+        .find_map(|format| NaiveDateTime::parse_from_str(trimmed, &format).ok());
+    Ok(handle_opt!(date_time_opt, FormatNotRecognized, value: trimmed))
+}
+
+/// Converts a Coda (Moment.js-style) format like "M/D/YYYY" or "h:mm A" into a chrono format string
+pub fn coda_format_to_strftime(format: &str) -> String {
+    const TOKENS: &[(&str, &str)] = &[
+        ("YYYY", "%Y"),
+        ("YY", "%y"),
+        ("MMMM", "%B"),
+        ("MMM", "%b"),
+        ("MM", "%m"),
+        ("M", "%m"),
+        ("DD", "%d"),
+        ("D", "%d"),
+        ("dddd", "%A"),
+        ("ddd", "%a"),
+        ("HH", "%H"),
+        ("H", "%H"),
+        ("hh", "%I"),
+        ("h", "%I"),
+        ("mm", "%M"),
+        ("ss", "%S"),
+        ("A", "%p"),
+        ("a", "%p"),
+        ("%", "%%"),
+    ];
+    let mut rest = format;
+    let mut output = String::with_capacity(format.len());
+    while let Some(char) = rest.chars().next() {
+        match TOKENS.iter().find(|(token, _)| rest.starts_with(token)) {
+            Some((token, replacement)) => {
+                output.push_str(replacement);
+                rest = &rest[token.len()..];
+            }
+            None => {
+                output.push(char);
+                rest = &rest[char.len_utf8()..];
+            }
+        }
+    }
+    output
+}
+
+/// Converts the fraction into a percent, rounded to 9 decimal places to drop the floating-point noise of the multiplication (e.g. `0.07 * 100.0` is `7.000000000000001`)
+fn percent_from_fraction(fraction: f64) -> f64 {
+    const SCALE: f64 = 1e9;
+    (fraction * 100.0 * SCALE).round() / SCALE
+}
+
+impl TypedValue {
+    /// Formats the value as a string that Coda parses back into the same value on write (dates as ISO 8601, durations as "1 day 2 hours", percents as "12%").
+    ///
+    /// Returns `None` for values that can't be written as strings (e.g. raw values of uninterpreted columns).
+    pub fn to_write_string(&self) -> Option<String> {
+        use TypedValue::*;
+        match self {
+            Null => Some(String::new()),
+            Text(text) => Some(text.clone()),
+            Number(number) => Some(number.to_string()),
+            Percent(fraction) => Some(format!("{}%", percent_from_fraction(*fraction))),
+            Currency(currency) => Some(currency.amount.to_string()),
+            Boolean(boolean) => Some(boolean.to_string()),
+            Date(date) => Some(date.format("%Y-%m-%d").to_string()),
+            DateTime(date_time) => Some(date_time.to_rfc3339()),
+            LocalDateTime(date_time) => Some(date_time.format("%Y-%m-%dT%H:%M:%S").to_string()),
+            Time(time) => Some(time.format("%H:%M:%S").to_string()),
+            Duration(duration) => Some(format_duration_seconds(duration.num_seconds())),
+            Person(person) => Some(person.email.clone().unwrap_or_else(|| person.name.clone())),
+            Row(reference) => Some(reference.name.clone()),
+            Url(url) => Some(url.url.clone()),
+            Image(image) => image.url.clone(),
+            List(values) => values
+                .iter()
+                .map(Self::to_write_string)
+                .collect::<Option<Vec<_>>>()
+                .map(|strings| strings.join(",")),
+            Raw(_) => None,
+        }
+    }
+}
+
+/// Returns the currency code of a currency column (also used by [`decode_value`](crate::decode_value))
+pub(crate) fn currency_code_hint(format: &ColumnFormat) -> Option<String> {
+    match format {
+        ColumnFormat::CurrencyColumnFormat(format) => format.currency_code.clone(),
+        _ => None,
+    }
+}
+
+fn date_format_hint(format: &ColumnFormat) -> Option<&str> {
+    match format {
+        ColumnFormat::DateColumnFormat(format) => format.format.as_deref(),
+        ColumnFormat::DateTimeColumnFormat(format) => format.date_format.as_deref(),
+        _ => None,
+    }
+}
+
+fn time_format_hint(format: &ColumnFormat) -> Option<&str> {
+    match format {
+        ColumnFormat::TimeColumnFormat(format) => format.format.as_deref(),
+        ColumnFormat::DateTimeColumnFormat(format) => format.time_format.as_deref(),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DecodeSimpleValueError {
+    #[error("failed to parse number")]
+    ParseNumberFailed { source: ParseLocalizedNumberError },
+    #[error("failed to parse percent")]
+    ParsePercentFailed { source: ParsePercentDisplayError },
+    #[error("failed to parse currency")]
+    ParseCurrencyFailed { source: ParseCurrencyDisplayError },
+    #[error("failed to parse date")]
+    ParseDateFailed { source: ParseDisplayDateTimeError },
+    #[error("failed to parse date-time")]
+    ParseDateTimeFailed { source: ParseDisplayDateTimeError },
+    #[error("failed to parse time")]
+    ParseTimeFailed { source: ParseDisplayDateTimeError },
+    #[error("failed to parse duration '{value}'")]
+    ParseDurationFailed { source: DurationValueParserError, value: String },
+    #[error("duration of '{seconds}' seconds is out of range")]
+    DurationOutOfRange { seconds: i64 },
+    #[error("checkbox value '{value}' is neither 'true' nor 'false'")]
+    CheckboxInvalid { value: String },
+}
+
+#[derive(Debug, Error)]
+pub enum ParseLocalizedNumberError {
+    #[error("failed to parse number '{value}'")]
+    ParseFloatFailed { source: ParseFloatError, value: String },
+}
+
+#[derive(Debug, Error)]
+pub enum ParsePercentDisplayError {
+    #[error("failed to parse percent number")]
+    ParseNumberFailed { source: ParseLocalizedNumberError },
+}
+
+#[derive(Debug, Error)]
+pub enum ParseCurrencyDisplayError {
+    #[error("currency value '{value}' does not contain digits")]
+    DigitsNotFound { value: String },
+    #[error("unknown currency symbol '{symbol}'")]
+    CurrencySymbolUnknown { symbol: String },
+    #[error("failed to parse currency amount")]
+    ParseNumberFailed { source: ParseLocalizedNumberError },
+}
+
+#[derive(Debug, Error)]
+pub enum ParseDisplayDateTimeError {
+    #[error("value '{value}' does not match any known format")]
+    FormatNotRecognized { value: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn must_parse_localized_numbers() {
+        assert_eq!(parse_localized_number("1,234.50").unwrap(), 1234.5);
+        assert_eq!(parse_localized_number("1.234,50").unwrap(), 1234.5);
+        assert_eq!(parse_localized_number("1,5").unwrap(), 1.5);
+        assert_eq!(parse_localized_number("1,234").unwrap(), 1234.0);
+    }
+
+    #[test]
+    fn must_parse_currency_display() {
+        let currency = parse_currency_display("$1,234.50", None).unwrap();
+        assert_eq!(currency.amount, 1234.5);
+        assert_eq!(currency.currency.as_deref(), Some("USD"));
+        let currency = parse_currency_display("(1.234,50 €)", None).unwrap();
+        assert_eq!(currency.amount, -1234.5);
+        assert_eq!(currency.currency.as_deref(), Some("EUR"));
+    }
+
+    #[test]
+    fn must_parse_percent_display() {
+        assert_eq!(parse_percent_display("12%").unwrap(), 0.12);
+        assert_eq!(TypedValue::Percent(0.07).to_write_string().as_deref(), Some("7%"));
+        assert_eq!(TypedValue::Percent(0.125).to_write_string().as_deref(), Some("12.5%"));
+    }
+
+    #[test]
+    fn must_parse_date_display_with_hint() {
+        let format = serde_json::from_value::<ColumnFormat>(serde_json::json!({"type": "date", "isArray": false, "format": "D/M/YYYY"})).unwrap();
+        let expected = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        assert_eq!(parse_date_display("18/10/2026", Some(&format)).unwrap(), expected);
+        assert_eq!(parse_date_display("10/18/2026", None).unwrap(), expected);
+        assert_eq!(TypedValue::Date(expected).to_write_string().as_deref(), Some("2026-10-18"));
+    }
+}
@@ -0,0 +1,255 @@
+use crate::types::{AddPermissionRequest, CellEdit, PageContent, RowEdit, Value, ValueVariant0};
+use crate::{DocId, added_principal_key};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The values of the `{{name}}` placeholders in a [`DocTemplate`]
+pub type TemplateParams = BTreeMap<String, String>;
+
+/// Replaces every `{{name}}` placeholder with the value of the `name` parameter (the placeholders without a parameter are left as is). The text is scanned once, so the placeholders in the values are not substituted.
+pub fn substitute_params(text: &str, params: &TemplateParams) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((before, after_open)) = rest.split_once("{{") {
+        result.push_str(before);
+        match after_open.split_once("}}") {
+            Some((name, after_close)) => match name.rfind("{{") {
+                // a stray `{{` before the placeholder: emit the text up to the last `{{` and resume the scan from there
+                Some(last_open) => {
+                    let (stray, from_last_open) = after_open.split_at(last_open);
+                    result.push_str("{{");
+                    result.push_str(stray);
+                    rest = from_last_open;
+                }
+                None => {
+                    match params.get(name) {
+                        Some(value) => result.push_str(value),
+                        None => {
+                            result.push_str("{{");
+                            result.push_str(name);
+                            result.push_str("}}");
+                        }
+                    }
+                    rest = after_close;
+                }
+            },
+            None => {
+                result.push_str("{{");
+                rest = after_open;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// A doc that is created by copying the `source_doc` and applying the parameters (see [`Client::apply_doc_template`](crate::Client::apply_doc_template)).
+///
+/// Every string may contain the `{{name}}` placeholders (see [`substitute_params`]).
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct DocTemplate {
+    /// The ID of the template doc
+    pub source_doc: DocId,
+    /// The title of the copy (also set with `update_doc` after the copy is accessible)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Substitute the placeholders in the names of every page of the copy
+    #[serde(default)]
+    pub substitute_page_names: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pages: Vec<PageTemplate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tables: Vec<TableSeed>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub permissions: Vec<AddPermissionRequest>,
+}
+
+/// The changes of a page of the copy (a `None` field is left unchanged)
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PageTemplate {
+    /// The ID or the name of the page in the copy (the name may contain placeholders)
+    pub page_id_or_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    /// Replaces the whole content of the page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<PageContent>,
+}
+
+/// The rows to insert into a table of the copy
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TableSeed {
+    pub table_id_or_name: String,
+    /// Delete the rows that were copied from the template before inserting the seed rows
+    #[serde(default)]
+    pub reset: bool,
+    /// Update the rows with the same values in these columns instead of inserting them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub key_columns: Vec<String>,
+    /// The string values may contain placeholders
+    pub rows: Vec<RowEdit>,
+}
+
+impl DocTemplate {
+    /// Returns the steps in the order they are applied
+    pub fn steps(&self) -> Vec<DocTemplateStep> {
+        use DocTemplateStep::*;
+        let mut steps = vec![CopyDoc, WaitForDoc];
+        if self.title.is_some() {
+            steps.push(UpdateTitle);
+        }
+        if self.substitute_page_names {
+            steps.push(SubstitutePageNames);
+        }
+        steps.extend(self.pages.iter().map(|page| UpdatePage {
+            page_id_or_name: page.page_id_or_name.clone(),
+        }));
+        steps.extend(self.tables.iter().map(|table| SeedTable {
+            table_id_or_name: table.table_id_or_name.clone(),
+        }));
+        steps.extend(self.permissions.iter().map(|permission| AddPermission {
+            principal: added_principal_key(&permission.principal),
+        }));
+        steps
+    }
+}
+
+impl PageTemplate {
+    pub fn page_id_or_name(&self, params: &TemplateParams) -> String {
+        substitute_params(&self.page_id_or_name, params)
+    }
+}
+
+impl TableSeed {
+    /// Returns the rows with the placeholders substituted in the string values
+    pub fn rows(&self, params: &TemplateParams) -> Vec<RowEdit> {
+        self.rows
+            .iter()
+            .map(|row| RowEdit {
+                cells: row
+                    .cells
+                    .iter()
+                    .map(|cell| CellEdit {
+                        column: cell.column.clone(),
+                        value: match &cell.value {
+                            Value::Variant0(ValueVariant0::Variant0(text)) => Value::from(substitute_params(text, params)),
+                            value => value.clone(),
+                        },
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Display, Deserialize, Serialize, Clone, Debug, Eq, PartialEq, Hash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DocTemplateStep {
+    #[display("copy doc")]
+    CopyDoc,
+    #[display("wait for doc")]
+    WaitForDoc,
+    #[display("update title")]
+    UpdateTitle,
+    #[display("substitute page names")]
+    SubstitutePageNames,
+    #[display("update page '{page_id_or_name}'")]
+    UpdatePage { page_id_or_name: String },
+    #[display("seed table '{table_id_or_name}'")]
+    SeedTable { table_id_or_name: String },
+    #[display("add permission for '{principal}'")]
+    AddPermission { principal: String },
+}
+
+#[derive(Serialize, Deserialize, Display, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum DocTemplateStepStatus {
+    #[display("applied")]
+    Applied,
+    #[display("failed")]
+    Failed,
+    /// The step was not applied because a previous step failed
+    #[display("skipped")]
+    Skipped,
+}
+
+/// The report of [`Client::apply_doc_template`](crate::Client::apply_doc_template). Pass the report of a failed run to the next run to resume it: the applied steps are not repeated, and the doc is not copied again.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DocTemplateReport {
+    /// The ID of the copy (`None` if the copy has not been created)
+    pub doc_id: Option<DocId>,
+    pub entries: Vec<DocTemplateReportEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DocTemplateReportEntry {
+    pub step: DocTemplateStep,
+    pub status: DocTemplateStepStatus,
+    /// The error message of a failed step
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DocTemplateReport {
+    pub fn is_applied(&self, step: &DocTemplateStep) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| &entry.step == step && entry.status == DocTemplateStepStatus::Applied)
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.status == DocTemplateStepStatus::Failed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn must_substitute_params_and_list_steps() {
+        let params = TemplateParams::from([("client".to_owned(), "Acme".to_owned())]);
+        assert_eq!(substitute_params("{{client}} onboarding ({{missing}})", &params), "Acme onboarding ({{missing}})");
+        assert_eq!(substitute_params("a {{ b {{client}}", &params), "a {{ b Acme");
+        let params_with_placeholders = TemplateParams::from([
+            ("a".to_owned(), "{{b}}".to_owned()),
+            ("b".to_owned(), "B".to_owned()),
+        ]);
+        assert_eq!(substitute_params("{{a}} {{b}} {{", &params_with_placeholders), "{{b}} B {{");
+        let template: DocTemplate = serde_json::from_value(json!({
+            "source_doc": "template",
+            "title": "{{client}}",
+            "tables": [{"table_id_or_name": "Tasks", "rows": [{"cells": [{"column": "Name", "value": "Call {{client}}"}, {"column": "Done", "value": false}]}]}],
+            "permissions": [{"access": "write", "principal": {"type": "email", "email": "pm@example.com"}}]
+        }))
+        .unwrap();
+        let steps = template
+            .steps()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            steps,
+            [
+                "copy doc",
+                "wait for doc",
+                "update title",
+                "seed table 'Tasks'",
+                "add permission for 'email:pm@example.com'"
+            ]
+        );
+        let rows = template.tables[0].rows(&params);
+        assert_eq!(serde_json::to_value(&rows[0].cells[0].value).unwrap(), json!("Call Acme"));
+        assert_eq!(serde_json::to_value(&rows[0].cells[1].value).unwrap(), json!(false));
+    }
+}
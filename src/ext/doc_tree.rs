@@ -0,0 +1,164 @@
+use crate::types::{Doc, Folder};
+use crate::{DocId, WorkspaceId};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub type FolderId = String;
+
+/// The filters of [`Client::doc_tree`](crate::Client::doc_tree) (passed to `list_docs`)
+#[derive(Clone, Debug, Default)]
+pub struct DocTreeOptions {
+    pub is_owner: Option<bool>,
+    pub is_published: Option<bool>,
+    /// Only the copies of this doc
+    pub source_doc: Option<DocId>,
+    /// A free-text search query
+    pub query: Option<String>,
+    /// Fetch the details of every folder with `get_folder` (one request per folder)
+    pub with_folder_details: bool,
+}
+
+/// The docs of a workspace or a folder, grouped by folder (see [`Client::doc_tree`](crate::Client::doc_tree))
+#[derive(Serialize, Clone, Debug)]
+pub struct DocTree {
+    pub workspace_id: Option<WorkspaceId>,
+    pub folder_id: Option<FolderId>,
+    /// Sorted by name
+    pub folders: Vec<FolderNode>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct FolderNode {
+    pub folder_id: FolderId,
+    pub name: Option<String>,
+    pub browser_link: String,
+    pub workspace_id: WorkspaceId,
+    /// `None` unless [`DocTreeOptions::with_folder_details`] is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Folder>,
+    /// Sorted by name
+    pub docs: Vec<DocNode>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DocNode {
+    pub doc_id: DocId,
+    pub name: String,
+    pub browser_link: String,
+    /// The email of the owner
+    pub owner: String,
+    pub owner_name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub published: bool,
+    pub source_doc_id: Option<DocId>,
+}
+
+impl DocTree {
+    pub fn new(workspace_id: Option<WorkspaceId>, folder_id: Option<FolderId>, docs: Vec<Doc>) -> Self {
+        let mut folders = BTreeMap::<FolderId, FolderNode>::new();
+        for doc in docs {
+            let folder = folders
+                .entry(doc.folder_id.clone())
+                .or_insert_with(|| FolderNode {
+                    folder_id: doc.folder_id.clone(),
+                    name: doc.folder.name.clone(),
+                    browser_link: doc.folder.browser_link.clone(),
+                    workspace_id: doc.workspace_id.clone(),
+                    details: None,
+                    docs: Vec::new(),
+                });
+            folder.docs.push(DocNode::from(doc));
+        }
+        let mut folders = folders.into_values().collect::<Vec<_>>();
+        for folder in &mut folders {
+            folder
+                .docs
+                .sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.doc_id.cmp(&b.doc_id)));
+        }
+        folders.sort_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+                .then_with(|| a.folder_id.cmp(&b.folder_id))
+        });
+        Self {
+            workspace_id,
+            folder_id,
+            folders,
+        }
+    }
+
+    pub fn docs(&self) -> impl Iterator<Item = &DocNode> {
+        self.folders.iter().flat_map(|folder| folder.docs.iter())
+    }
+
+    pub fn folder(&self, folder_id: &str) -> Option<&FolderNode> {
+        self.folders
+            .iter()
+            .find(|folder| folder.folder_id == folder_id)
+    }
+}
+
+impl From<Doc> for DocNode {
+    fn from(doc: Doc) -> Self {
+        Self {
+            doc_id: doc.id,
+            name: doc.name,
+            browser_link: doc.browser_link,
+            owner: doc.owner,
+            owner_name: doc.owner_name,
+            created_at: doc.created_at,
+            updated_at: doc.updated_at,
+            published: doc.published.is_some(),
+            source_doc_id: doc.source_doc.map(|source_doc| source_doc.id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn doc(id: &str, name: &str, folder_id: &str, folder_name: &str) -> Doc {
+        serde_json::from_value(json!({
+            "browserLink": format!("https://coda.io/d/_d{id}"),
+            "createdAt": "2024-01-01T00:00:00Z",
+            "folder": {"browserLink": format!("https://coda.io/folders/{folder_id}"), "id": folder_id, "name": folder_name, "type": "folder"},
+            "folderId": folder_id,
+            "href": format!("https://coda.io/apis/v1/docs/{id}"),
+            "id": id,
+            "name": name,
+            "owner": "owner@example.com",
+            "ownerName": "Owner",
+            "type": "doc",
+            "updatedAt": "2024-01-02T00:00:00Z",
+            "workspace": {"browserLink": "https://coda.io/docs?workspaceId=ws-1", "id": "ws-1", "type": "workspace"},
+            "workspaceId": "ws-1"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn must_group_docs_by_folder() {
+        let docs = vec![
+            doc("d3", "Zeta", "fl-2", "Archive"),
+            doc("d1", "Beta", "fl-1", "Projects"),
+            doc("d2", "Alpha", "fl-2", "Archive"),
+        ];
+        let tree = DocTree::new(Some("ws-1".to_owned()), None, docs);
+        let folder_ids = tree
+            .folders
+            .iter()
+            .map(|folder| folder.folder_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(folder_ids, ["fl-2", "fl-1"]);
+        let doc_ids = tree
+            .docs()
+            .map(|doc| doc.doc_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(doc_ids, ["d2", "d3", "d1"]);
+        assert_eq!(tree.folder("fl-1").unwrap().docs[0].owner, "owner@example.com");
+    }
+}
@@ -1,58 +1,154 @@
-use core::iter::from_fn;
-use errgonomic::{handle, handle_opt};
-use std::num::ParseIntError;
+use errgonomic::{handle, handle_bool, handle_opt};
+use std::num::{NonZeroU64, ParseIntError};
 use thiserror::Error;
-use time::Duration;
 
 const SECONDS_PER_MINUTE: i64 = 60;
 const SECONDS_PER_HOUR: i64 = 3_600;
 const SECONDS_PER_DAY: i64 = 86_400;
+const SECONDS_PER_WEEK: i64 = 604_800;
 
-pub fn parse_duration_value(source: &impl AsRef<str>) -> Result<Option<Duration>, DurationValueParserError> {
+/// The units of [`format_duration_seconds`], from the largest to the smallest
+const FORMAT_UNITS: [(NonZeroU64, &str, &str); 4] = [
+    (NonZeroU64::new(SECONDS_PER_DAY.unsigned_abs()).expect("seconds per day is not zero"), "day", "days"),
+    (NonZeroU64::new(SECONDS_PER_HOUR.unsigned_abs()).expect("seconds per hour is not zero"), "hour", "hours"),
+    (NonZeroU64::new(SECONDS_PER_MINUTE.unsigned_abs()).expect("seconds per minute is not zero"), "minute", "minutes"),
+    (NonZeroU64::MIN, "second", "seconds"),
+];
+
+#[cfg(feature = "time")]
+pub fn parse_duration_value(source: &impl AsRef<str>) -> Result<Option<time::Duration>, DurationValueParserError> {
+    parse_duration_seconds(source).map(|seconds_opt| seconds_opt.map(time::Duration::seconds))
+}
+
+/// Parses a duration value into the total number of seconds.
+///
+/// Supports every form Coda uses to display durations:
+///
+/// * Unit words: "2 hrs 30 mins", "1 day, 2 hours"
+/// * Compact units: "3w", "2h30m", "1d 4h"
+/// * Clock notation, optionally after other components: "2:30:00", "1 day 2:30:00"
+///
+/// A leading minus sign applies to the whole duration: "-1 day 2 hours" and "-2:30" are negative.
+pub fn parse_duration_seconds(source: &impl AsRef<str>) -> Result<Option<i64>, DurationValueParserError> {
     use DurationValueParserError::*;
     let trimmed = source.as_ref().trim();
-    if trimmed.is_empty() {
-        return Ok(None);
+    match trimmed.strip_prefix('-') {
+        Some(unsigned) => match parse_unsigned_duration_seconds(unsigned)? {
+            Some(seconds) => Ok(Some(handle_opt!(seconds.checked_neg(), DurationOverflow))),
+            None => Err(NumberNotFound),
+        },
+        None => parse_unsigned_duration_seconds(trimmed),
     }
+}
 
-    let mut tokens = trimmed.split_whitespace();
-    let number_str = handle_opt!(tokens.next(), NumberNotFound);
-    let unit_str = handle_opt!(tokens.next(), UnitNotFound);
-    parse_duration_component(number_str, unit_str)
-        .and_then(|duration_initial| {
-            from_fn(|| tokens.next().map(|number_str| (number_str, tokens.next()))).try_fold(duration_initial, |duration_total, (number_str, unit_str_opt)| {
-                let unit_str = handle_opt!(unit_str_opt, UnitNotFound);
-                parse_duration_component(number_str, unit_str).and_then(|duration| duration_total.checked_add(duration).ok_or(DurationOverflow))
-            })
-        })
-        .map(Some)
+fn parse_unsigned_duration_seconds(source: &str) -> Result<Option<i64>, DurationValueParserError> {
+    use DurationValueParserError::*;
+    let normalized = source.replace(',', " ");
+    let mut tokens = normalized.split_whitespace();
+    let mut total_opt = None;
+    while let Some(token) = tokens.next() {
+        let seconds = if token.contains(':') {
+            parse_clock(token)?
+        } else if token.bytes().all(|byte| byte.is_ascii_digit()) {
+            let unit_str = handle_opt!(tokens.next(), UnitNotFound);
+            parse_duration_component(token, unit_str)?
+        } else {
+            parse_compact_components(token)?
+        };
+        let total = total_opt.unwrap_or(0i64);
+        total_opt = Some(handle_opt!(total.checked_add(seconds), DurationOverflow));
+    }
+    Ok(total_opt)
+}
+
+/// Formats the number of seconds in a form that Coda accepts on write and [`parse_duration_seconds`] accepts on read (e.g. "1 day 2 hours 30 minutes", or "-1 day 2 hours" for a negative duration)
+pub fn format_duration_seconds(seconds: i64) -> String {
+    let sign = if seconds < 0 { "-" } else { "" };
+    let mut remainder = seconds.unsigned_abs();
+    let mut components = Vec::new();
+    for (seconds_per_unit, singular, plural) in FORMAT_UNITS {
+        let count = remainder / seconds_per_unit;
+        remainder %= seconds_per_unit;
+        if count > 0 {
+            components.push(format!("{count} {}", if count == 1 { singular } else { plural }));
+        }
+    }
+    if components.is_empty() {
+        "0 seconds".to_owned()
+    } else {
+        format!("{sign}{}", components.join(" "))
+    }
 }
 
-fn parse_duration_component(number_str: &str, unit_str: &str) -> Result<Duration, DurationValueParserError> {
+fn parse_duration_component(number_str: &str, unit_str: &str) -> Result<i64, DurationValueParserError> {
     use DurationValueParserError::*;
     let number = handle!(number_str.parse::<i64>(), NumberParseFailed);
-    duration_from_number_and_unit(number, unit_str)
+    seconds_from_number_and_unit(number, unit_str)
+}
+
+/// Parses tokens like "3w" or "2h30m"
+fn parse_compact_components(token: &str) -> Result<i64, DurationValueParserError> {
+    use DurationValueParserError::*;
+    let mut rest = token;
+    let mut total = 0i64;
+    while !rest.is_empty() {
+        let number_len = rest.bytes().take_while(u8::is_ascii_digit).count();
+        handle_bool!(number_len == 0, NumberNotFound);
+        let (number_str, after_number) = rest.split_at(number_len);
+        let unit_len = after_number
+            .bytes()
+            .take_while(u8::is_ascii_alphabetic)
+            .count();
+        handle_bool!(unit_len == 0, UnitNotFound);
+        let (unit_str, after_unit) = after_number.split_at(unit_len);
+        let seconds = parse_duration_component(number_str, unit_str)?;
+        total = handle_opt!(total.checked_add(seconds), DurationOverflow);
+        rest = after_unit;
+    }
+    Ok(total)
+}
+
+/// Parses tokens like "2:30" (hours and minutes) or "2:30:00" (hours, minutes and seconds)
+fn parse_clock(token: &str) -> Result<i64, DurationValueParserError> {
+    use DurationValueParserError::*;
+    let parts = token.split(':').collect::<Vec<_>>();
+    let units: &[i64] = match parts.len() {
+        2 => &[SECONDS_PER_HOUR, SECONDS_PER_MINUTE],
+        3 => &[SECONDS_PER_HOUR, SECONDS_PER_MINUTE, 1],
+        _ => {
+            return Err(ClockInvalid {
+                clock: token.to_owned(),
+            });
+        }
+    };
+    parts
+        .into_iter()
+        .zip(units)
+        .try_fold(0i64, |total, (part, seconds_per_unit)| {
+            // the parts are unsigned, because the sign applies to the whole duration (see `parse_duration_seconds`)
+            let number = i64::from(handle!(part.parse::<u32>(), NumberParseFailed));
+            let seconds = handle_opt!(number.checked_mul(*seconds_per_unit), DurationOverflow);
+            total.checked_add(seconds).ok_or(DurationOverflow)
+        })
 }
 
-fn duration_from_number_and_unit(number: i64, unit_str: &str) -> Result<Duration, DurationValueParserError> {
+fn seconds_from_number_and_unit(number: i64, unit_str: &str) -> Result<i64, DurationValueParserError> {
     use DurationValueParserError::*;
     match unit_str.to_ascii_lowercase().as_str() {
-        "second" | "seconds" | "sec" | "secs" => Ok(Duration::seconds(number)),
-        "minute" | "minutes" | "min" | "mins" => checked_duration_from_seconds(number, SECONDS_PER_MINUTE),
-        "hour" | "hours" | "hr" | "hrs" => checked_duration_from_seconds(number, SECONDS_PER_HOUR),
-        "day" | "days" => checked_duration_from_seconds(number, SECONDS_PER_DAY),
+        "s" | "second" | "seconds" | "sec" | "secs" => Ok(number),
+        "m" | "minute" | "minutes" | "min" | "mins" => checked_seconds(number, SECONDS_PER_MINUTE),
+        "h" | "hour" | "hours" | "hr" | "hrs" => checked_seconds(number, SECONDS_PER_HOUR),
+        "d" | "day" | "days" => checked_seconds(number, SECONDS_PER_DAY),
+        "w" | "wk" | "wks" | "week" | "weeks" => checked_seconds(number, SECONDS_PER_WEEK),
         _ => Err(UnitUnexpected {
             unit: unit_str.to_owned(),
         }),
     }
 }
 
-fn checked_duration_from_seconds(number: i64, seconds_per_unit: i64) -> Result<Duration, DurationValueParserError> {
+fn checked_seconds(number: i64, seconds_per_unit: i64) -> Result<i64, DurationValueParserError> {
     use DurationValueParserError::*;
-    match number.checked_mul(seconds_per_unit) {
-        Some(seconds) => Ok(Duration::seconds(seconds)),
-        None => Err(DurationOverflow),
-    }
+    number.checked_mul(seconds_per_unit).ok_or(DurationOverflow)
 }
 
 #[derive(Debug, PartialEq, Error)]
@@ -65,6 +161,8 @@ pub enum DurationValueParserError {
     NumberParseFailed { source: ParseIntError },
     #[error("unexpected duration unit: {unit}")]
     UnitUnexpected { unit: String },
+    #[error("invalid clock duration '{clock}'")]
+    ClockInvalid { clock: String },
     #[error("duration value is too large")]
     DurationOverflow,
 }
@@ -73,13 +171,49 @@ pub enum DurationValueParserError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn must_parse_duration_seconds() {
+        assert_eq!(parse_duration_seconds(&"1 day 2 hrs"), Ok(Some(93_600)));
+        assert_eq!(parse_duration_seconds(&" "), Ok(None));
+    }
+
+    #[test]
+    fn must_parse_display_durations() {
+        assert_eq!(parse_duration_seconds(&"1 day 2:30:00"), Ok(Some(95_400)));
+        assert_eq!(parse_duration_seconds(&"3w"), Ok(Some(1_814_400)));
+        assert_eq!(parse_duration_seconds(&"2h30m"), Ok(Some(9_000)));
+        assert_eq!(parse_duration_seconds(&"1 day, 2 hours"), Ok(Some(93_600)));
+    }
+
+    #[test]
+    fn must_round_trip_duration() {
+        let formatted = format_duration_seconds(95_405);
+        assert_eq!(formatted, "1 day 2 hours 30 minutes 5 seconds");
+        assert_eq!(parse_duration_seconds(&formatted), Ok(Some(95_405)));
+    }
+
+    #[test]
+    fn must_round_trip_negative_duration() {
+        let formatted = format_duration_seconds(-93_600);
+        assert_eq!(formatted, "-1 day 2 hours");
+        assert_eq!(parse_duration_seconds(&formatted), Ok(Some(-93_600)));
+        assert_eq!(parse_duration_seconds(&"-2:30"), Ok(Some(-9_000)));
+        assert_eq!(parse_duration_seconds(&"-"), Err(DurationValueParserError::NumberNotFound));
+        assert!(parse_duration_seconds(&"2:-30").is_err());
+        assert!(parse_duration_seconds(&"--1 day").is_err());
+    }
+
+    #[cfg(feature = "time")]
     #[test]
     fn must_parse_simple_duration() {
+        use time::Duration;
         assert_eq!(parse_duration_value(&"2 hrs"), Ok(Some(Duration::hours(2))));
     }
 
+    #[cfg(feature = "time")]
     #[test]
     fn must_parse_complex_duration() {
+        use time::Duration;
         assert_eq!(parse_duration_value(&"2 hrs 30 mins"), Ok(Some(Duration::hours(2) + Duration::minutes(30))));
     }
 }
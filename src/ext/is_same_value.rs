@@ -0,0 +1,9 @@
+use crate::types::Value;
+
+/// [`Value`] doesn't implement `PartialEq`, so the values are compared by their JSON representation
+pub fn is_same_value(a: &Value, b: &Value) -> bool {
+    match (serde_json::to_value(a), serde_json::to_value(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{DurationValueParserError, parse_duration_seconds};
+use errgonomic::{handle, handle_opt};
+use jiff::civil::{Date, Time};
+use jiff::fmt::temporal::Pieces;
+use jiff::{Span, Timestamp};
+use thiserror::Error;
+
+pub fn parse_jiff_timestamp(text: &str) -> Result<Timestamp, jiff::Error> {
+    text.parse()
+}
+
+/// Accepts both RFC3339 timestamps (Coda returns dates at midnight in the doc timezone) and ISO 8601 dates
+pub fn parse_jiff_date(text: &str) -> Result<Date, jiff::Error> {
+    Pieces::parse(text).map(|pieces| pieces.date())
+}
+
+/// Accepts both RFC3339 timestamps (Coda returns times with a placeholder date) and ISO 8601 times. Returns an error for a date without a time.
+pub fn parse_jiff_time(text: &str) -> Result<Time, jiff::Error> {
+    match Pieces::parse(text).map(|pieces| pieces.time()) {
+        Ok(Some(time)) => Ok(time),
+        // `Time::from_str` rejects the dates without a time
+        Ok(None) | Err(_) => text.parse(),
+    }
+}
+
+pub fn parse_jiff_span(text: &str) -> Result<Span, ParseJiffSpanError> {
+    use ParseJiffSpanError::*;
+    let seconds_opt = handle!(parse_duration_seconds(&text), DurationParseFailed);
+    let seconds = handle_opt!(seconds_opt, DurationEmptyInvalid);
+    Ok(handle!(Span::new().try_seconds(seconds), DurationOutOfRange, seconds))
+}
+
+#[derive(Debug, Error)]
+pub enum ParseJiffSpanError {
+    #[error("failed to parse duration value")]
+    DurationParseFailed { source: DurationValueParserError },
+    #[error("duration value is empty")]
+    DurationEmptyInvalid,
+    #[error("duration of '{seconds}' seconds is out of range")]
+    DurationOutOfRange { source: jiff::Error, seconds: i64 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RichValue;
+
+    #[test]
+    fn must_convert_rich_value_to_jiff_types() {
+        let rich_value = serde_json::from_value::<RichValue>(serde_json::json!("2026-10-18T00:00:00.000Z")).unwrap();
+        let date = Option::<Date>::try_from(&rich_value).unwrap();
+        assert_eq!(date, Date::new(2026, 10, 18).ok());
+        let duration = serde_json::from_value::<RichValue>(serde_json::json!("2 hrs 30 mins")).unwrap();
+        let span = Option::<Span>::try_from(duration).unwrap().unwrap();
+        assert_eq!(span.get_seconds(), 9_000);
+        assert_eq!(parse_jiff_time("1899-12-30T14:30:00.000-08:00").unwrap(), Time::new(14, 30, 0, 0).unwrap());
+        assert_eq!(parse_jiff_time("14:30").unwrap(), Time::new(14, 30, 0, 0).unwrap());
+        assert!(parse_jiff_time("2026-10-18").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{Client, DocId, Middleware};
+use errgonomic::handle_opt;
+use progenitor_client::OperationInfo;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU16, AtomicUsize, Ordering};
+use thiserror::Error;
+
+pub type WorkspaceId = String;
+
+/// A set of named [`Client`]s, each with its own API key and [`Limiter`](crate::Limiter), so that the requests can be spread across several service accounts.
+///
+/// A request is routed by the doc or workspace rules first, and round-robin across the healthy keys otherwise. A key becomes unhealthy when Coda responds with `401 Unauthorized` or `403 Forbidden` to any request made with it (use [`KeyPool::mark_healthy`] after fixing the key).
+///
+/// ```no_run
+/// # use coda_api::{ClientBuilder, KeyPool};
+/// let pool = KeyPool::new()
+///     .with_key("marketing", ClientBuilder::new().with_api_key_from_env("CODA_MARKETING_API_KEY").build().unwrap())
+///     .with_key("sales", ClientBuilder::new().with_api_key_from_env("CODA_SALES_API_KEY").build().unwrap())
+///     .with_workspace_rule("ws-sales", "sales");
+/// ```
+#[derive(Default)]
+pub struct KeyPool {
+    keys: Vec<PooledKey>,
+    doc_rules: HashMap<DocId, String>,
+    workspace_rules: HashMap<WorkspaceId, String>,
+    next: AtomicUsize,
+}
+
+struct PooledKey {
+    name: String,
+    client: Client,
+    health: Arc<AtomicU16>,
+}
+
+/// The health of a key in a [`KeyPool`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyHealth {
+    pub name: String,
+    /// The status that made the key unhealthy (`None` if the key is healthy)
+    pub failed_status: Option<StatusCode>,
+}
+
+impl KeyPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `client` under the `name` (a key with the same name is replaced)
+    pub fn with_key(mut self, name: impl Into<String>, client: Client) -> Self {
+        let name = name.into();
+        let health = Arc::new(AtomicU16::new(0));
+        let client = client.with_middleware(KeyHealthTracker {
+            health: health.clone(),
+        });
+        let key = PooledKey {
+            name,
+            client,
+            health,
+        };
+        match self
+            .keys
+            .iter_mut()
+            .find(|existing| existing.name == key.name)
+        {
+            Some(existing) => *existing = key,
+            None => self.keys.push(key),
+        }
+        self
+    }
+
+    /// Routes the requests for the `doc_id` to the key with the `key_name`
+    pub fn with_doc_rule(mut self, doc_id: impl Into<DocId>, key_name: impl Into<String>) -> Self {
+        self.doc_rules.insert(doc_id.into(), key_name.into());
+        self
+    }
+
+    /// Routes the requests for the `workspace_id` to the key with the `key_name`
+    pub fn with_workspace_rule(mut self, workspace_id: impl Into<WorkspaceId>, key_name: impl Into<String>) -> Self {
+        self.workspace_rules
+            .insert(workspace_id.into(), key_name.into());
+        self
+    }
+
+    pub fn get(&self, key_name: &str) -> Result<&Client, KeyPoolGetError> {
+        use KeyPoolGetError::*;
+        let key = handle_opt!(self.keys.iter().find(|key| key.name == key_name), KeyNotFound, name: key_name);
+        match key.failed_status() {
+            Some(status) => Err(KeyUnhealthy {
+                name: key.name.clone(),
+                status,
+            }),
+            None => Ok(&key.client),
+        }
+    }
+
+    /// Returns the client of the doc rule, or the next healthy client if there is no rule for the `doc_id`
+    pub fn for_doc(&self, doc_id: &str) -> Result<&Client, KeyPoolGetError> {
+        match self.doc_rules.get(doc_id) {
+            Some(key_name) => self.get(key_name),
+            None => self.next(),
+        }
+    }
+
+    /// Returns the client of the workspace rule, or the next healthy client if there is no rule for the `workspace_id`
+    pub fn for_workspace(&self, workspace_id: &str) -> Result<&Client, KeyPoolGetError> {
+        match self.workspace_rules.get(workspace_id) {
+            Some(key_name) => self.get(key_name),
+            None => self.next(),
+        }
+    }
+
+    /// Returns the next healthy client (round-robin)
+    pub fn next(&self) -> Result<&Client, KeyPoolGetError> {
+        use KeyPoolGetError::*;
+        let len = handle_opt!(NonZeroUsize::new(self.keys.len()), PoolEmpty);
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let key_opt = (0..len.get())
+            // the index is reduced modulo `len`, so wrapping around only shifts the round-robin start
+            .map(|offset| start.wrapping_add(offset) % len)
+            .filter_map(|index| self.keys.get(index))
+            .find(|key| key.failed_status().is_none());
+        match key_opt {
+            Some(key) => Ok(&key.client),
+            None => Err(NoHealthyKeys {
+                unhealthy: self
+                    .health()
+                    .into_iter()
+                    .filter_map(|health| Some(format!("{} ({})", health.name, health.failed_status?)))
+                    .collect(),
+            }),
+        }
+    }
+
+    pub fn health(&self) -> Vec<KeyHealth> {
+        self.keys
+            .iter()
+            .map(|key| KeyHealth {
+                name: key.name.clone(),
+                failed_status: key.failed_status(),
+            })
+            .collect()
+    }
+
+    pub fn mark_unhealthy(&self, key_name: &str, status: StatusCode) {
+        if let Some(key) = self.keys.iter().find(|key| key.name == key_name) {
+            key.health.store(status.as_u16(), Ordering::Relaxed);
+        }
+    }
+
+    pub fn mark_healthy(&self, key_name: &str) {
+        if let Some(key) = self.keys.iter().find(|key| key.name == key_name) {
+            key.health.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+impl PooledKey {
+    fn failed_status(&self) -> Option<StatusCode> {
+        StatusCode::from_u16(self.health.load(Ordering::Relaxed)).ok()
+    }
+}
+
+/// Stores the status of the `401 Unauthorized` and `403 Forbidden` responses (`0` means healthy)
+struct KeyHealthTracker {
+    health: Arc<AtomicU16>,
+}
+
+impl Middleware for KeyHealthTracker {
+    fn after_response(&self, result: &reqwest::Result<reqwest::Response>, _info: &OperationInfo) {
+        if let Ok(response) = result
+            && matches!(response.status(), StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+        {
+            self.health
+                .store(response.status().as_u16(), Ordering::Relaxed);
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum KeyPoolGetError {
+    #[error("API key '{name}' is not in the pool")]
+    KeyNotFound { name: String },
+    #[error("API key '{name}' is unhealthy: Coda responded with '{status}'")]
+    KeyUnhealthy { name: String, status: StatusCode },
+    #[error("key pool has no API keys")]
+    PoolEmpty,
+    #[error("all API keys are unhealthy: {}", unhealthy.join(", "))]
+    NoHealthyKeys { unhealthy: Vec<String> },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool() -> KeyPool {
+        KeyPool::new()
+            .with_key("a", Client::new("http://127.0.0.1:9"))
+            .with_key("b", Client::new("http://127.0.0.1:9"))
+            .with_doc_rule("doc-b", "b")
+    }
+
+    #[test]
+    fn must_route_by_rules_and_skip_unhealthy_keys() {
+        let pool = pool();
+        let b = pool.get("b").unwrap() as *const Client;
+        assert_eq!(pool.for_doc("doc-b").unwrap() as *const Client, b);
+        pool.mark_unhealthy("a", StatusCode::UNAUTHORIZED);
+        assert_eq!(pool.next().unwrap() as *const Client, b);
+        assert_eq!(pool.next().unwrap() as *const Client, b);
+        pool.mark_unhealthy("b", StatusCode::FORBIDDEN);
+        assert!(matches!(pool.for_doc("doc-b"), Err(KeyPoolGetError::KeyUnhealthy { .. })));
+        assert!(matches!(pool.next(), Err(KeyPoolGetError::NoHealthyKeys { ref unhealthy }) if unhealthy.len() == 2));
+        pool.mark_healthy("a");
+        assert!(pool.for_doc("doc-c").is_ok());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{Error, RawClient, record_response, wait_for_limiter_gate};
+use progenitor_client::{ClientHooks, OperationInfo};
+use std::error::Error as StdError;
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::Instant;
+
+pub type MiddlewareError = Box<dyn StdError + Send + Sync>;
+
+/// Runs around every request sent by [`RawClient`] (and therefore by [`Client`](crate::Client)), including the `*_correct` methods.
+///
+/// The `before_request` methods run in the order the middleware was added, the `after_response` methods run in the reverse order.
+pub trait Middleware: Send + Sync {
+    /// Runs before the request is sent. May modify the request (e.g. add headers or sign it). An error aborts the request with [`Error::InvalidRequest`].
+    fn before_request(&self, _request: &mut reqwest::Request, _info: &OperationInfo) -> Result<(), MiddlewareError> {
+        Ok(())
+    }
+
+    /// Runs after the response is received (or the request fails)
+    fn after_response(&self, _result: &reqwest::Result<reqwest::Response>, _info: &OperationInfo) {}
+}
+
+/// The middleware of a [`RawClient`], in the order it was added
+#[derive(Clone, Default)]
+pub struct MiddlewareChain(Vec<Arc<dyn Middleware>>);
+
+impl MiddlewareChain {
+    pub fn push(&mut self, middleware: Arc<dyn Middleware>) {
+        self.0.push(middleware);
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MiddlewareChain")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl ClientHooks<()> for &RawClient {
+    async fn pre<E>(&self, request: &mut reqwest::Request, info: &OperationInfo) -> Result<(), Error<E>> {
+        for middleware in &self.middleware.0 {
+            middleware
+                .before_request(request, info)
+                .map_err(|error| Error::InvalidRequest(format!("middleware rejected the request: {error}")))?;
+        }
+        Ok(())
+    }
+
+    async fn post<E>(&self, result: &reqwest::Result<reqwest::Response>, info: &OperationInfo) -> Result<(), Error<E>> {
+        for middleware in self.middleware.0.iter().rev() {
+            middleware.after_response(result, info);
+        }
+        Ok(())
+    }
+
+    async fn exec(&self, request: reqwest::Request, info: &OperationInfo) -> reqwest::Result<reqwest::Response> {
+        let execute = |request: reqwest::Request| async move {
+            wait_for_limiter_gate().await;
+            let path = request.url().path().to_owned();
+            let started = Instant::now();
+            let result = self.client.execute(request).await;
+            record_response(info, &path, &result, started.elapsed());
+            result
+        };
+        match &self.cache {
+            Some(cache) => cache.exec(request, info.operation_id, execute).await,
+            None => execute(request).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use crate::types::RowUpdate;
+    use std::sync::Mutex;
+
+    struct RejectAll {
+        operation_ids: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl Middleware for RejectAll {
+        fn before_request(&self, _request: &mut reqwest::Request, info: &OperationInfo) -> Result<(), MiddlewareError> {
+            self.operation_ids.lock().unwrap().push(info.operation_id);
+            Err("rejected".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn must_run_for_generated_and_correct_methods() {
+        let operation_ids = Arc::new(Mutex::new(Vec::new()));
+        let middleware = RejectAll {
+            operation_ids: operation_ids.clone(),
+        };
+        let client = Client::new("http://127.0.0.1:9").with_middleware(middleware);
+        let body = serde_json::from_value::<RowUpdate>(serde_json::json!({"row": {"cells": []}})).unwrap();
+        let result = client.update_row_correct("d", "t", "r", None, &body).await;
+        assert!(matches!(result, Err(Error::InvalidRequest(_))));
+        let result = client.get_doc("d").await;
+        assert!(matches!(result, Err(Error::InvalidRequest(_))));
+        assert_eq!(*operation_ids.lock().unwrap(), ["update_row", "get_doc"]);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::types::{AddPackPermissionRequest, CreatePackInvitationRequest, Maker, PackAccessType, PackInvitation, PackPermission, PackPrincipal, PublishingCategory};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU64;
+
+/// The desired permissions, makers, categories and invitations of a Pack. A `None` field is left unmanaged.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct PackAccessConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Vec<AddPackPermissionRequest>>,
+    /// Login IDs (emails) of the makers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub makers: Option<Vec<String>>,
+    /// Category names
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub categories: Option<Vec<String>>,
+    /// Pending invitations (accepted invitations become permissions, so they should be listed in `permissions` too)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invitations: Option<Vec<CreatePackInvitationRequest>>,
+}
+
+/// The current permissions, makers, categories and invitations of a Pack
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PackAccessState {
+    pub permissions: Vec<PackPermission>,
+    pub makers: Vec<Maker>,
+    pub categories: Vec<PublishingCategory>,
+    pub invitations: Vec<PackInvitation>,
+}
+
+#[derive(Display, Serialize, Clone, Debug)]
+pub enum PackAccessChange {
+    /// Adds the permission, or changes the access of the existing permission of the principal
+    #[display("set permission of '{}' to '{}'", pack_principal_key(&_0.principal), _0.access)]
+    SetPermission(AddPackPermissionRequest),
+    #[display("delete permission '{permission_id}' of '{}'", pack_principal_key(principal))]
+    DeletePermission { permission_id: String, principal: PackPrincipal },
+    #[display("add maker '{login_id}'")]
+    AddMaker { login_id: String },
+    #[display("delete maker '{login_id}'")]
+    DeleteMaker { login_id: String },
+    #[display("add category '{category_name}'")]
+    AddCategory { category_name: String },
+    #[display("delete category '{category_name}'")]
+    DeleteCategory { category_name: String },
+    #[display("invite '{}' with access '{}'", _0.email, _0.access)]
+    CreateInvitation(CreatePackInvitationRequest),
+    #[display("change access of invitation '{invitation_id}' for '{email}' to '{access}'")]
+    UpdateInvitation { invitation_id: String, email: String, access: PackAccessType },
+    #[display("delete invitation '{invitation_id}' for '{email}'")]
+    DeleteInvitation { invitation_id: String, email: String },
+}
+
+/// The changes that make the current state of a Pack match a [`PackAccessConfig`]
+#[derive(Serialize, Clone, Debug)]
+pub struct PackAccessPlan {
+    pub pack_id: NonZeroU64,
+    pub changes: Vec<PackAccessChange>,
+}
+
+impl PackAccessPlan {
+    pub fn new(pack_id: NonZeroU64, desired: &PackAccessConfig, current: &PackAccessState) -> Self {
+        use PackAccessChange::*;
+        let mut changes = Vec::new();
+
+        if let Some(permissions) = &desired.permissions {
+            for permission in permissions {
+                let key = pack_principal_key(&permission.principal);
+                let current_opt = current
+                    .permissions
+                    .iter()
+                    .find(|current| pack_principal_key(&current.principal) == key);
+                if current_opt.is_none_or(|current| current.access != permission.access) {
+                    changes.push(SetPermission(permission.clone()));
+                }
+            }
+            for permission in &current.permissions {
+                let key = pack_principal_key(&permission.principal);
+                if !permissions
+                    .iter()
+                    .any(|desired| pack_principal_key(&desired.principal) == key)
+                {
+                    changes.push(DeletePermission {
+                        permission_id: permission.id.clone(),
+                        principal: permission.principal.clone(),
+                    });
+                }
+            }
+        }
+
+        if let Some(makers) = &desired.makers {
+            for login_id in makers {
+                if !current
+                    .makers
+                    .iter()
+                    .any(|maker| maker.login_id.eq_ignore_ascii_case(login_id))
+                {
+                    changes.push(AddMaker {
+                        login_id: login_id.clone(),
+                    });
+                }
+            }
+            for maker in &current.makers {
+                if !makers
+                    .iter()
+                    .any(|login_id| maker.login_id.eq_ignore_ascii_case(login_id))
+                {
+                    changes.push(DeleteMaker {
+                        login_id: maker.login_id.clone(),
+                    });
+                }
+            }
+        }
+
+        if let Some(categories) = &desired.categories {
+            for category_name in categories {
+                if !current
+                    .categories
+                    .iter()
+                    .any(|category| &category.category_name == category_name)
+                {
+                    changes.push(AddCategory {
+                        category_name: category_name.clone(),
+                    });
+                }
+            }
+            for category in &current.categories {
+                if !categories.contains(&category.category_name) {
+                    changes.push(DeleteCategory {
+                        category_name: category.category_name.clone(),
+                    });
+                }
+            }
+        }
+
+        if let Some(invitations) = &desired.invitations {
+            for invitation in invitations {
+                let current_opt = current.invitations.iter().find(|current| {
+                    current
+                        .invitee_email
+                        .eq_ignore_ascii_case(&invitation.email)
+                });
+                match current_opt {
+                    None => changes.push(CreateInvitation(invitation.clone())),
+                    Some(current) if current.access != invitation.access => changes.push(UpdateInvitation {
+                        invitation_id: current.invitation_id.clone(),
+                        email: current.invitee_email.clone(),
+                        access: invitation.access,
+                    }),
+                    Some(_) => {}
+                }
+            }
+            for invitation in &current.invitations {
+                if !invitations.iter().any(|desired| {
+                    desired
+                        .email
+                        .eq_ignore_ascii_case(&invitation.invitee_email)
+                }) {
+                    changes.push(DeleteInvitation {
+                        invitation_id: invitation.invitation_id.clone(),
+                        email: invitation.invitee_email.clone(),
+                    });
+                }
+            }
+        }
+
+        Self {
+            pack_id,
+            changes,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Returns a key that identifies the principal (e.g. "user:john@example.com", "workspace:ws-1", "global")
+pub fn pack_principal_key(principal: &PackPrincipal) -> String {
+    use PackPrincipal::*;
+    match principal {
+        UserPrincipal(principal) => format!("user:{}", principal.email.to_lowercase()),
+        WorkspacePrincipal(principal) => format!("workspace:{}", principal.workspace_id),
+        GlobalPrincipal(_) => "global".to_string(),
+        NomosOrganizationPrincipal(principal) => format!("nomosOrganization:{}", principal.nomos_organization_id),
+        GroupPrincipal(principal) => format!("group:{}", principal.group_id),
+        GrammarlyInstitutionPrincipal(principal) => format!("grammarlyInstitution:{}", principal.grammarly_institution_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn must_plan_permission_and_maker_changes() {
+        let current: PackAccessState = serde_json::from_value(json!({
+            "permissions": [
+                {"id": "p1", "access": "view", "principal": {"type": "user", "email": "alice@example.com"}},
+                {"id": "p2", "access": "edit", "principal": {"type": "user", "email": "bob@example.com"}}
+            ],
+            "makers": [{"loginId": "alice@example.com", "name": "Alice"}],
+            "categories": [],
+            "invitations": []
+        }))
+        .unwrap();
+        let desired: PackAccessConfig = serde_json::from_value(json!({
+            "permissions": [{"access": "edit", "principal": {"type": "user", "email": "Alice@example.com"}}],
+            "makers": ["alice@example.com"]
+        }))
+        .unwrap();
+        let plan = PackAccessPlan::new(NonZeroU64::MIN, &desired, &current);
+        let descriptions = plan
+            .changes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            descriptions,
+            vec![
+                "set permission of 'user:alice@example.com' to 'edit'",
+                "delete permission 'p2' of 'user:bob@example.com'"
+            ]
+        );
+    }
+}
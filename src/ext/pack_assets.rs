@@ -0,0 +1,198 @@
+use crate::types::{ImageFileForUpdatePackRequest, PackImageFile, UpdatePackRequestCover, UpdatePackRequestLogo};
+use errgonomic::handle;
+use std::fs::read_dir;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// An uploaded Pack asset
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PackAssetFile {
+    pub asset_id: String,
+    pub filename: String,
+    pub mime_type: String,
+}
+
+impl From<PackAssetFile> for UpdatePackRequestLogo {
+    fn from(file: PackAssetFile) -> Self {
+        Self {
+            asset_id: file.asset_id,
+            filename: file.filename,
+            mime_type: Some(file.mime_type),
+        }
+    }
+}
+
+impl From<PackAssetFile> for UpdatePackRequestCover {
+    fn from(file: PackAssetFile) -> Self {
+        Self {
+            asset_id: file.asset_id,
+            filename: file.filename,
+            mime_type: Some(file.mime_type),
+        }
+    }
+}
+
+impl From<PackAssetFile> for ImageFileForUpdatePackRequest {
+    fn from(file: PackAssetFile) -> Self {
+        Self {
+            asset_id: file.asset_id,
+            filename: file.filename,
+            mime_type: Some(file.mime_type),
+        }
+    }
+}
+
+/// Returns the MIME type of an image by its file extension
+pub fn image_mime_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "svg" => Some("image/svg+xml"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Extracts the asset ID from [`PackAssetUploadInfo::pack_asset_uploaded_path_name`](crate::types::PackAssetUploadInfo::pack_asset_uploaded_path_name) (e.g. "/packs/123/assets/e23fcb5e564f08b71183d424c2c380c0")
+pub fn pack_asset_id_from_path_name(path_name: &str) -> Option<&str> {
+    path_name
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|segment| !segment.is_empty())
+}
+
+/// A local directory of Pack assets:
+///
+/// - `logo.<ext>`: the logo
+/// - `cover.<ext>`: the cover image
+/// - `examples/*`: the example images (in the order of their file names)
+/// - `agent/*`: the agent images (in the order of their file names)
+#[derive(Clone, Debug, Default)]
+pub struct PackAssetsDir {
+    pub logo: Option<PathBuf>,
+    pub cover: Option<PathBuf>,
+    pub example_images: Vec<PathBuf>,
+    pub agent_images: Vec<PathBuf>,
+}
+
+impl PackAssetsDir {
+    pub fn read(dir: impl AsRef<Path>) -> Result<Self, PackAssetsDirReadError> {
+        let dir = dir.as_ref();
+        let mut assets = Self::default();
+        for path in read_images(dir)? {
+            match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some("logo") => assets.logo = Some(path),
+                Some("cover") => assets.cover = Some(path),
+                _ => {}
+            }
+        }
+        let examples_dir = dir.join("examples");
+        if examples_dir.is_dir() {
+            assets.example_images = read_images(&examples_dir)?;
+        }
+        let agent_dir = dir.join("agent");
+        if agent_dir.is_dir() {
+            assets.agent_images = read_images(&agent_dir)?;
+        }
+        Ok(assets)
+    }
+}
+
+fn read_images(dir: &Path) -> Result<Vec<PathBuf>, PackAssetsDirReadError> {
+    use PackAssetsDirReadError::*;
+    let entries = handle!(read_dir(dir), ReadDirFailed, dir: dir.to_path_buf());
+    let mut paths = Vec::new();
+    for entry in entries {
+        let entry = handle!(entry, ReadDirFailed, dir: dir.to_path_buf());
+        let path = entry.path();
+        if path.is_file() && image_mime_type(&path).is_some() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// An image in a list of Pack images (example images or agent images)
+#[derive(Clone, Debug)]
+pub enum PackImageAction {
+    /// The image is already uploaded (matched by file name)
+    Keep(PackImageFile),
+    Upload(PathBuf),
+}
+
+/// Matches the local images with the current images of the Pack by file name. The images that are not present locally are dropped from the list.
+///
+/// The API doesn't return the hashes of the uploaded images, so a changed image with the same file name is kept unless `force` is set.
+pub fn plan_pack_images(local: &[PathBuf], current: &[PackImageFile], force: bool) -> Vec<PackImageAction> {
+    local
+        .iter()
+        .map(|path| {
+            let filename = path.file_name().and_then(|name| name.to_str());
+            let current_opt = current
+                .iter()
+                .find(|image| !force && Some(image.filename.as_str()) == filename);
+            match current_opt {
+                Some(image) => PackImageAction::Keep(image.clone()),
+                None => PackImageAction::Upload(path.clone()),
+            }
+        })
+        .collect()
+}
+
+/// Returns true if the local logo or cover must be uploaded. The API returns only the URL of the current image, so the image is matched by the file name at the end of the URL (an image whose URL doesn't end with its file name is always uploaded).
+///
+/// Like [`plan_pack_images`], a changed image with the same file name is kept unless `force` is set.
+pub fn needs_pack_image_upload(local: &Path, current_url: Option<&str>, force: bool) -> bool {
+    let filename = local.file_name().and_then(|name| name.to_str());
+    let current_filename = current_url
+        .and_then(|url| reqwest::Url::parse(url).ok())
+        .and_then(|url| url.path_segments()?.next_back().map(str::to_owned));
+    force || filename.is_none() || current_filename.as_deref() != filename
+}
+
+#[derive(Error, Debug)]
+pub enum PackAssetsDirReadError {
+    #[error("failed to read directory '{dir}'")]
+    ReadDirFailed { source: io::Error, dir: PathBuf },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn must_extract_asset_id() {
+        assert_eq!(pack_asset_id_from_path_name("/packs/123/assets/e23fcb5e564f08b71183d424c2c380c0"), Some("e23fcb5e564f08b71183d424c2c380c0"));
+    }
+
+    #[test]
+    fn must_keep_images_with_same_filename() {
+        let current = vec![PackImageFile {
+            alt_text: None,
+            asset_id: "a1".into(),
+            filename: "1.png".into(),
+            image_url: "https://example.com/1.png".into(),
+            mime_type: Some("image/png".into()),
+        }];
+        let local = vec![
+            PathBuf::from("examples/1.png"),
+            PathBuf::from("examples/2.png"),
+        ];
+        let plan = plan_pack_images(&local, &current, false);
+        assert!(matches!(&plan[..], [PackImageAction::Keep(image), PackImageAction::Upload(_)] if image.asset_id == "a1"));
+    }
+
+    #[test]
+    fn must_upload_logo_only_if_filename_changed() {
+        let local = Path::new("assets/logo.png");
+        assert!(!needs_pack_image_upload(local, Some("https://example.com/packs/1/assets/a1/logo.png"), false));
+        assert!(needs_pack_image_upload(local, Some("https://example.com/packs/1/assets/a1/logo.png"), true));
+        assert!(needs_pack_image_upload(local, Some("https://example.com/packs/1/assets/a1/logo.svg"), false));
+        assert!(needs_pack_image_upload(local, None, false));
+    }
+}
@@ -0,0 +1,316 @@
+use crate::types::{ListIngestionLogsOrder, ListPackLogsOrder, PackLog, PackLogContext, PackLogDetails, PackLogType, PackLogsList};
+use crate::{Client, DocId, Error, types};
+use chrono::{DateTime, TimeDelta, Utc};
+use errgonomic::handle;
+use futures_util::Stream;
+use futures_util::stream::unfold;
+use std::collections::{HashSet, VecDeque};
+use std::num::NonZeroU64;
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+use tokio::time::sleep;
+use uuid::Uuid;
+
+impl PackLog {
+    pub fn context(&self) -> &PackLogContext {
+        use PackLog::*;
+        match self {
+            CustomLog(log) => &log.context,
+            InvocationLog(log) => &log.context,
+            FetcherLog(log) => &log.context,
+            InternalLog(log) => &log.context,
+            AuthLog(log) => &log.context,
+            IngestionLifecycleLog(log) => &log.context,
+            IngestionDebugLog(log) => &log.context,
+            AgentRuntimeLog(log) => &log.context,
+            McpLog(log) => &log.context,
+        }
+    }
+
+    pub fn log_type(&self) -> PackLogType {
+        use PackLog::*;
+        match self {
+            CustomLog(_) => PackLogType::Custom,
+            InvocationLog(_) => PackLogType::Invocation,
+            FetcherLog(_) => PackLogType::Fetcher,
+            InternalLog(_) => PackLogType::Internal,
+            AuthLog(_) => PackLogType::Auth,
+            IngestionLifecycleLog(_) => PackLogType::IngestionLifecycle,
+            IngestionDebugLog(_) => PackLogType::IngestionDebug,
+            AgentRuntimeLog(_) => PackLogType::AgentRuntime,
+            McpLog(_) => PackLogType::Mcp,
+        }
+    }
+
+    /// Returns true if the log has details that can be fetched with [`PackLogTail::details`]
+    pub fn has_details(&self) -> bool {
+        matches!(self, Self::InvocationLog(_) | Self::FetcherLog(_) | Self::AgentRuntimeLog(_))
+    }
+}
+
+/// The logs to follow
+#[derive(Clone, Debug)]
+pub enum PackLogSource {
+    /// The logs of the Pack in a doc ([`Client::list_pack_logs`])
+    Doc { doc_id: DocId },
+    /// The logs of an ingestion ([`Client::list_ingestion_logs`])
+    Ingestion { tenant_id: String, root_ingestion_id: Uuid },
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PackLogFilter {
+    pub log_types: Option<Vec<PackLogType>>,
+    pub request_ids: Option<Vec<String>>,
+    /// A free-text search query
+    pub q: Option<String>,
+}
+
+/// Follows the new logs of a Pack, like `tail -f`.
+///
+/// Every poll requests the logs in ascending order, starting 1 ms before the timestamp of the last returned log (the `afterTimestamp` parameter is exclusive), so the logs with the same timestamp are deduplicated by their log ID.
+pub struct PackLogTail<'a> {
+    client: &'a Client,
+    pack_id: NonZeroU64,
+    source: PackLogSource,
+    filter: PackLogFilter,
+    poll_interval: Duration,
+    cursor: Option<DateTime<Utc>>,
+    /// The IDs of the returned logs that were created at `cursor`
+    seen: HashSet<String>,
+    buffer: VecDeque<PackLog>,
+    polled: bool,
+}
+
+impl<'a> PackLogTail<'a> {
+    /// Creates a tail that starts at the current time (use [`PackLogTail::since`] to include older logs)
+    pub fn new(client: &'a Client, pack_id: NonZeroU64, source: PackLogSource) -> Self {
+        Self {
+            client,
+            pack_id,
+            source,
+            filter: PackLogFilter::default(),
+            poll_interval: Duration::from_secs(2),
+            cursor: Some(DateTime::from(SystemTime::now())),
+            seen: HashSet::new(),
+            buffer: VecDeque::new(),
+            polled: false,
+        }
+    }
+
+    pub fn with_filter(mut self, filter: PackLogFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Starts at the `timestamp` (or at the oldest available log if `None`)
+    pub fn since(mut self, timestamp: Option<DateTime<Utc>>) -> Self {
+        self.cursor = timestamp;
+        self.seen.clear();
+        self
+    }
+
+    /// Fetches every log that was created since the previous poll (may be empty). The cursor is advanced only if every page is fetched, so a failed poll can be retried without losing logs.
+    pub async fn poll(&mut self) -> Result<Vec<PackLog>, PackLogTailError> {
+        // set before fetching, so that `next` waits before retrying a failed poll
+        self.polled = true;
+        // `afterTimestamp` is exclusive, so the query starts 1 ms before the cursor to include the logs created at the cursor
+        let after_timestamp = self.cursor.map(|cursor| {
+            cursor
+                .checked_sub_signed(TimeDelta::milliseconds(1))
+                .unwrap_or(cursor)
+        });
+        let mut cursor = self.cursor;
+        let mut seen = self.seen.clone();
+        let mut new_logs = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let page = self
+                .list_page(after_timestamp.as_ref(), page_token.as_deref())
+                .await?;
+            push_new_logs(&mut cursor, &mut seen, page.items, &mut new_logs);
+            match page.next_page_token {
+                Some(next_page_token) => page_token = Some(next_page_token.into()),
+                None => break,
+            }
+        }
+        self.cursor = cursor;
+        self.seen = seen;
+        Ok(new_logs)
+    }
+
+    /// Waits for the next log (the first poll is sent without waiting, and every following poll waits for the poll interval, including the retries of a failed poll)
+    pub async fn next(&mut self) -> Result<PackLog, PackLogTailError> {
+        loop {
+            if let Some(log) = self.buffer.pop_front() {
+                return Ok(log);
+            }
+            if self.polled {
+                sleep(self.poll_interval).await;
+            }
+            let logs = self.poll().await?;
+            self.buffer.extend(logs);
+        }
+    }
+
+    /// Converts the tail into an infinite stream of logs. The stream yields an error if a poll fails, and continues from the same cursor on the next item.
+    pub fn into_stream(self) -> impl Stream<Item = Result<PackLog, PackLogTailError>> + 'a {
+        unfold(self, |mut tail| async move {
+            let result = tail.next().await;
+            Some((result, tail))
+        })
+    }
+
+    /// Fetches the details of the log. Returns `None` if the log has no details, or if the source doesn't support fetching them (only ingestion logs have a details endpoint).
+    pub async fn details(&self, log: &PackLog) -> Result<Option<PackLogDetails>, PackLogTailDetailsError> {
+        use PackLogTailDetailsError::*;
+        if !log.has_details() {
+            return Ok(None);
+        }
+        let context = log.context();
+        match &self.source {
+            PackLogSource::Doc {
+                ..
+            } => Ok(None),
+            PackLogSource::Ingestion {
+                tenant_id,
+                root_ingestion_id,
+            } => {
+                let details = handle!(
+                    self.client
+                        .get_pack_log_details(self.pack_id, tenant_id, root_ingestion_id, &context.log_id, &context.details_key)
+                        .await,
+                    GetPackLogDetailsFailed,
+                    log_id: context.log_id.clone()
+                );
+                Ok(Some(details.into_inner()))
+            }
+        }
+    }
+
+    async fn list_page(&self, after_timestamp: Option<&DateTime<Utc>>, page_token: Option<&str>) -> Result<PackLogsList, PackLogTailError> {
+        use PackLogTailError::*;
+        let filter = &self.filter;
+        match &self.source {
+            PackLogSource::Doc {
+                doc_id,
+            } => {
+                let response = handle!(
+                    self.client
+                        .list_pack_logs(self.pack_id, doc_id, after_timestamp, None, None, filter.log_types.as_ref(), Some(ListPackLogsOrder::Asc), page_token, filter.q.as_deref(), filter.request_ids.as_ref())
+                        .await,
+                    ListPackLogsFailed
+                );
+                Ok(response.into_inner())
+            }
+            PackLogSource::Ingestion {
+                tenant_id,
+                root_ingestion_id,
+            } => {
+                let response = handle!(
+                    self.client
+                        .list_ingestion_logs(self.pack_id, tenant_id, root_ingestion_id, after_timestamp, None, None, None, None, filter.log_types.as_ref(), None, Some(ListIngestionLogsOrder::Asc), page_token, filter.q.as_deref(), filter.request_ids.as_ref())
+                        .await,
+                    ListIngestionLogsFailed
+                );
+                Ok(response.into_inner())
+            }
+        }
+    }
+}
+
+/// Appends the `logs` that were not returned yet to `new_logs`, advancing the `cursor` to the timestamp of the last log and keeping the IDs of the logs created at the `cursor` in `seen`
+fn push_new_logs(cursor: &mut Option<DateTime<Utc>>, seen: &mut HashSet<String>, logs: Vec<PackLog>, new_logs: &mut Vec<PackLog>) {
+    for log in logs {
+        let context = log.context();
+        let created_at = context.created_at;
+        if cursor.is_some_and(|cursor| created_at < cursor) || seen.contains(&context.log_id) {
+            continue;
+        }
+        if *cursor != Some(created_at) {
+            *cursor = Some(created_at);
+            seen.clear();
+        }
+        seen.insert(context.log_id.clone());
+        new_logs.push(log);
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PackLogTailError {
+    #[error("failed to list pack logs")]
+    ListPackLogsFailed { source: Box<Error<types::ListPackLogsResponse>> },
+    #[error("failed to list ingestion logs")]
+    ListIngestionLogsFailed { source: Box<Error<types::ListIngestionLogsResponse>> },
+}
+
+#[derive(Error, Debug)]
+pub enum PackLogTailDetailsError {
+    #[error("failed to get details of log '{log_id}'")]
+    GetPackLogDetailsFailed { log_id: String, source: Box<Error<types::GetPackLogDetailsResponse>> },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn log(log_id: &str, created_at: &str) -> PackLog {
+        serde_json::from_value(json!({
+            "type": "custom",
+            "level": "info",
+            "message": log_id,
+            "context": {
+                "connectionId": "c",
+                "createdAt": created_at,
+                "detailsKey": "k",
+                "docId": "d",
+                "formulaName": "f",
+                "logId": log_id,
+                "packId": "1",
+                "packVersion": "1",
+                "requestId": "r",
+                "requestType": "unknown",
+                "userId": "u"
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn must_return_each_log_of_overlapping_pages_once() {
+        let mut cursor = None;
+        let mut seen = HashSet::new();
+        let mut new_logs = Vec::new();
+        // every poll after the first starts 1 ms before the cursor, so the logs at the cursor (and any log that was returned late) are returned again
+        let polls = [
+            vec![
+                log("a", "2025-01-01T00:00:00.000Z"),
+                log("b", "2025-01-01T00:00:01.000Z"),
+            ],
+            vec![
+                log("a", "2025-01-01T00:00:00.000Z"),
+                log("b", "2025-01-01T00:00:01.000Z"),
+                log("c", "2025-01-01T00:00:01.000Z"),
+            ],
+            vec![
+                log("b", "2025-01-01T00:00:01.000Z"),
+                log("c", "2025-01-01T00:00:01.000Z"),
+                log("d", "2025-01-01T00:00:02.000Z"),
+            ],
+            vec![log("d", "2025-01-01T00:00:02.000Z")],
+        ];
+        for logs in polls {
+            push_new_logs(&mut cursor, &mut seen, logs, &mut new_logs);
+        }
+        let log_ids = new_logs
+            .iter()
+            .map(|log| log.context().log_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(log_ids, ["a", "b", "c", "d"]);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::types::{CreatePackReleaseRequest, CreatePackVersionRequest, GetNextPackVersionRequest, PackRelease, PackSourceCodeUploadCompleteRequest, PackVersionDiffs, RegisterPackVersionRequest, UploadPackSourceCodeRequest, ValidationError};
+use crate::{Client, Error, UploadToSignedUrlError, sha256_hex, types, upload_to_signed_url};
+use bytes::Bytes;
+use errgonomic::handle;
+use std::fs::read;
+use std::io;
+use std::num::NonZeroU64;
+use std::path::{Path, PathBuf};
+use std::string::FromUtf8Error;
+use thiserror::Error;
+
+/// Publishes Pack versions: registers the version, uploads the bundle (and optionally the source code), fetches the diffs from the previous version and optionally creates a release.
+pub struct PackPublisher<'a> {
+    pub client: &'a Client,
+    /// The client for the pre-signed upload URLs (must not send the Coda API key)
+    pub http: reqwest::Client,
+}
+
+/// A Pack version to publish
+#[derive(Clone, Debug)]
+pub struct PackVersionBundle {
+    /// The upload payload produced by the Packs SDK build (the JSON with the metadata and the compiled bundle)
+    pub bundle: Bytes,
+    /// The Pack metadata as JSON
+    pub metadata: String,
+    pub sdk_version: Option<String>,
+    /// The version to publish (defaults to the next version suggested by Coda)
+    pub pack_version: Option<String>,
+    pub notes: Option<String>,
+    pub allow_older_sdk_version: Option<bool>,
+    pub source_code: Option<PackSourceCode>,
+    /// Create a release for the version
+    pub release: bool,
+    pub release_notes: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PackSourceCode {
+    pub filename: String,
+    pub content: Bytes,
+}
+
+#[derive(Clone, Debug)]
+pub struct PackPublishResult {
+    pub pack_version: String,
+    /// The findings returned by Coda for the proposed metadata (e.g. breaking changes)
+    pub findings: Vec<String>,
+    pub deprecation_warnings: Vec<ValidationError>,
+    /// The diffs from the previous version (`None` for the first version of the Pack)
+    pub diffs: Option<PackVersionDiffs>,
+    pub release: Option<PackRelease>,
+}
+
+impl PackVersionBundle {
+    pub fn new(bundle: impl Into<Bytes>, metadata: String) -> Self {
+        Self {
+            bundle: bundle.into(),
+            metadata,
+            sdk_version: None,
+            pack_version: None,
+            notes: None,
+            allow_older_sdk_version: None,
+            source_code: None,
+            release: false,
+            release_notes: None,
+        }
+    }
+
+    pub fn from_files(bundle_path: impl AsRef<Path>, metadata_path: impl AsRef<Path>) -> Result<Self, PackVersionBundleFromFilesError> {
+        use PackVersionBundleFromFilesError::*;
+        let bundle_path = bundle_path.as_ref();
+        let metadata_path = metadata_path.as_ref();
+        let bundle = handle!(read(bundle_path), ReadBundleFailed, path: bundle_path.to_path_buf());
+        let metadata_bytes = handle!(read(metadata_path), ReadMetadataFailed, path: metadata_path.to_path_buf());
+        let metadata = handle!(String::from_utf8(metadata_bytes), MetadataNotUtf8, path: metadata_path.to_path_buf());
+        Ok(Self::new(bundle, metadata))
+    }
+}
+
+impl<'a> PackPublisher<'a> {
+    pub fn new(client: &'a Client) -> Self {
+        Self {
+            client,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn publish(&self, pack_id: NonZeroU64, bundle: &PackVersionBundle) -> Result<PackPublishResult, PackPublishError> {
+        use PackPublishError::*;
+        let client = self.client;
+
+        let versions = handle!(
+            client
+                .list_pack_versions(pack_id, NonZeroU64::new(1), None)
+                .await,
+            ListPackVersionsFailed
+        )
+        .into_inner();
+        let previous_version = versions
+            .items
+            .into_iter()
+            .next()
+            .map(|version| version.pack_version);
+
+        let next_version_request = GetNextPackVersionRequest {
+            proposed_metadata: bundle.metadata.clone(),
+            sdk_version: bundle.sdk_version.clone(),
+        };
+        let next_version = handle!(
+            client
+                .get_next_pack_version(pack_id, &next_version_request)
+                .await,
+            GetNextPackVersionFailed
+        )
+        .into_inner();
+        let pack_version = bundle
+            .pack_version
+            .clone()
+            .unwrap_or(next_version.next_version);
+
+        let register_request = RegisterPackVersionRequest {
+            bundle_hash: sha256_hex(&bundle.bundle),
+        };
+        let upload_info = handle!(
+            client
+                .register_pack_version(pack_id, &pack_version, &register_request)
+                .await,
+            RegisterPackVersionFailed,
+            pack_version
+        )
+        .into_inner();
+        handle!(upload_to_signed_url(&self.http, &upload_info.upload_url, &upload_info.headers, bundle.bundle.clone()).await, UploadBundleFailed, pack_version);
+
+        let create_request = CreatePackVersionRequest {
+            allow_older_sdk_version: bundle.allow_older_sdk_version,
+            notes: bundle.notes.clone(),
+            source: None,
+        };
+        let created = handle!(
+            client
+                .pack_version_upload_complete(pack_id, &pack_version, &create_request)
+                .await,
+            PackVersionUploadCompleteFailed,
+            pack_version
+        )
+        .into_inner();
+
+        if let Some(source_code) = &bundle.source_code {
+            handle!(
+                self.upload_source_code(pack_id, &pack_version, source_code)
+                    .await,
+                UploadSourceCodeFailed,
+                pack_version
+            );
+        }
+
+        let diffs = match &previous_version {
+            Some(previous_version) => Some(handle!(client.get_pack_version_diffs(pack_id, previous_version, &pack_version).await, FetchDiffsFailed, pack_version).into_inner()),
+            None => None,
+        };
+
+        let release = if bundle.release {
+            let release_request = CreatePackReleaseRequest {
+                pack_version: pack_version.clone(),
+                release_notes: bundle.release_notes.clone(),
+            };
+            Some(handle!(client.create_pack_release(pack_id, &release_request).await, CreatePackReleaseFailed, pack_version).into_inner())
+        } else {
+            None
+        };
+
+        Ok(PackPublishResult {
+            pack_version,
+            findings: next_version.findings,
+            deprecation_warnings: created.deprecation_warnings,
+            diffs,
+            release,
+        })
+    }
+
+    pub async fn upload_source_code(&self, pack_id: NonZeroU64, pack_version: &str, source_code: &PackSourceCode) -> Result<(), PackUploadSourceCodeError> {
+        use PackUploadSourceCodeError::*;
+        let client = self.client;
+        let hash = sha256_hex(&source_code.content);
+        let upload_request = UploadPackSourceCodeRequest {
+            filename: source_code.filename.clone(),
+            pack_version: Some(pack_version.to_string()),
+            payload_hash: hash.clone(),
+        };
+        let upload_info = handle!(
+            client
+                .upload_pack_source_code(pack_id, &upload_request)
+                .await,
+            UploadPackSourceCodeFailed
+        )
+        .into_inner();
+        handle!(upload_to_signed_url(&self.http, &upload_info.upload_url, &upload_info.headers, source_code.content.clone()).await, UploadFailed);
+        let complete_request = PackSourceCodeUploadCompleteRequest {
+            code_hash: hash,
+            filename: source_code.filename.clone(),
+        };
+        handle!(
+            client
+                .pack_source_code_upload_complete(pack_id, pack_version, &complete_request)
+                .await,
+            PackSourceCodeUploadCompleteFailed
+        );
+        Ok(())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PackVersionBundleFromFilesError {
+    #[error("failed to read bundle from '{path}'")]
+    ReadBundleFailed { source: io::Error, path: PathBuf },
+    #[error("failed to read metadata from '{path}'")]
+    ReadMetadataFailed { source: io::Error, path: PathBuf },
+    #[error("metadata in '{path}' is not valid UTF-8")]
+    MetadataNotUtf8 { source: FromUtf8Error, path: PathBuf },
+}
+
+#[derive(Error, Debug)]
+pub enum PackPublishError {
+    #[error("failed to list pack versions")]
+    ListPackVersionsFailed { source: Box<Error<types::ListPackVersionsResponse>> },
+    #[error("failed to get next pack version")]
+    GetNextPackVersionFailed { source: Box<Error<types::GetNextPackVersionResponse>> },
+    #[error("failed to register pack version '{pack_version}'")]
+    RegisterPackVersionFailed { pack_version: String, source: Box<Error<types::RegisterPackVersionResponse>> },
+    #[error("failed to upload bundle of pack version '{pack_version}'")]
+    UploadBundleFailed { pack_version: String, source: UploadToSignedUrlError },
+    #[error("failed to complete upload of pack version '{pack_version}'")]
+    PackVersionUploadCompleteFailed { pack_version: String, source: Box<Error<types::PackVersionUploadCompleteResponse>> },
+    #[error("failed to upload source code of pack version '{pack_version}'")]
+    UploadSourceCodeFailed { pack_version: String, source: PackUploadSourceCodeError },
+    #[error("failed to fetch diffs of pack version '{pack_version}'")]
+    FetchDiffsFailed { pack_version: String, source: Box<Error<types::GetPackVersionDiffsResponse>> },
+    #[error("failed to create release for pack version '{pack_version}'")]
+    CreatePackReleaseFailed { pack_version: String, source: Box<Error<types::CreatePackReleaseResponse>> },
+}
+
+#[derive(Error, Debug)]
+pub enum PackUploadSourceCodeError {
+    #[error("failed to request source code upload")]
+    UploadPackSourceCodeFailed { source: Box<Error<types::UploadPackSourceCodeResponse>> },
+    #[error("failed to upload source code")]
+    UploadFailed { source: UploadToSignedUrlError },
+    #[error("failed to complete source code upload")]
+    PackSourceCodeUploadCompleteFailed { source: Box<Error<types::PackSourceCodeUploadCompleteResponse>> },
+}
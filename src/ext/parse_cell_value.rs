@@ -115,6 +115,81 @@ mod time_impls {
 #[cfg(feature = "time")]
 pub use time_impls::*;
 
+/// Implements `TryFrom<CellValue>` for `Option<$target>` (empty strings are converted to `None`)
+#[cfg(any(feature = "chrono", feature = "jiff"))]
+macro_rules! impl_try_from_cell_value_for_option {
+    ($target:ty, $parse:path, $parse_error:ty, $error:ident, $description:literal) => {
+        impl TryFrom<CellValue> for Option<$target> {
+            type Error = $error;
+
+            fn try_from(value: CellValue) -> Result<Self, Self::Error> {
+                use $error::*;
+                let string = handle!(String::try_from(value), ConvertCellValueToStringFailed);
+                let trimmed = string.trim();
+                if trimmed.is_empty() {
+                    return Ok(None);
+                }
+
+                Ok(Some(handle!($parse(trimmed), ParseFailed, value: trimmed)))
+            }
+        }
+
+        #[derive(Debug, Error)]
+        pub enum $error {
+            #[error("failed to convert cell value to {} string: {source}", $description)]
+            ConvertCellValueToStringFailed {
+                #[source]
+                source: ConvertCellValueToStringError,
+            },
+            #[error("failed to parse {} '{value}': {source}", $description)]
+            ParseFailed {
+                #[source]
+                source: $parse_error,
+                value: String,
+            },
+        }
+    };
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::*;
+    use crate::{ParseChronoTimeDeltaError, parse_chrono_date_time, parse_chrono_naive_date, parse_chrono_naive_time, parse_chrono_time_delta};
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeDelta};
+    use errgonomic::handle;
+
+    impl_try_from_cell_value_for_option!(DateTime<FixedOffset>, parse_chrono_date_time, chrono::ParseError, ConvertCellValueToOptionDateTimeError, "timestamp");
+
+    impl_try_from_cell_value_for_option!(NaiveDate, parse_chrono_naive_date, chrono::ParseError, ConvertCellValueToOptionNaiveDateError, "date");
+
+    impl_try_from_cell_value_for_option!(NaiveTime, parse_chrono_naive_time, chrono::ParseError, ConvertCellValueToOptionNaiveTimeError, "time");
+
+    impl_try_from_cell_value_for_option!(TimeDelta, parse_chrono_time_delta, ParseChronoTimeDeltaError, ConvertCellValueToOptionTimeDeltaError, "duration");
+}
+
+#[cfg(feature = "chrono")]
+pub use chrono_impls::*;
+
+#[cfg(feature = "jiff")]
+mod jiff_impls {
+    use super::*;
+    use crate::{ParseJiffSpanError, parse_jiff_date, parse_jiff_span, parse_jiff_time, parse_jiff_timestamp};
+    use errgonomic::handle;
+    use jiff::civil::{Date, Time};
+    use jiff::{Span, Timestamp};
+
+    impl_try_from_cell_value_for_option!(Timestamp, parse_jiff_timestamp, jiff::Error, ConvertCellValueToOptionTimestampError, "timestamp");
+
+    impl_try_from_cell_value_for_option!(Date, parse_jiff_date, jiff::Error, ConvertCellValueToOptionDateError, "date");
+
+    impl_try_from_cell_value_for_option!(Time, parse_jiff_time, jiff::Error, ConvertCellValueToOptionTimeError, "time");
+
+    impl_try_from_cell_value_for_option!(Span, parse_jiff_span, ParseJiffSpanError, ConvertCellValueToOptionSpanError, "duration");
+}
+
+#[cfg(feature = "jiff")]
+pub use jiff_impls::*;
+
 #[derive(Debug, Error)]
 pub enum ConvertCellValueToStringError {
     #[error("cell value is not a string: {cell_value:?}")]
@@ -156,4 +231,16 @@ pub enum ConvertCellValueError {
         #[source]
         source: ConvertCellValueToOptionOffsetDateTimeError,
     },
+    #[cfg(feature = "chrono")]
+    #[error("failed to convert cell value to timestamp: {source}")]
+    ConvertCellValueToDateTimeFailed {
+        #[source]
+        source: ConvertCellValueToOptionDateTimeError,
+    },
+    #[cfg(feature = "jiff")]
+    #[error("failed to convert cell value to timestamp: {source}")]
+    ConvertCellValueToTimestampFailed {
+        #[source]
+        source: ConvertCellValueToOptionTimestampError,
+    },
 }
@@ -1,3 +1,105 @@
+/// Implements `TryFrom<&RichValue>` and `TryFrom<RichValue>` for `Option<$target>` (empty strings are converted to `None`)
+#[cfg(any(feature = "chrono", feature = "jiff"))]
+macro_rules! impl_try_from_rich_value_for_option {
+    ($target:ty, $parse:path, $parse_error:ty, $ref_error:ident, $owned_error:ident, $description:literal) => {
+        #[derive(Debug, Error)]
+        pub enum $ref_error {
+            #[error("failed to convert rich value to string")]
+            ConvertStringFailed { source: ConvertRichValueRefToStringError },
+            #[error("failed to parse {}", $description)]
+            ParseFailed { source: $parse_error },
+        }
+
+        #[derive(Debug, Error)]
+        pub enum $owned_error {
+            #[error("failed to convert rich value to string")]
+            ConvertStringFailed { source: ConvertRichValueToStringError },
+            #[error("failed to parse {} '{value}'", $description)]
+            ParseFailed { source: $parse_error, value: String },
+        }
+
+        impl TryFrom<&RichValue> for Option<$target> {
+            type Error = $ref_error;
+
+            fn try_from(value: &RichValue) -> Result<Self, Self::Error> {
+                use $ref_error::*;
+                let string = handle!(String::try_from(value), ConvertStringFailed);
+                let trimmed = string.trim();
+                if trimmed.is_empty() {
+                    return Ok(None);
+                }
+
+                Ok(Some(handle!($parse(trimmed), ParseFailed)))
+            }
+        }
+
+        impl TryFrom<RichValue> for Option<$target> {
+            type Error = $owned_error;
+
+            fn try_from(value: RichValue) -> Result<Self, Self::Error> {
+                use $owned_error::*;
+                let string = handle!(String::try_from(value), ConvertStringFailed);
+                let trimmed = string.trim();
+                if trimmed.is_empty() {
+                    return Ok(None);
+                }
+
+                Ok(Some(handle!($parse(trimmed), ParseFailed, value: trimmed)))
+            }
+        }
+    };
+}
+
+/// Implements `TryFrom<&RichValue>` and `TryFrom<RichValue>` for `$target` (empty strings are rejected)
+#[cfg(any(feature = "chrono", feature = "jiff"))]
+macro_rules! impl_try_from_rich_value_for_required {
+    ($target:ty, $parse:path, $parse_error:ty, $ref_error:ident, $owned_error:ident, $description:literal) => {
+        #[derive(Debug, Error)]
+        pub enum $ref_error {
+            #[error("failed to convert rich value to string")]
+            ConvertStringFailed { source: ConvertRichValueRefToStringError },
+            #[error("{} string is empty", $description)]
+            EmptyInvalid,
+            #[error("failed to parse {}", $description)]
+            ParseFailed { source: $parse_error },
+        }
+
+        #[derive(Debug, Error)]
+        pub enum $owned_error {
+            #[error("failed to convert rich value to string")]
+            ConvertStringFailed { source: ConvertRichValueToStringError },
+            #[error("{} string is empty", $description)]
+            EmptyInvalid,
+            #[error("failed to parse {} '{value}'", $description)]
+            ParseFailed { source: $parse_error, value: String },
+        }
+
+        impl TryFrom<&RichValue> for $target {
+            type Error = $ref_error;
+
+            fn try_from(value: &RichValue) -> Result<Self, Self::Error> {
+                use $ref_error::*;
+                let string = handle!(String::try_from(value), ConvertStringFailed);
+                let trimmed = string.trim();
+                handle_bool!(trimmed.is_empty(), EmptyInvalid);
+                Ok(handle!($parse(trimmed), ParseFailed))
+            }
+        }
+
+        impl TryFrom<RichValue> for $target {
+            type Error = $owned_error;
+
+            fn try_from(value: RichValue) -> Result<Self, Self::Error> {
+                use $owned_error::*;
+                let string = handle!(String::try_from(value), ConvertStringFailed);
+                let trimmed = string.trim();
+                handle_bool!(trimmed.is_empty(), EmptyInvalid);
+                Ok(handle!($parse(trimmed), ParseFailed, value: trimmed))
+            }
+        }
+    };
+}
+
 mod impl_try_from_rich_value_for_string;
 
 pub use impl_try_from_rich_value_for_string::*;
@@ -32,6 +134,18 @@ mod impl_try_from_rich_value_for_offset_date_time;
 #[cfg(feature = "time")]
 pub use impl_try_from_rich_value_for_offset_date_time::*;
 
+#[cfg(feature = "chrono")]
+mod impl_try_from_rich_value_for_chrono;
+
+#[cfg(feature = "chrono")]
+pub use impl_try_from_rich_value_for_chrono::*;
+
+#[cfg(feature = "jiff")]
+mod impl_try_from_rich_value_for_jiff;
+
+#[cfg(feature = "jiff")]
+pub use impl_try_from_rich_value_for_jiff::*;
+
 mod impl_try_from_rich_value_for_rich_row_reference;
 
 pub use impl_try_from_rich_value_for_rich_row_reference::*;
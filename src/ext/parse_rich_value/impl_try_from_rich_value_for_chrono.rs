@@ -0,0 +1,14 @@
+use crate::{ConvertRichValueRefToStringError, ConvertRichValueToStringError, ParseChronoTimeDeltaError, RichValue, parse_chrono_date_time, parse_chrono_naive_date, parse_chrono_naive_time, parse_chrono_time_delta};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeDelta};
+use errgonomic::{handle, handle_bool};
+use thiserror::Error;
+
+impl_try_from_rich_value_for_required!(DateTime<FixedOffset>, parse_chrono_date_time, chrono::ParseError, ConvertRichValueRefToDateTimeError, ConvertRichValueToDateTimeError, "RFC3339 timestamp");
+
+impl_try_from_rich_value_for_option!(DateTime<FixedOffset>, parse_chrono_date_time, chrono::ParseError, ConvertRichValueRefToOptionDateTimeError, ConvertRichValueToOptionDateTimeError, "RFC3339 timestamp");
+
+impl_try_from_rich_value_for_option!(NaiveDate, parse_chrono_naive_date, chrono::ParseError, ConvertRichValueRefToOptionNaiveDateError, ConvertRichValueToOptionNaiveDateError, "date");
+
+impl_try_from_rich_value_for_option!(NaiveTime, parse_chrono_naive_time, chrono::ParseError, ConvertRichValueRefToOptionNaiveTimeError, ConvertRichValueToOptionNaiveTimeError, "time");
+
+impl_try_from_rich_value_for_option!(TimeDelta, parse_chrono_time_delta, ParseChronoTimeDeltaError, ConvertRichValueRefToOptionTimeDeltaError, ConvertRichValueToOptionTimeDeltaError, "duration");
@@ -0,0 +1,15 @@
+use crate::{ConvertRichValueRefToStringError, ConvertRichValueToStringError, ParseJiffSpanError, RichValue, parse_jiff_date, parse_jiff_span, parse_jiff_time, parse_jiff_timestamp};
+use errgonomic::{handle, handle_bool};
+use jiff::civil::{Date, Time};
+use jiff::{Span, Timestamp};
+use thiserror::Error;
+
+impl_try_from_rich_value_for_required!(Timestamp, parse_jiff_timestamp, jiff::Error, ConvertRichValueRefToTimestampError, ConvertRichValueToTimestampError, "RFC3339 timestamp");
+
+impl_try_from_rich_value_for_option!(Timestamp, parse_jiff_timestamp, jiff::Error, ConvertRichValueRefToOptionTimestampError, ConvertRichValueToOptionTimestampError, "RFC3339 timestamp");
+
+impl_try_from_rich_value_for_option!(Date, parse_jiff_date, jiff::Error, ConvertRichValueRefToOptionDateError, ConvertRichValueToOptionDateError, "date");
+
+impl_try_from_rich_value_for_option!(Time, parse_jiff_time, jiff::Error, ConvertRichValueRefToOptionTimeError, ConvertRichValueToOptionTimeError, "time");
+
+impl_try_from_rich_value_for_option!(Span, parse_jiff_span, ParseJiffSpanError, ConvertRichValueRefToOptionSpanError, ConvertRichValueToOptionSpanError, "duration");
@@ -0,0 +1,28 @@
+/// Appends a CSV line with the `fields`, quoting the fields that contain commas, quotes or line breaks (RFC 4180)
+pub fn push_csv_line(csv: &mut String, fields: &[&str]) {
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            csv.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            csv.push('"');
+            csv.push_str(&field.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(field);
+        }
+    }
+    csv.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn must_quote_csv_fields() {
+        let mut csv = String::new();
+        push_csv_line(&mut csv, &["a", "b,c", "say \"hi\""]);
+        assert_eq!(csv, "a,\"b,c\",\"say \"\"hi\"\"\"\n");
+    }
+}
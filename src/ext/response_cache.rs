@@ -0,0 +1,547 @@
+use crate::{DocId, RequestPathIds, sha256_hex};
+use chrono::{DateTime, Utc};
+use futures_util::FutureExt;
+use futures_util::future::BoxFuture;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, ETAG, HeaderValue, IF_NONE_MATCH};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tokio::fs;
+
+/// The operations that return doc metadata (see [`ResponseCache::with_metadata_ttl`])
+pub const METADATA_OPERATIONS: [&str; 11] = [
+    "get_doc",
+    "list_pages",
+    "get_page",
+    "list_tables",
+    "get_table",
+    "list_columns",
+    "get_column",
+    "list_formulas",
+    "get_formula",
+    "list_controls",
+    "get_control",
+];
+
+/// Identifies a cached response: the operation, the hash of the `Authorization` header (so that the responses for one API key are never returned for another) and the full request URL (including the query parameters)
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CacheKey {
+    pub operation_id: &'static str,
+    pub authorization_hash: Option<String>,
+    pub doc_id: Option<DocId>,
+    pub url: String,
+}
+
+impl CacheKey {
+    pub fn new(operation_id: &'static str, authorization_hash: Option<String>, url: &reqwest::Url) -> Self {
+        Self {
+            operation_id,
+            authorization_hash,
+            doc_id: RequestPathIds::from_path(url.path()).doc_id,
+            url: url.to_string(),
+        }
+    }
+}
+
+/// Returns the hash that identifies the `authorization` in a [`CacheKey`]
+pub fn authorization_hash(authorization: &HeaderValue) -> String {
+    sha256_hex(authorization.as_bytes())
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct CachedResponse {
+    pub doc_id: Option<DocId>,
+    pub stored_at: DateTime<Utc>,
+    pub etag: Option<String>,
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+impl CachedResponse {
+    /// Returns `None` if the response is not cacheable (the body must be UTF-8)
+    pub async fn from_response(key: &CacheKey, response: reqwest::Response) -> reqwest::Result<(Option<Self>, reqwest::Response)> {
+        let status = response.status();
+        let header_str = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        let etag = header_str(ETAG);
+        let content_type = header_str(CONTENT_TYPE);
+        let bytes = response.bytes().await?;
+        let cached_opt = String::from_utf8(bytes.to_vec()).ok().map(|body| Self {
+            doc_id: key.doc_id.clone(),
+            stored_at: DateTime::<Utc>::from(SystemTime::now()),
+            etag: etag.clone(),
+            content_type: content_type.clone(),
+            body,
+        });
+        let response = build_response(status, etag.as_deref(), content_type.as_deref(), bytes);
+        Ok((cached_opt, response))
+    }
+
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        DateTime::<Utc>::from(SystemTime::now())
+            .signed_duration_since(self.stored_at)
+            .to_std()
+            .is_ok_and(|age| age < ttl)
+    }
+
+    pub fn to_response(&self) -> reqwest::Response {
+        build_response(StatusCode::OK, self.etag.as_deref(), self.content_type.as_deref(), self.body.clone())
+    }
+}
+
+fn build_response(status: StatusCode, etag: Option<&str>, content_type: Option<&str>, body: impl Into<reqwest::Body>) -> reqwest::Response {
+    let mut response = http::Response::new(body.into());
+    *response.status_mut() = status;
+    let headers = [(ETAG, etag), (CONTENT_TYPE, content_type)];
+    for (name, value_opt) in headers {
+        if let Some(value) = value_opt.and_then(|value| HeaderValue::from_str(value).ok()) {
+            response.headers_mut().insert(name, value);
+        }
+    }
+    reqwest::Response::from(response)
+}
+
+/// A storage for [`ResponseCache`]. The stores are best-effort: a failure to read or write an entry is treated as a cache miss.
+///
+/// The methods return futures, so that the stores don't block the runtime while they do I/O.
+pub trait CacheStore: Send + Sync {
+    fn get<'a>(&'a self, key: &'a CacheKey) -> BoxFuture<'a, Option<CachedResponse>>;
+    fn put<'a>(&'a self, key: &'a CacheKey, response: CachedResponse) -> BoxFuture<'a, ()>;
+    fn invalidate_doc<'a>(&'a self, doc_id: &'a str) -> BoxFuture<'a, ()>;
+    fn clear(&self) -> BoxFuture<'_, ()>;
+}
+
+/// Keeps up to `capacity` responses in memory, evicting the least recently used one
+pub struct MemoryCacheStore {
+    capacity: NonZeroUsize,
+    state: Mutex<MemoryCacheState>,
+}
+
+#[derive(Default)]
+struct MemoryCacheState {
+    entries: HashMap<CacheKey, (CachedResponse, u64)>,
+    tick: u64,
+}
+
+impl MemoryCacheStore {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(MemoryCacheState::default()),
+        }
+    }
+}
+
+impl MemoryCacheState {
+    fn next_tick(&mut self) -> u64 {
+        self.tick = self.tick.saturating_add(1);
+        self.tick
+    }
+}
+
+impl CacheStore for MemoryCacheStore {
+    fn get<'a>(&'a self, key: &'a CacheKey) -> BoxFuture<'a, Option<CachedResponse>> {
+        async move {
+            let mut state = self.state.lock().ok()?;
+            let tick = state.next_tick();
+            let (response, used_at) = state.entries.get_mut(key)?;
+            *used_at = tick;
+            Some(response.clone())
+        }
+        .boxed()
+    }
+
+    fn put<'a>(&'a self, key: &'a CacheKey, response: CachedResponse) -> BoxFuture<'a, ()> {
+        async move {
+            let Ok(mut state) = self.state.lock() else {
+                return;
+            };
+            let tick = state.next_tick();
+            state.entries.insert(key.clone(), (response, tick));
+            while state.entries.len() > self.capacity.get() {
+                let oldest_opt = state
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, (_, used_at))| *used_at)
+                    .map(|(key, _)| key.clone());
+                match oldest_opt {
+                    Some(oldest) => state.entries.remove(&oldest),
+                    None => break,
+                };
+            }
+        }
+        .boxed()
+    }
+
+    fn invalidate_doc<'a>(&'a self, doc_id: &'a str) -> BoxFuture<'a, ()> {
+        async move {
+            if let Ok(mut state) = self.state.lock() {
+                state
+                    .entries
+                    .retain(|key, _| key.doc_id.as_deref() != Some(doc_id));
+            }
+        }
+        .boxed()
+    }
+
+    fn clear(&self) -> BoxFuture<'_, ()> {
+        async move {
+            if let Ok(mut state) = self.state.lock() {
+                state.entries.clear();
+            }
+        }
+        .boxed()
+    }
+}
+
+/// Keeps the responses as JSON files in `dir/{doc_id}/{hash}.json`, where `hash` covers every field of the [`CacheKey`] (the responses that don't belong to a doc are kept in `dir/_`)
+pub struct DiskCacheStore {
+    dir: PathBuf,
+}
+
+impl DiskCacheStore {
+    const NO_DOC_DIR: &'static str = "_";
+
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+        }
+    }
+
+    fn doc_dir(&self, doc_id: Option<&str>) -> PathBuf {
+        let name = doc_id.map_or_else(|| Self::NO_DOC_DIR.to_owned(), sanitize_file_name);
+        self.dir.join(name)
+    }
+
+    fn path(&self, key: &CacheKey) -> PathBuf {
+        let authorization_hash = key.authorization_hash.as_deref().unwrap_or_default();
+        let hash = sha256_hex(format!("{} {} {}", key.operation_id, authorization_hash, key.url).as_bytes());
+        self.doc_dir(key.doc_id.as_deref())
+            .join(format!("{hash}.json"))
+    }
+}
+
+impl CacheStore for DiskCacheStore {
+    fn get<'a>(&'a self, key: &'a CacheKey) -> BoxFuture<'a, Option<CachedResponse>> {
+        async move {
+            let contents = fs::read(self.path(key)).await.ok()?;
+            serde_json::from_slice(&contents).ok()
+        }
+        .boxed()
+    }
+
+    fn put<'a>(&'a self, key: &'a CacheKey, response: CachedResponse) -> BoxFuture<'a, ()> {
+        async move {
+            let path = self.path(key);
+            let Ok(contents) = serde_json::to_vec(&response) else {
+                return;
+            };
+            if let Some(parent) = path.parent()
+                && fs::create_dir_all(parent).await.is_ok()
+            {
+                fs::write(path, contents).await.ok();
+            }
+        }
+        .boxed()
+    }
+
+    fn invalidate_doc<'a>(&'a self, doc_id: &'a str) -> BoxFuture<'a, ()> {
+        async move {
+            fs::remove_dir_all(self.doc_dir(Some(doc_id))).await.ok();
+        }
+        .boxed()
+    }
+
+    fn clear(&self) -> BoxFuture<'_, ()> {
+        async move {
+            fs::remove_dir_all(&self.dir).await.ok();
+        }
+        .boxed()
+    }
+}
+
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|char| if char.is_ascii_alphanumeric() || char == '-' || char == '_' { char } else { '%' })
+        .collect()
+}
+
+/// An opt-in cache for the `GET` responses of [`RawClient`](crate::RawClient). Only the operations with a TTL are cached.
+///
+/// - A fresh entry is returned without sending the request (and without taking a token from the [`Limiter`](crate::Limiter) of [`Client`](crate::Client)).
+/// - A stale entry with an `ETag` is revalidated with `If-None-Match`, so a `304 Not Modified` response refreshes the entry.
+/// - The entries are keyed by the hash of the `Authorization` header, so a store may be shared by clients with different API keys. The header is read from the request if it is set there (e.g. by a [`Middleware`](crate::Middleware)), otherwise it is the one passed to [`ResponseCache::with_authorization`]. [`ClientBuilder::build`](crate::ClientBuilder::build) passes the API key of the client, but [`Client::with_cache`](crate::Client::with_cache) can't read it, so call [`ResponseCache::with_authorization`] yourself if the store is shared.
+/// - Any non-`GET` request to a doc invalidates the cached responses of that doc. Coda applies most writes asynchronously (the request returns `202 Accepted` before the doc is changed), so a read that is sent before the write is applied may cache the old state again. If the next reads must see the write, wait for it with [`Client::wait_for_mutation`](crate::Client::wait_for_mutation) and then call [`CacheStore::invalidate_doc`] on [`ResponseCache::store`].
+pub struct ResponseCache {
+    store: Box<dyn CacheStore>,
+    ttls: HashMap<&'static str, Duration>,
+    authorization_hash: Option<String>,
+}
+
+impl ResponseCache {
+    pub fn new(store: impl CacheStore + 'static) -> Self {
+        Self {
+            store: Box::new(store),
+            ttls: HashMap::new(),
+            authorization_hash: None,
+        }
+    }
+
+    pub fn memory(capacity: NonZeroUsize) -> Self {
+        Self::new(MemoryCacheStore::new(capacity))
+    }
+
+    pub fn disk(dir: impl Into<PathBuf>) -> Self {
+        Self::new(DiskCacheStore::new(dir))
+    }
+
+    /// Caches the responses of the `operation_id` (see [`OperationInfo::operation_id`](progenitor_client::OperationInfo::operation_id)) for the `ttl`
+    pub fn with_ttl(mut self, operation_id: &'static str, ttl: Duration) -> Self {
+        self.ttls.insert(operation_id, ttl);
+        self
+    }
+
+    /// Caches the responses of the [`METADATA_OPERATIONS`] for the `ttl`
+    pub fn with_metadata_ttl(self, ttl: Duration) -> Self {
+        METADATA_OPERATIONS
+            .into_iter()
+            .fold(self, |cache, operation_id| cache.with_ttl(operation_id, ttl))
+    }
+
+    /// Keys the entries by the `authorization` header of the client (used if the request doesn't set the header itself)
+    pub fn with_authorization(mut self, authorization: &HeaderValue) -> Self {
+        self.authorization_hash = Some(authorization_hash(authorization));
+        self
+    }
+
+    pub fn ttl(&self, operation_id: &str) -> Option<Duration> {
+        self.ttls.get(operation_id).copied()
+    }
+
+    pub fn store(&self) -> &dyn CacheStore {
+        self.store.as_ref()
+    }
+
+    /// Sends the `request` with `execute`, unless there is a fresh cached response
+    pub(crate) async fn exec<F, Fut>(&self, mut request: reqwest::Request, operation_id: &'static str, execute: F) -> reqwest::Result<reqwest::Response>
+    where
+        F: FnOnce(reqwest::Request) -> Fut,
+        Fut: Future<Output = reqwest::Result<reqwest::Response>>,
+    {
+        if request.method() != Method::GET {
+            let doc_id_opt = RequestPathIds::from_path(request.url().path()).doc_id;
+            let result = execute(request).await;
+            if let Some(doc_id) = doc_id_opt {
+                self.store.invalidate_doc(&doc_id).await;
+            }
+            return result;
+        }
+        let Some(ttl) = self.ttl(operation_id) else {
+            return execute(request).await;
+        };
+        let authorization_hash_opt = request
+            .headers()
+            .get(AUTHORIZATION)
+            .map(authorization_hash)
+            .or_else(|| self.authorization_hash.clone());
+        let key = CacheKey::new(operation_id, authorization_hash_opt, request.url());
+        let cached_opt = self.store.get(&key).await;
+        if let Some(cached) = &cached_opt {
+            if cached.is_fresh(ttl) {
+                return Ok(cached.to_response());
+            }
+            if let Some(etag) = cached
+                .etag
+                .as_deref()
+                .and_then(|etag| HeaderValue::from_str(etag).ok())
+            {
+                request.headers_mut().insert(IF_NONE_MATCH, etag);
+            }
+        }
+        let response = execute(request).await?;
+        match (response.status(), cached_opt) {
+            (StatusCode::NOT_MODIFIED, Some(mut cached)) => {
+                cached.stored_at = DateTime::<Utc>::from(SystemTime::now());
+                let response = cached.to_response();
+                self.store.put(&key, cached).await;
+                Ok(response)
+            }
+            (StatusCode::OK, _) => {
+                let (cached_opt, response) = CachedResponse::from_response(&key, response).await?;
+                if let Some(cached) = cached_opt {
+                    self.store.put(&key, cached).await;
+                }
+                Ok(response)
+            }
+            _ => Ok(response),
+        }
+    }
+}
+
+impl Debug for ResponseCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("ttls", &self.ttls)
+            .field("authorization_hash", &self.authorization_hash)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Client, Limiter};
+    use governor::{DefaultDirectRateLimiter, Quota};
+    use std::future::{Ready, ready};
+    use std::num::NonZeroU32;
+    use std::sync::Arc;
+    use tokio::time::timeout;
+
+    const TABLE_URL: &str = "https://coda.io/apis/v1/docs/d1/tables/t1";
+
+    fn key(doc_id: &str, table_id: &str) -> CacheKey {
+        let url = reqwest::Url::parse(&format!("https://coda.io/apis/v1/docs/{doc_id}/tables/{table_id}")).unwrap();
+        CacheKey::new("get_table", None, &url)
+    }
+
+    fn response(body: &str) -> CachedResponse {
+        CachedResponse {
+            doc_id: None,
+            stored_at: DateTime::<Utc>::from(SystemTime::now()),
+            etag: None,
+            content_type: None,
+            body: body.to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn must_evict_least_recently_used_and_invalidate_docs() {
+        let store = MemoryCacheStore::new(NonZeroUsize::new(2).unwrap());
+        store.put(&key("d1", "t1"), response("1")).await;
+        store.put(&key("d1", "t2"), response("2")).await;
+        assert!(store.get(&key("d1", "t1")).await.is_some());
+        store.put(&key("d2", "t3"), response("3")).await;
+        assert!(store.get(&key("d1", "t2")).await.is_none());
+        store.invalidate_doc("d1").await;
+        assert!(store.get(&key("d1", "t1")).await.is_none());
+        assert_eq!(store.get(&key("d2", "t3")).await.map(|cached| cached.body), Some("3".to_owned()));
+    }
+
+    fn request(method: Method, url: &str, authorization: &str) -> reqwest::Request {
+        let mut request = reqwest::Request::new(method, reqwest::Url::parse(url).unwrap());
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, HeaderValue::from_str(authorization).unwrap());
+        request
+    }
+
+    fn respond(status: StatusCode, etag: Option<&'static str>, body: &'static str) -> impl FnOnce(reqwest::Request) -> Ready<reqwest::Result<reqwest::Response>> {
+        move |_request| ready(Ok(build_response(status, etag, None, body)))
+    }
+
+    async fn unreachable_execute(_request: reqwest::Request) -> reqwest::Result<reqwest::Response> {
+        unreachable!("the request must be served from the cache")
+    }
+
+    #[tokio::test]
+    async fn must_key_entries_by_authorization() {
+        let cache = ResponseCache::memory(NonZeroUsize::new(10).unwrap()).with_ttl("get_table", Duration::from_secs(60));
+        let response = cache
+            .exec(request(Method::GET, TABLE_URL, "Bearer a"), "get_table", respond(StatusCode::OK, None, "a"))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "a");
+        let response = cache
+            .exec(request(Method::GET, TABLE_URL, "Bearer b"), "get_table", respond(StatusCode::OK, None, "b"))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "b");
+        let response = cache
+            .exec(request(Method::GET, TABLE_URL, "Bearer a"), "get_table", unreachable_execute)
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "a");
+    }
+
+    #[tokio::test]
+    async fn must_revalidate_stale_entry_with_etag() {
+        let cache = ResponseCache::memory(NonZeroUsize::new(10).unwrap()).with_ttl("get_table", Duration::ZERO);
+        let response = cache
+            .exec(request(Method::GET, TABLE_URL, "Bearer a"), "get_table", respond(StatusCode::OK, Some("\"v1\""), "1"))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "1");
+        let execute = |request: reqwest::Request| {
+            assert_eq!(request.headers().get(IF_NONE_MATCH).unwrap(), "\"v1\"");
+            ready(Ok(build_response(StatusCode::NOT_MODIFIED, None, None, "")))
+        };
+        let response = cache
+            .exec(request(Method::GET, TABLE_URL, "Bearer a"), "get_table", execute)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "1");
+    }
+
+    #[tokio::test]
+    async fn must_invalidate_doc_on_write() {
+        let cache = ResponseCache::memory(NonZeroUsize::new(10).unwrap()).with_ttl("get_table", Duration::from_secs(60));
+        let other_table_url = "https://coda.io/apis/v1/docs/d2/tables/t1";
+        cache
+            .exec(request(Method::GET, TABLE_URL, "Bearer a"), "get_table", respond(StatusCode::OK, None, "1"))
+            .await
+            .unwrap();
+        cache
+            .exec(request(Method::GET, other_table_url, "Bearer a"), "get_table", respond(StatusCode::OK, None, "2"))
+            .await
+            .unwrap();
+        let rows_url = "https://coda.io/apis/v1/docs/d1/tables/t1/rows";
+        cache
+            .exec(request(Method::POST, rows_url, "Bearer a"), "upsert_rows", respond(StatusCode::ACCEPTED, None, ""))
+            .await
+            .unwrap();
+        let response = cache
+            .exec(request(Method::GET, TABLE_URL, "Bearer a"), "get_table", respond(StatusCode::OK, None, "3"))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "3");
+        let response = cache
+            .exec(request(Method::GET, other_table_url, "Bearer a"), "get_table", unreachable_execute)
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "2");
+    }
+
+    #[tokio::test]
+    async fn must_return_fresh_entry_without_waiting_for_limiter() {
+        let cache = ResponseCache::memory(NonZeroUsize::new(10).unwrap()).with_ttl("list_categories", Duration::from_secs(60));
+        let url = reqwest::Url::parse("http://127.0.0.1:9/categories").unwrap();
+        cache
+            .store()
+            .put(&CacheKey::new("list_categories", None, &url), response(r#"{"items": []}"#))
+            .await;
+        let mut client = Client::new("http://127.0.0.1:9").with_cache(cache);
+        client.limiter = Arc::new(Limiter {
+            read: DefaultDirectRateLimiter::direct(Quota::per_hour(NonZeroU32::new(1).unwrap())),
+            ..Limiter::default()
+        });
+        client.limiter.read.check().unwrap();
+        let categories = timeout(Duration::from_secs(1), client.list_categories())
+            .await
+            .unwrap()
+            .unwrap()
+            .into_inner();
+        assert!(categories.items.is_empty());
+    }
+}
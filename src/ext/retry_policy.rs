@@ -0,0 +1,90 @@
+use crate::Error;
+use reqwest::StatusCode;
+use reqwest::header::RETRY_AFTER;
+use std::time::Duration;
+
+/// Decides whether [`Client`](crate::Client) should retry a failed request, and how long to wait before the retry.
+///
+/// The `429 Too Many Requests` responses are always safe to retry, because Coda hasn't processed the request. The `5xx` responses and the connection errors are retried only if `retry_server_errors` is `true`, because a write may have reached the server before the failure.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// `0` disables the retries
+    pub max_retries: u32,
+    /// The delay before the first retry (doubled for every next retry)
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub retry_server_errors: bool,
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            retry_server_errors: false,
+        }
+    }
+
+    pub fn none() -> Self {
+        Self::new(0)
+    }
+
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn with_retry_server_errors(mut self, retry_server_errors: bool) -> Self {
+        self.retry_server_errors = retry_server_errors;
+        self
+    }
+
+    /// Returns the delay before the next retry, or `None` if the request should not be retried. The `Retry-After` header takes precedence over the backoff (but is still capped by `max_backoff`).
+    pub fn delay<E>(&self, retries: u32, error: &Error<E>) -> Option<Duration> {
+        if retries >= self.max_retries || !self.is_retryable(error) {
+            return None;
+        }
+        // a large `retries` saturates the backoff at `Duration::MAX`, which is then capped by `max_backoff`
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retries));
+        let delay = retry_after(error).unwrap_or(backoff);
+        Some(delay.min(self.max_backoff))
+    }
+
+    pub fn is_retryable<E>(&self, error: &Error<E>) -> bool {
+        match error {
+            Error::CommunicationError(error) => self.retry_server_errors && (error.is_connect() || error.is_timeout()),
+            _ => match error.status() {
+                Some(StatusCode::TOO_MANY_REQUESTS) => true,
+                Some(status) => self.retry_server_errors && status.is_server_error(),
+                None => false,
+            },
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Returns the `Retry-After` header of the error response (only the delay in seconds is supported)
+pub fn retry_after<E>(error: &Error<E>) -> Option<Duration> {
+    let headers = match error {
+        Error::ErrorResponse(response) => response.headers(),
+        Error::UnexpectedResponse(response) => response.headers(),
+        _ => return None,
+    };
+    let seconds = headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds))
+}
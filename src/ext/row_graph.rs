@@ -0,0 +1,327 @@
+use crate::{DocData, RichRow, RichRowReference, RichSingleValue, RichValue, RichValueEntry, RowId, TableId};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+#[derive(Display, Deserialize, Serialize, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[display("{table_id}/{row_id}")]
+pub struct RowKey {
+    pub table_id: TableId,
+    pub row_id: RowId,
+}
+
+impl RowKey {
+    pub fn new(table_id: impl Into<TableId>, row_id: impl Into<RowId>) -> Self {
+        Self {
+            table_id: table_id.into(),
+            row_id: row_id.into(),
+        }
+    }
+}
+
+impl From<&RichRowReference> for RowKey {
+    fn from(reference: &RichRowReference) -> Self {
+        Self::new(reference.table_id.clone(), reference.row_id.clone())
+    }
+}
+
+/// A lookup from the `source` row to the `target` row through the `column` of the source row
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct RowEdge {
+    pub source: RowKey,
+    /// The key of the value in [`RichRow::values`] (a column ID, or a column name if the rows were fetched with `use_column_names`)
+    pub column: String,
+    pub target: RowKey,
+}
+
+/// A graph of rows connected by lookup columns.
+///
+/// A reference is dangling if its target row is known to be missing: either the target table was loaded completely (see [`RowGraph::from_doc_data`]) and doesn't contain the row, or the row was not found while the graph was built lazily (see [`Client::row_graph`](crate::Client::row_graph)).
+#[derive(Default, Clone, Debug)]
+pub struct RowGraph {
+    rows: BTreeMap<RowKey, RichRow>,
+    edges: Vec<RowEdge>,
+    outgoing: BTreeMap<RowKey, Vec<usize>>,
+    incoming: BTreeMap<RowKey, Vec<usize>>,
+    complete_tables: BTreeSet<TableId>,
+    missing: BTreeSet<RowKey>,
+}
+
+impl RowGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_doc_data(doc_data: &DocData) -> Self {
+        let mut graph = Self::new();
+        for (table_id, rows) in &doc_data.rows {
+            for row in rows {
+                graph.insert_row(table_id.clone(), row.clone());
+            }
+            graph.complete_tables.insert(table_id.clone());
+        }
+        graph
+    }
+
+    /// Inserts the row along with the edges for every row reference in its values. Returns the keys of the referenced rows.
+    ///
+    /// If the row is already in the graph, it is replaced, and its previous edges are removed.
+    pub fn insert_row(&mut self, table_id: TableId, row: RichRow) -> Vec<RowKey> {
+        let source = RowKey::new(table_id, row.id.clone());
+        self.missing.remove(&source);
+        if self.outgoing.contains_key(&source) {
+            self.remove_edges_from(&source);
+        }
+        let mut targets = Vec::new();
+        for (column, value) in &row.values {
+            for reference in row_references(value) {
+                let target = RowKey::from(reference);
+                let edge_index = self.edges.len();
+                self.edges.push(RowEdge {
+                    source: source.clone(),
+                    column: column.clone(),
+                    target: target.clone(),
+                });
+                self.outgoing
+                    .entry(source.clone())
+                    .or_default()
+                    .push(edge_index);
+                self.incoming
+                    .entry(target.clone())
+                    .or_default()
+                    .push(edge_index);
+                targets.push(target);
+            }
+        }
+        self.rows.insert(source, row);
+        targets
+    }
+
+    /// Records that the row doesn't exist (e.g. it was deleted)
+    pub fn insert_missing(&mut self, key: RowKey) {
+        if !self.rows.contains_key(&key) {
+            self.missing.insert(key);
+        }
+    }
+
+    pub fn get(&self, key: &RowKey) -> Option<&RichRow> {
+        self.rows.get(key)
+    }
+
+    pub fn contains(&self, key: &RowKey) -> bool {
+        self.rows.contains_key(key)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = (&RowKey, &RichRow)> {
+        self.rows.iter()
+    }
+
+    pub fn edges(&self) -> &[RowEdge] {
+        &self.edges
+    }
+
+    /// Returns the lookups of the row
+    pub fn references(&self, key: &RowKey) -> impl Iterator<Item = &RowEdge> {
+        self.edges_at(self.outgoing.get(key))
+    }
+
+    /// Returns the lookups from other rows to this row
+    pub fn referrers(&self, key: &RowKey) -> impl Iterator<Item = &RowEdge> {
+        self.edges_at(self.incoming.get(key))
+    }
+
+    /// Resolves the lookups of the row to the target rows (`None` if the target row is not in the graph)
+    pub fn resolve(&self, key: &RowKey) -> impl Iterator<Item = (&RowEdge, Option<&RichRow>)> {
+        self.references(key)
+            .map(|edge| (edge, self.rows.get(&edge.target)))
+    }
+
+    pub fn is_dangling(&self, edge: &RowEdge) -> bool {
+        !self.rows.contains_key(&edge.target) && (self.missing.contains(&edge.target) || self.complete_tables.contains(&edge.target.table_id))
+    }
+
+    /// Returns the lookups that point to rows which are known to be missing
+    pub fn dangling(&self) -> impl Iterator<Item = &RowEdge> {
+        self.edges.iter().filter(|edge| self.is_dangling(edge))
+    }
+
+    /// Returns the lookups that point to rows which were not loaded (neither found nor known to be missing)
+    pub fn unresolved(&self) -> impl Iterator<Item = &RowEdge> {
+        self.edges
+            .iter()
+            .filter(|edge| !self.rows.contains_key(&edge.target) && !self.is_dangling(edge))
+    }
+
+    pub fn export(&self) -> RowGraphExport {
+        let nodes = self
+            .rows
+            .iter()
+            .map(|(key, row)| RowGraphNode {
+                key: key.clone(),
+                name: row.name.clone(),
+                browser_link: row.browser_link.clone(),
+            })
+            .collect();
+        let dangling = self.dangling().cloned().collect();
+        RowGraphExport {
+            nodes,
+            edges: self.edges.clone(),
+            dangling,
+        }
+    }
+
+    /// Renders the graph in the Graphviz DOT format. Dangling references point to dashed nodes.
+    pub fn to_dot(&self) -> String {
+        let mut output = String::from("digraph rows {\n");
+        for (key, row) in &self.rows {
+            // writing to a String never fails
+            let _ = writeln!(output, "    {} [label={}];", dot_quote(&key.to_string()), dot_quote(&row.name));
+        }
+        let dangling_targets = self
+            .dangling()
+            .map(|edge| &edge.target)
+            .collect::<BTreeSet<_>>();
+        for target in dangling_targets {
+            let _ = writeln!(output, "    {} [style=dashed];", dot_quote(&target.to_string()));
+        }
+        for edge in &self.edges {
+            let _ = writeln!(output, "    {} -> {} [label={}];", dot_quote(&edge.source.to_string()), dot_quote(&edge.target.to_string()), dot_quote(&edge.column));
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    /// Removes the edges of the `source` row and rebuilds the edge indexes (the indexes of the remaining edges change)
+    fn remove_edges_from(&mut self, source: &RowKey) {
+        self.edges.retain(|edge| &edge.source != source);
+        self.outgoing.clear();
+        self.incoming.clear();
+        for (edge_index, edge) in self.edges.iter().enumerate() {
+            self.outgoing
+                .entry(edge.source.clone())
+                .or_default()
+                .push(edge_index);
+            self.incoming
+                .entry(edge.target.clone())
+                .or_default()
+                .push(edge_index);
+        }
+    }
+
+    fn edges_at<'a>(&'a self, indexes: Option<&'a Vec<usize>>) -> impl Iterator<Item = &'a RowEdge> {
+        indexes
+            .into_iter()
+            .flatten()
+            .filter_map(|index| self.edges.get(*index))
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RowGraphExport {
+    pub nodes: Vec<RowGraphNode>,
+    pub edges: Vec<RowEdge>,
+    pub dangling: Vec<RowEdge>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RowGraphNode {
+    #[serde(flatten)]
+    pub key: RowKey,
+    pub name: String,
+    pub browser_link: String,
+}
+
+/// Returns every row reference in the value (lookup columns may contain a single reference or a list of references)
+pub fn row_references(value: &RichValue) -> Vec<&RichRowReference> {
+    match value {
+        RichValue::Single(single) => single_row_reference(single).into_iter().collect(),
+        RichValue::Collection(entries) => entries
+            .iter()
+            .flat_map(|entry| match entry {
+                RichValueEntry::Single(single) => single_row_reference(single).into_iter().collect::<Vec<_>>(),
+                RichValueEntry::Many(singles) => singles.iter().filter_map(single_row_reference).collect(),
+            })
+            .collect(),
+    }
+}
+
+fn single_row_reference(value: &RichSingleValue) -> Option<&RichRowReference> {
+    match value {
+        RichSingleValue::Row(reference) => Some(reference),
+        _ => None,
+    }
+}
+
+fn dot_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn row(id: &str, references: &[&str]) -> RichRow {
+        let references = references
+            .iter()
+            .map(|row_id| {
+                json!({
+                    "@context": "http://schema.org/",
+                    "@type": "StructuredValue",
+                    "additionalType": "row",
+                    "name": row_id,
+                    "url": "https://coda.io/d/_dDoc#_tugrid-b/_ru",
+                    "tableId": "grid-b",
+                    "rowId": row_id,
+                    "tableUrl": "https://coda.io/d/_dDoc#_tugrid-b"
+                })
+            })
+            .collect::<Vec<_>>();
+        serde_json::from_value(json!({
+            "browserLink": "https://coda.io/d/_dDoc#_tugrid-a/_ru",
+            "createdAt": "2026-10-18T00:00:00Z",
+            "href": "https://coda.io/apis/v1/docs/doc/tables/grid-a/rows/i-a",
+            "id": id,
+            "index": 0,
+            "name": id,
+            "type": "row",
+            "updatedAt": "2026-10-18T00:00:00Z",
+            "values": {"c-lookup": references}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn must_track_references_and_dangling_rows() {
+        let mut graph = RowGraph::new();
+        graph.insert_row("grid-a".into(), row("i-a", &["i-b", "i-c"]));
+        graph.insert_row("grid-b".into(), row("i-b", &[]));
+        graph.insert_missing(RowKey::new("grid-b", "i-c"));
+        let target = RowKey::new("grid-b", "i-b");
+        assert_eq!(graph.referrers(&target).count(), 1);
+        assert_eq!(
+            graph
+                .resolve(&RowKey::new("grid-a", "i-a"))
+                .filter(|(_, row)| row.is_some())
+                .count(),
+            1
+        );
+        let dangling = graph.dangling().collect::<Vec<_>>();
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].target, RowKey::new("grid-b", "i-c"));
+        assert!(graph.to_dot().contains("\"grid-b/i-c\" [style=dashed];"));
+    }
+
+    #[test]
+    fn must_replace_edges_of_reinserted_row() {
+        let mut graph = RowGraph::new();
+        graph.insert_row("grid-a".into(), row("i-a", &["i-b", "i-c"]));
+        graph.insert_row("grid-a".into(), row("i-z", &["i-b"]));
+        graph.insert_row("grid-a".into(), row("i-a", &["i-b"]));
+        assert_eq!(graph.edges().len(), 2);
+        assert_eq!(graph.references(&RowKey::new("grid-a", "i-a")).count(), 1);
+        assert_eq!(graph.referrers(&RowKey::new("grid-b", "i-b")).count(), 2);
+        assert_eq!(graph.referrers(&RowKey::new("grid-b", "i-c")).count(), 0);
+    }
+}
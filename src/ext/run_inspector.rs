@@ -0,0 +1,173 @@
+use crate::types::{GroupedPackLog, IngestionBatchExecution, IngestionParentItem, IngestionStatus, LogLevel, PackLog, PackLogDetails};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+/// An ingestion run reconstructed from a root ingestion: batch executions → parent items → logs
+#[derive(Serialize, Clone, Debug)]
+pub struct IngestionRun {
+    pub root_ingestion_id: String,
+    pub executions: Vec<IngestionExecutionNode>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct IngestionExecutionNode {
+    pub execution: IngestionBatchExecution,
+    pub duration_secs: Option<f64>,
+    pub parent_items: Vec<IngestionParentItemNode>,
+    pub logs: Vec<GroupedPackLog>,
+    /// The error messages of the execution and its parent items
+    pub errors: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct IngestionParentItemNode {
+    pub item: IngestionParentItem,
+    pub duration_secs: Option<f64>,
+}
+
+/// The logs of an agent session in chronological order
+#[derive(Serialize, Clone, Debug)]
+pub struct AgentSessionTimeline {
+    pub agent_session_id: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub entries: Vec<AgentTimelineEntry>,
+    /// The messages of the error-level logs
+    pub errors: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AgentTimelineEntry {
+    pub log: PackLog,
+    /// Milliseconds since the first log of the session
+    pub offset_ms: i64,
+    /// The duration reported by the agent runtime (for agent runtime logs only)
+    pub duration_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<PackLogDetails>,
+}
+
+impl IngestionRun {
+    pub fn has_errors(&self) -> bool {
+        self.executions
+            .iter()
+            .any(|execution| !execution.errors.is_empty())
+    }
+
+    pub fn failed_parent_items(&self) -> impl Iterator<Item = &IngestionParentItem> {
+        self.executions
+            .iter()
+            .flat_map(|execution| execution.parent_items.iter())
+            .map(|node| &node.item)
+            .filter(|item| item.ingestion_status == Some(IngestionStatus::Failed))
+    }
+}
+
+impl IngestionExecutionNode {
+    pub fn new(execution: IngestionBatchExecution, parent_items: Vec<IngestionParentItem>, logs: Vec<GroupedPackLog>) -> Self {
+        let mut errors = Vec::new();
+        errors.extend(error_message_from_value(&execution.error_message));
+        errors.extend(
+            parent_items
+                .iter()
+                .filter_map(|item| item.error_message.clone())
+                .filter(|message| !message.is_empty()),
+        );
+        let parent_items = parent_items
+            .into_iter()
+            .map(|item| IngestionParentItemNode {
+                duration_secs: duration_secs(item.start_timestamp, item.completion_timestamp),
+                item,
+            })
+            .collect();
+        Self {
+            duration_secs: duration_secs(execution.start_timestamp, execution.completion_timestamp),
+            execution,
+            parent_items,
+            logs,
+            errors,
+        }
+    }
+}
+
+impl AgentSessionTimeline {
+    /// Builds the timeline from the logs of the session (in any order) and the details fetched for some of them (matched by log ID)
+    pub fn new(agent_session_id: String, mut logs: Vec<PackLog>, mut details: Vec<(String, PackLogDetails)>) -> Self {
+        logs.sort_by_key(|log| log.context().created_at);
+        let started_at = logs.first().map(|log| log.context().created_at);
+        let ended_at = logs.last().map(|log| log.context().created_at);
+        let errors = logs
+            .iter()
+            .filter_map(|log| match log {
+                PackLog::CustomLog(log) if log.level == LogLevel::Error => Some(log.message.clone()),
+                _ => None,
+            })
+            .collect();
+        let entries = logs
+            .into_iter()
+            .map(|log| {
+                let context = log.context();
+                let offset_ms = started_at
+                    .map(|started_at| {
+                        context
+                            .created_at
+                            .signed_duration_since(started_at)
+                            .num_milliseconds()
+                    })
+                    .unwrap_or_default();
+                let details = details
+                    .iter()
+                    .position(|(log_id, _)| log_id == &context.log_id)
+                    .map(|index| details.swap_remove(index).1);
+                let duration_ms = match &log {
+                    PackLog::AgentRuntimeLog(log) => log.duration_ms,
+                    _ => None,
+                };
+                AgentTimelineEntry {
+                    log,
+                    offset_ms,
+                    duration_ms,
+                    details,
+                }
+            })
+            .collect();
+        Self {
+            agent_session_id,
+            started_at,
+            ended_at,
+            entries,
+            errors,
+        }
+    }
+}
+
+/// Returns the duration between the timestamps (in seconds since epoch), or `None` if the run is not completed
+fn duration_secs(start: f64, completion: f64) -> Option<f64> {
+    (start > 0.0 && completion >= start).then_some(completion - start)
+}
+
+/// The error message of a batch execution may be `null`, a string, or an object with a `message`
+fn error_message_from_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(message) if message.is_empty() => None,
+        Value::String(message) => Some(message.clone()),
+        Value::Object(object) => match object.get("message") {
+            Some(Value::String(message)) => Some(message.clone()),
+            _ => Some(value.to_string()),
+        },
+        value => Some(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn must_compute_duration_only_for_completed_runs() {
+        assert_eq!(duration_secs(100.0, 160.5), Some(60.5));
+        assert_eq!(duration_secs(100.0, 0.0), None);
+    }
+}
@@ -0,0 +1,285 @@
+use crate::types::{AccessType, AclMetadata, Doc, Permission, Principal, WorkspaceUser, WorkspaceUserRole};
+use crate::{DocId, push_csv_line};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Clone, Debug, Default)]
+pub struct SharingAuditOptions {
+    /// The email domains that are considered internal (the workspace members are always considered internal, but their domains are not, because a member may use a public domain like `gmail.com`)
+    pub internal_domains: Vec<String>,
+}
+
+/// A sharing audit of every doc in a workspace (see [`Client::audit_workspace_sharing`](crate::Client::audit_workspace_sharing))
+#[derive(Serialize, Clone, Debug)]
+pub struct SharingAuditReport {
+    pub workspace_id: String,
+    pub internal_domains: BTreeSet<String>,
+    pub docs: Vec<DocSharingAudit>,
+    /// The access of every user that is a workspace member, owns a doc, or has an email permission on a doc. The access granted through groups, domains and workspaces is not expanded to users.
+    pub users: Vec<UserAccessSummary>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DocSharingAudit {
+    pub doc_id: DocId,
+    pub name: String,
+    pub browser_link: String,
+    pub owner: String,
+    pub metadata: AclMetadata,
+    pub permissions: Vec<Permission>,
+    pub findings: Vec<SharingFinding>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SharingFinding {
+    /// The doc is published
+    Published,
+    /// Anyone with the link can access the doc
+    AnyoneWithLink { access: AccessType },
+    /// Everyone in an external domain can access the doc
+    ExternalDomain { domain: String, access: AccessType },
+    /// A user with an external email can access the doc
+    ExternalUser { email: String, access: AccessType },
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct UserAccessSummary {
+    pub email: String,
+    pub name: Option<String>,
+    /// `None` if the user is not a workspace member
+    pub role: Option<WorkspaceUserRole>,
+    pub owned_docs: Vec<DocId>,
+    pub shared_docs: Vec<UserDocAccess>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct UserDocAccess {
+    pub doc_id: DocId,
+    pub access: AccessType,
+}
+
+impl SharingAuditReport {
+    pub fn new(workspace_id: String, docs: Vec<(Doc, Vec<Permission>, AclMetadata)>, members: Vec<WorkspaceUser>, options: &SharingAuditOptions) -> Self {
+        let internal_domains = options
+            .internal_domains
+            .iter()
+            .map(|domain| domain.to_lowercase())
+            .collect::<BTreeSet<_>>();
+        let member_emails = members
+            .iter()
+            .map(|member| member.email.to_lowercase())
+            .collect::<BTreeSet<_>>();
+
+        let mut users = members
+            .into_iter()
+            .map(|member| {
+                let summary = UserAccessSummary {
+                    email: member.email.clone(),
+                    name: Some(member.name),
+                    role: Some(member.role),
+                    owned_docs: Vec::new(),
+                    shared_docs: Vec::new(),
+                };
+                (member.email.to_lowercase(), summary)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let docs = docs
+            .into_iter()
+            .map(|(doc, permissions, metadata)| {
+                let mut findings = Vec::new();
+                if doc.published.is_some() {
+                    findings.push(SharingFinding::Published);
+                }
+                users
+                    .entry(doc.owner.to_lowercase())
+                    .or_insert_with(|| UserAccessSummary::external(doc.owner.clone(), Some(doc.owner_name.clone())))
+                    .owned_docs
+                    .push(doc.id.clone());
+                for permission in &permissions {
+                    let access = permission.access;
+                    match &permission.principal {
+                        Principal::AnyonePrincipal(_) => findings.push(SharingFinding::AnyoneWithLink {
+                            access,
+                        }),
+                        Principal::DomainPrincipal(principal) if !internal_domains.contains(&principal.domain.to_lowercase()) => findings.push(SharingFinding::ExternalDomain {
+                            domain: principal.domain.clone(),
+                            access,
+                        }),
+                        Principal::EmailPrincipal(principal) => {
+                            let is_internal = member_emails.contains(&principal.email.to_lowercase()) || email_domain(&principal.email).is_some_and(|domain| internal_domains.contains(&domain));
+                            if !is_internal {
+                                findings.push(SharingFinding::ExternalUser {
+                                    email: principal.email.clone(),
+                                    access,
+                                });
+                            }
+                            users
+                                .entry(principal.email.to_lowercase())
+                                .or_insert_with(|| UserAccessSummary::external(principal.email.clone(), None))
+                                .shared_docs
+                                .push(UserDocAccess {
+                                    doc_id: doc.id.clone(),
+                                    access,
+                                });
+                        }
+                        _ => {}
+                    }
+                }
+                DocSharingAudit {
+                    doc_id: doc.id,
+                    name: doc.name,
+                    browser_link: doc.browser_link,
+                    owner: doc.owner,
+                    metadata,
+                    permissions,
+                    findings,
+                }
+            })
+            .collect();
+
+        Self {
+            workspace_id,
+            internal_domains,
+            docs,
+            users: users.into_values().collect(),
+        }
+    }
+
+    /// Returns the docs that have at least one finding
+    pub fn flagged_docs(&self) -> impl Iterator<Item = &DocSharingAudit> {
+        self.docs.iter().filter(|doc| !doc.findings.is_empty())
+    }
+
+    /// Returns a CSV with one line per finding: `doc_id,doc_name,owner,finding,principal,access`
+    pub fn findings_csv(&self) -> String {
+        let mut csv = String::from("doc_id,doc_name,owner,finding,principal,access\n");
+        for doc in &self.docs {
+            for finding in &doc.findings {
+                let (finding, principal, access) = match finding {
+                    SharingFinding::Published => ("published", "", String::new()),
+                    SharingFinding::AnyoneWithLink {
+                        access,
+                    } => ("anyoneWithLink", "", access.to_string()),
+                    SharingFinding::ExternalDomain {
+                        domain,
+                        access,
+                    } => ("externalDomain", domain.as_str(), access.to_string()),
+                    SharingFinding::ExternalUser {
+                        email,
+                        access,
+                    } => ("externalUser", email.as_str(), access.to_string()),
+                };
+                push_csv_line(
+                    &mut csv,
+                    &[
+                        &doc.doc_id,
+                        &doc.name,
+                        &doc.owner,
+                        finding,
+                        principal,
+                        &access,
+                    ],
+                );
+            }
+        }
+        csv
+    }
+
+    /// Returns a CSV with one line per user: `email,name,role,owned_docs,write_docs,comment_docs,readonly_docs`
+    pub fn users_csv(&self) -> String {
+        let mut csv = String::from("email,name,role,owned_docs,write_docs,comment_docs,readonly_docs\n");
+        for user in &self.users {
+            let count = |access: AccessType| {
+                user.shared_docs
+                    .iter()
+                    .filter(|doc| doc.access == access)
+                    .count()
+                    .to_string()
+            };
+            let role = user.role.map(|role| role.to_string()).unwrap_or_default();
+            push_csv_line(
+                &mut csv,
+                &[
+                    &user.email,
+                    user.name.as_deref().unwrap_or_default(),
+                    &role,
+                    &user.owned_docs.len().to_string(),
+                    &count(AccessType::Write),
+                    &count(AccessType::Comment),
+                    &count(AccessType::Readonly),
+                ],
+            );
+        }
+        csv
+    }
+}
+
+impl UserAccessSummary {
+    fn external(email: String, name: Option<String>) -> Self {
+        Self {
+            email,
+            name,
+            role: None,
+            owned_docs: Vec::new(),
+            shared_docs: Vec::new(),
+        }
+    }
+}
+
+fn email_domain(email: &str) -> Option<String> {
+    email
+        .rsplit_once('@')
+        .map(|(_, domain)| domain.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn must_not_treat_member_domains_as_internal() {
+        let doc: Doc = serde_json::from_value(json!({
+            "browserLink": "https://coda.io/d/_dd1",
+            "createdAt": "2024-01-01T00:00:00Z",
+            "folder": {"browserLink": "https://coda.io/folders/fl-1", "id": "fl-1", "type": "folder"},
+            "folderId": "fl-1",
+            "href": "https://coda.io/apis/v1/docs/d1",
+            "id": "d1",
+            "name": "Doc",
+            "owner": "owner@example.com",
+            "ownerName": "Owner",
+            "type": "doc",
+            "updatedAt": "2024-01-02T00:00:00Z",
+            "workspace": {"browserLink": "https://coda.io/docs?workspaceId=ws-1", "id": "ws-1", "type": "workspace"},
+            "workspaceId": "ws-1"
+        }))
+        .unwrap();
+        let permissions: Vec<Permission> = serde_json::from_value(json!([
+            {"id": "p1", "access": "write", "principal": {"type": "email", "email": "Member@gmail.com"}},
+            {"id": "p2", "access": "readonly", "principal": {"type": "email", "email": "stranger@gmail.com"}},
+            {"id": "p3", "access": "comment", "principal": {"type": "domain", "domain": "gmail.com"}},
+            {"id": "p4", "access": "readonly", "principal": {"type": "email", "email": "colleague@example.com"}}
+        ]))
+        .unwrap();
+        let metadata: AclMetadata = serde_json::from_value(json!({"canShare": true, "canShareWithWorkspace": true, "canShareWithOrg": true, "canCopy": true})).unwrap();
+        let members: Vec<WorkspaceUser> = serde_json::from_value(json!([
+            {"email": "member@gmail.com", "name": "Member", "role": "Editor", "pictureUrl": "https://example.com/p.png", "registeredAt": "2024-01-01T00:00:00Z"}
+        ]))
+        .unwrap();
+        let options = SharingAuditOptions {
+            internal_domains: vec!["Example.com".to_owned()],
+        };
+        let report = SharingAuditReport::new("ws-1".to_owned(), vec![(doc, permissions, metadata)], members, &options);
+        let findings = serde_json::to_value(&report.docs[0].findings).unwrap();
+        assert_eq!(
+            findings,
+            json!([
+                {"type": "externalUser", "email": "stranger@gmail.com", "access": "readonly"},
+                {"type": "externalDomain", "domain": "gmail.com", "access": "comment"}
+            ])
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use crate::DocId;
+use crate::types::{AccessType, AccessTypeNotNone, AclSettings, AddPermissionRequest, AddedPrincipal, Permission, Principal, UpdateAclSettingsRequest};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Formatter;
+
+/// The desired sharing of a doc. A `None` field is left unmanaged.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct DocSharingConfig {
+    /// The principals (emails, groups, domains, workspaces, anyone) with their access. The permissions of the other principals are deleted, except for the internal access permissions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Vec<AddPermissionRequest>>,
+    /// The ACL settings (a `None` setting is left unchanged)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<UpdateAclSettingsRequest>,
+}
+
+/// The current sharing of a doc
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct DocSharingState {
+    pub permissions: Vec<Permission>,
+    pub settings: AclSettings,
+}
+
+#[derive(Display, Serialize, Clone, Debug)]
+pub enum SharingChange {
+    #[display("add '{}' with access '{}'", added_principal_key(&_0.principal), _0.access)]
+    AddPermission(AddPermissionRequest),
+    /// Applied with `add_permission`, which replaces the access of an existing principal (the existing permission is never deleted, so the principal doesn't lose access if the request fails)
+    #[display("change access of '{}' from '{from}' to '{}'", added_principal_key(&request.principal), request.access)]
+    UpdatePermission { from: AccessType, request: AddPermissionRequest },
+    #[display("delete '{}' with access '{access}'", principal_key(principal))]
+    DeletePermission { permission_id: String, principal: Principal, access: AccessType },
+    /// Contains only the settings that differ from the current ones
+    #[display("update settings ({})", acl_settings_description(_0))]
+    UpdateSettings(UpdateAclSettingsRequest),
+}
+
+/// The changes that make the current sharing of a doc match a [`DocSharingConfig`]
+#[derive(Serialize, Clone, Debug)]
+pub struct DocSharingPlan {
+    pub doc_id: DocId,
+    pub changes: Vec<SharingChange>,
+}
+
+impl DocSharingPlan {
+    pub fn new(doc_id: DocId, desired: &DocSharingConfig, current: &DocSharingState) -> Self {
+        use SharingChange::*;
+        let mut changes = Vec::new();
+
+        if let Some(permissions) = &desired.permissions {
+            for permission in permissions {
+                let key = added_principal_key(&permission.principal);
+                let current_opt = current
+                    .permissions
+                    .iter()
+                    .find(|current| principal_key(&current.principal) == key);
+                match current_opt {
+                    None => changes.push(AddPermission(permission.clone())),
+                    Some(current) if !access_eq(current.access, permission.access) => changes.push(UpdatePermission {
+                        from: current.access,
+                        request: permission.clone(),
+                    }),
+                    Some(_) => {}
+                }
+            }
+            for permission in &current.permissions {
+                if matches!(permission.principal, Principal::InternalAccessPrincipal(_)) {
+                    continue;
+                }
+                let key = principal_key(&permission.principal);
+                if !permissions
+                    .iter()
+                    .any(|desired| added_principal_key(&desired.principal) == key)
+                {
+                    changes.push(DeletePermission {
+                        permission_id: permission.id.clone(),
+                        principal: permission.principal.clone(),
+                        access: permission.access,
+                    });
+                }
+            }
+        }
+
+        if let Some(settings) = &desired.settings {
+            let current = &current.settings;
+            let diff = |desired: Option<bool>, current: bool| desired.filter(|desired| *desired != current);
+            let update = UpdateAclSettingsRequest {
+                allow_copying: diff(settings.allow_copying, current.allow_copying),
+                allow_editors_to_change_permissions: diff(settings.allow_editors_to_change_permissions, current.allow_editors_to_change_permissions),
+                allow_viewers_to_request_editing: diff(settings.allow_viewers_to_request_editing, current.allow_viewers_to_request_editing),
+            };
+            if update.allow_copying.is_some() || update.allow_editors_to_change_permissions.is_some() || update.allow_viewers_to_request_editing.is_some() {
+                changes.push(UpdateSettings(update));
+            }
+        }
+
+        Self {
+            doc_id,
+            changes,
+        }
+    }
+}
+
+/// The changes for many docs (see [`Client::plan_sharing`](crate::Client::plan_sharing) and [`Client::apply_sharing_plan`](crate::Client::apply_sharing_plan))
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct SharingPlan {
+    pub docs: Vec<DocSharingPlan>,
+}
+
+impl SharingPlan {
+    pub fn is_empty(&self) -> bool {
+        self.docs.iter().all(|doc| doc.changes.is_empty())
+    }
+}
+
+impl fmt::Display for SharingPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for doc in &self.docs {
+            if doc.changes.is_empty() {
+                writeln!(f, "{}: no changes", doc.doc_id)?;
+            } else {
+                writeln!(f, "{}:", doc.doc_id)?;
+                for change in &doc.changes {
+                    writeln!(f, "  {change}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The audit report of [`Client::apply_sharing_plan`](crate::Client::apply_sharing_plan)
+#[derive(Serialize, Clone, Debug)]
+pub struct SharingReport {
+    pub dry_run: bool,
+    pub entries: Vec<SharingReportEntry>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SharingReportEntry {
+    pub doc_id: DocId,
+    pub description: String,
+    pub change: SharingChange,
+    pub status: SharingChangeStatus,
+}
+
+#[derive(Serialize, Deserialize, Display, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum SharingChangeStatus {
+    /// The change was not applied because of the dry run
+    #[display("planned")]
+    Planned,
+    #[display("applied")]
+    Applied,
+    #[display("failed")]
+    Failed,
+    /// The change was not applied because a previous change of the same doc failed
+    #[display("skipped")]
+    Skipped,
+}
+
+impl SharingReport {
+    pub fn has_failures(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.status == SharingChangeStatus::Failed)
+    }
+}
+
+/// Returns a key that identifies the principal (e.g. "email:john@example.com", "domain:example.com", "anyone")
+pub fn principal_key(principal: &Principal) -> String {
+    use Principal::*;
+    match principal {
+        EmailPrincipal(principal) => format!("email:{}", principal.email.to_lowercase()),
+        GroupPrincipal(principal) => format!("group:{}", principal.group_id),
+        DomainPrincipal(principal) => format!("domain:{}", principal.domain.to_lowercase()),
+        WorkspacePrincipal(principal) => format!("workspace:{}", principal.workspace_id),
+        AnyonePrincipal(_) => "anyone".to_string(),
+        InternalAccessPrincipal(principal) => format!("internalAccess:{}", principal.internal_access_type),
+    }
+}
+
+/// Returns the same key as [`principal_key`] for the principal of an [`AddPermissionRequest`]
+pub fn added_principal_key(principal: &AddedPrincipal) -> String {
+    use AddedPrincipal::*;
+    match principal {
+        EmailPrincipal(principal) => format!("email:{}", principal.email.to_lowercase()),
+        GroupPrincipal(principal) => format!("group:{}", principal.group_id),
+        DomainPrincipal(principal) => format!("domain:{}", principal.domain.to_lowercase()),
+        WorkspacePrincipal(principal) => format!("workspace:{}", principal.workspace_id),
+        AnyonePrincipal(_) => "anyone".to_string(),
+    }
+}
+
+fn access_eq(current: AccessType, desired: AccessTypeNotNone) -> bool {
+    matches!((current, desired), (AccessType::Readonly, AccessTypeNotNone::Readonly) | (AccessType::Write, AccessTypeNotNone::Write) | (AccessType::Comment, AccessTypeNotNone::Comment))
+}
+
+fn acl_settings_description(settings: &UpdateAclSettingsRequest) -> String {
+    [
+        ("allowCopying", settings.allow_copying),
+        ("allowEditorsToChangePermissions", settings.allow_editors_to_change_permissions),
+        ("allowViewersToRequestEditing", settings.allow_viewers_to_request_editing),
+    ]
+    .into_iter()
+    .filter_map(|(name, value_opt)| value_opt.map(|value| format!("{name}: {value}")))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn must_plan_sharing_changes() {
+        let current: DocSharingState = serde_json::from_value(json!({
+            "permissions": [
+                {"id": "p1", "access": "readonly", "principal": {"type": "email", "email": "alice@example.com"}},
+                {"id": "p2", "access": "write", "principal": {"type": "anyone"}},
+                {"id": "p3", "access": "readonly", "principal": {"type": "internalAccess", "internalAccessType": "support"}}
+            ],
+            "settings": {"allowCopying": true, "allowEditorsToChangePermissions": false, "allowViewersToRequestEditing": true}
+        }))
+        .unwrap();
+        let desired: DocSharingConfig = serde_json::from_value(json!({
+            "permissions": [
+                {"access": "write", "principal": {"type": "email", "email": "alice@example.com"}},
+                {"access": "comment", "principal": {"type": "domain", "domain": "example.com"}}
+            ],
+            "settings": {"allowCopying": false, "allowViewersToRequestEditing": true}
+        }))
+        .unwrap();
+        let plan = DocSharingPlan::new("doc".to_string(), &desired, &current);
+        let descriptions = plan
+            .changes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            descriptions,
+            vec![
+                "change access of 'email:alice@example.com' from 'readonly' to 'write'",
+                "add 'domain:example.com' with access 'comment'",
+                "delete 'anyone' with access 'write'",
+                "update settings (allowCopying: false)"
+            ]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use errgonomic::handle;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, InvalidHeaderName, InvalidHeaderValue};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Uploads the body to a pre-signed URL returned by the Coda API (e.g. [`PackVersionUploadInfo::upload_url`](crate::types::PackVersionUploadInfo::upload_url)).
+///
+/// The `http` client must not send the Coda API key: the URL is already signed, and the storage backend rejects requests with an extra `Authorization` header.
+pub async fn upload_to_signed_url(http: &reqwest::Client, upload_url: &str, headers: &HashMap<String, String>, body: impl Into<reqwest::Body>) -> Result<(), UploadToSignedUrlError> {
+    use UploadToSignedUrlError::*;
+    let mut header_map = HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        let header_name = handle!(HeaderName::try_from(name.as_str()), HeaderNameInvalid, name: name.clone());
+        let header_value = handle!(HeaderValue::try_from(value.as_str()), HeaderValueInvalid, name: name.clone());
+        header_map.insert(header_name, header_value);
+    }
+    let response = handle!(
+        http.put(upload_url)
+            .headers(header_map)
+            .body(body)
+            .send()
+            .await,
+        RequestFailed
+    );
+    handle!(response.error_for_status(), ResponseStatusUnsuccessful);
+    Ok(())
+}
+
+/// Returns the lowercase hex SHA-256 digest of the content (the format of `bundleHash`, `payloadHash` and `codeHash` in the Pack upload requests)
+pub fn sha256_hex(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[derive(Error, Debug)]
+pub enum UploadToSignedUrlError {
+    #[error("upload header name '{name}' is invalid")]
+    HeaderNameInvalid { source: InvalidHeaderName, name: String },
+    #[error("upload header '{name}' has an invalid value")]
+    HeaderValueInvalid { source: InvalidHeaderValue, name: String },
+    #[error("upload request failed")]
+    RequestFailed { source: reqwest::Error },
+    #[error("upload request returned an unsuccessful status")]
+    ResponseStatusUnsuccessful { source: reqwest::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn must_hash_as_lowercase_hex() {
+        assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{DocId, LimiterBucket, TableId};
+use progenitor_client::OperationInfo;
+use std::time::Duration;
+#[cfg(feature = "tracing")]
+use tracing::field::Empty;
+#[cfg(feature = "tracing")]
+use tracing::{Instrument, Span};
+
+/// The span that [`Client`](crate::Client) creates for every API call (with the `tracing` feature). It has the following fields:
+///
+/// - `bucket`: the [`LimiterBucket`] that the call waited on
+/// - `limiter_wait_seconds`: how long the call waited for the limiter
+/// - `operation_id`: the [`OperationInfo::operation_id`]
+/// - `doc_id`, `table_id`: the IDs from the request path (if any)
+/// - `status`: the HTTP status code of the response
+/// - `response_size`: the `Content-Length` of the response (if known)
+/// - `retries`: how many times the request was retried (see [`RetryPolicy`](crate::RetryPolicy))
+pub const REQUEST_SPAN_NAME: &str = "coda_api.request";
+
+/// Counter of the responses and transport errors, labeled with `operation_id` and `status` (`error` for transport errors)
+pub const REQUESTS_TOTAL_METRIC: &str = "coda_api_requests_total";
+/// Histogram of the request durations (without the limiter wait), labeled with `operation_id`
+pub const REQUEST_DURATION_SECONDS_METRIC: &str = "coda_api_request_duration_seconds";
+/// Histogram of the `Content-Length` of the responses, labeled with `operation_id`
+pub const RESPONSE_SIZE_BYTES_METRIC: &str = "coda_api_response_size_bytes";
+/// Counter of the retries, labeled with `bucket`
+pub const RETRIES_TOTAL_METRIC: &str = "coda_api_retries_total";
+/// Histogram of the limiter waits, labeled with `bucket`
+pub const LIMITER_WAIT_SECONDS_METRIC: &str = "coda_api_limiter_wait_seconds";
+
+/// The doc and table IDs from the path of a request URL
+#[derive(Default, Clone, Debug, Eq, PartialEq)]
+pub struct RequestPathIds {
+    pub doc_id: Option<DocId>,
+    pub table_id: Option<TableId>,
+}
+
+impl RequestPathIds {
+    pub fn from_path(path: &str) -> Self {
+        let mut ids = Self::default();
+        let mut segments = path.split('/');
+        while let Some(segment) = segments.next() {
+            match segment {
+                "docs" if ids.doc_id.is_none() => ids.doc_id = segments.next().map(str::to_owned),
+                "tables" if ids.table_id.is_none() => ids.table_id = segments.next().map(str::to_owned),
+                _ => {}
+            }
+        }
+        ids.doc_id.take_if(|doc_id| doc_id.is_empty());
+        ids.table_id.take_if(|table_id| table_id.is_empty());
+        ids
+    }
+}
+
+/// Runs the `future` in the [`REQUEST_SPAN_NAME`] span (a no-op without the `tracing` feature)
+pub(crate) async fn in_request_span<F: Future>(bucket: LimiterBucket, future: F) -> F::Output {
+    #[cfg(feature = "tracing")]
+    let future = future.instrument(tracing::info_span!(REQUEST_SPAN_NAME, bucket = bucket.as_str(), limiter_wait_seconds = Empty, operation_id = Empty, doc_id = Empty, table_id = Empty, status = Empty, response_size = Empty, retries = Empty));
+    #[cfg(not(feature = "tracing"))]
+    let _ = bucket;
+    future.await
+}
+
+#[cfg_attr(not(all(feature = "tracing", feature = "metrics")), allow(unused_variables))]
+pub(crate) fn record_limiter_wait(bucket: LimiterBucket, wait: Duration) {
+    #[cfg(feature = "tracing")]
+    Span::current().record("limiter_wait_seconds", wait.as_secs_f64());
+    #[cfg(feature = "metrics")]
+    metrics::histogram!(LIMITER_WAIT_SECONDS_METRIC, "bucket" => bucket.as_str()).record(wait);
+}
+
+#[cfg_attr(not(all(feature = "tracing", feature = "metrics")), allow(unused_variables))]
+pub(crate) fn record_retry(bucket: LimiterBucket, retries: u32, delay: Duration) {
+    #[cfg(feature = "tracing")]
+    {
+        Span::current().record("retries", retries);
+        tracing::debug!(retries, delay_seconds = delay.as_secs_f64(), "retrying request");
+    }
+    #[cfg(feature = "metrics")]
+    metrics::counter!(RETRIES_TOTAL_METRIC, "bucket" => bucket.as_str()).increment(1);
+}
+
+#[cfg_attr(not(all(feature = "tracing", feature = "metrics")), allow(unused_variables))]
+pub(crate) fn record_response(info: &OperationInfo, path: &str, result: &reqwest::Result<reqwest::Response>, elapsed: Duration) {
+    let ids = RequestPathIds::from_path(path);
+    let status = result.as_ref().ok().map(|response| response.status());
+    let response_size = result
+        .as_ref()
+        .ok()
+        .and_then(|response| response.content_length());
+    #[cfg(feature = "tracing")]
+    {
+        let span = Span::current();
+        span.record("operation_id", info.operation_id);
+        span.record("doc_id", ids.doc_id.as_deref());
+        span.record("table_id", ids.table_id.as_deref());
+        span.record("status", status.map(|status| status.as_u16()));
+        span.record("response_size", response_size);
+        if let Err(error) = result {
+            tracing::warn!(%error, "request failed");
+        }
+    }
+    #[cfg(feature = "metrics")]
+    {
+        let status_label = status.map_or_else(|| "error".to_owned(), |status| status.as_str().to_owned());
+        metrics::counter!(REQUESTS_TOTAL_METRIC, "operation_id" => info.operation_id, "status" => status_label).increment(1);
+        metrics::histogram!(REQUEST_DURATION_SECONDS_METRIC, "operation_id" => info.operation_id).record(elapsed);
+        if let Some(response_size) = response_size {
+            #[allow(clippy::cast_precision_loss)]
+            metrics::histogram!(RESPONSE_SIZE_BYTES_METRIC, "operation_id" => info.operation_id).record(response_size as f64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn must_extract_ids_from_path() {
+        let ids = RequestPathIds::from_path("/apis/v1/docs/AbCDeFGH/tables/grid-pqRst-U/rows/i-tuVwxYz");
+        assert_eq!(ids.doc_id.as_deref(), Some("AbCDeFGH"));
+        assert_eq!(ids.table_id.as_deref(), Some("grid-pqRst-U"));
+        let ids = RequestPathIds::from_path("/apis/v1/analytics/docs");
+        assert_eq!(ids, RequestPathIds::default());
+    }
+}
@@ -0,0 +1,55 @@
+use crate::types::Column;
+use crate::{DecodeValueError, RichRow, RowId, TypedValue, decode_value};
+use chrono::{DateTime, Utc};
+use errgonomic::{ErrVec, ItemError, handle, handle_iter};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// A [`RichRow`] with every value decoded according to the format of its column
+#[derive(Clone, Debug)]
+pub struct TypedRow {
+    pub id: RowId,
+    pub name: String,
+    pub index: i64,
+    pub browser_link: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Keys are the same as in [`RichRow::values`] (column IDs or column names, depending on `use_column_names`)
+    pub values: HashMap<String, TypedValue>,
+}
+
+/// Decodes the row values using the formats of the `columns`. The columns are matched by ID first, then by name. The values of unknown columns are returned as [`TypedValue::Raw`].
+pub fn decode_row(row: &RichRow, columns: &[Column]) -> Result<TypedRow, DecodeRowError> {
+    use DecodeRowError::*;
+    let results = row.values.iter().map(|(key, value)| {
+        let column_opt = columns
+            .iter()
+            .find(|column| &column.id == key)
+            .or_else(|| columns.iter().find(|column| &column.name == key));
+        match column_opt {
+            Some(column) => {
+                let typed_value = handle!(decode_value(value, &column.format), ItemError, item: key.clone());
+                Ok((key.clone(), typed_value))
+            }
+            None => Ok((key.clone(), TypedValue::Raw(value.clone()))),
+        }
+    });
+    let values = handle_iter!(results, DecodeValuesFailed, row_id: row.id.clone())
+        .into_iter()
+        .collect();
+    Ok(TypedRow {
+        id: row.id.clone(),
+        name: row.name.clone(),
+        index: row.index,
+        browser_link: row.browser_link.clone(),
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+        values,
+    })
+}
+
+#[derive(Error, Debug)]
+pub enum DecodeRowError {
+    #[error("failed to decode values of row '{row_id}'")]
+    DecodeValuesFailed { row_id: RowId, source: ErrVec<ItemError<String, DecodeValueError>> },
+}
@@ -0,0 +1,226 @@
+use crate::types::{ColumnFormat, ColumnFormatType, CurrencyAmount, ScalarValue};
+use crate::{DurationValueParserError, ParseDisplayDateTimeError, RichCurrencyValue, RichImageValue, RichPersonValue, RichRowReference, RichSingleValue, RichUrlValue, RichValue, RichValueEntry, currency_code_hint, normalize_rich_string, parse_duration_seconds, parse_time_display};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use errgonomic::{handle, handle_opt};
+use std::num::ParseFloatError;
+use thiserror::Error;
+
+/// A cell value interpreted according to the format of its column
+#[derive(Clone, Debug)]
+pub enum TypedValue {
+    /// Coda returns an empty string for every empty cell, regardless of the column format
+    Null,
+    Text(String),
+    Number(f64),
+    /// A fraction (`0.12` means `12%`)
+    Percent(f64),
+    Currency(TypedCurrency),
+    Boolean(bool),
+    Date(NaiveDate),
+    DateTime(DateTime<FixedOffset>),
+    /// A date-time in the doc timezone (returned by [`ValueFormat::Simple`](crate::types::ValueFormat::Simple), which omits the offset)
+    LocalDateTime(NaiveDateTime),
+    Time(NaiveTime),
+    Duration(TimeDelta),
+    Person(RichPersonValue),
+    Row(RichRowReference),
+    Url(RichUrlValue),
+    Image(RichImageValue),
+    List(Vec<TypedValue>),
+    /// A value of a column whose format is not interpreted (e.g. buttons, reactions, pack objects)
+    Raw(RichValue),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypedCurrency {
+    pub amount: f64,
+    /// ISO 4217 currency code (e.g. "USD"), if known
+    pub currency: Option<String>,
+}
+
+impl TypedValue {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+}
+
+pub fn decode_value(value: &RichValue, format: &ColumnFormat) -> Result<TypedValue, DecodeValueError> {
+    let typed_value = match value {
+        RichValue::Single(single) => decode_single_value(single, format)?,
+        RichValue::Collection(entries) => {
+            let values = entries
+                .iter()
+                .map(|entry| decode_value_entry(entry, format))
+                .collect::<Result<Vec<_>, _>>()?;
+            TypedValue::List(values)
+        }
+    };
+    match typed_value {
+        TypedValue::Null | TypedValue::List(_) | TypedValue::Raw(_) => Ok(typed_value),
+        typed_value if format.is_array() => Ok(TypedValue::List(vec![typed_value])),
+        typed_value => Ok(typed_value),
+    }
+}
+
+fn decode_value_entry(entry: &RichValueEntry, format: &ColumnFormat) -> Result<TypedValue, DecodeValueError> {
+    match entry {
+        RichValueEntry::Single(single) => decode_single_value(single, format),
+        RichValueEntry::Many(singles) => singles
+            .iter()
+            .map(|single| decode_single_value(single, format))
+            .collect::<Result<Vec<_>, _>>()
+            .map(TypedValue::List),
+    }
+}
+
+pub fn decode_single_value(value: &RichSingleValue, format: &ColumnFormat) -> Result<TypedValue, DecodeValueError> {
+    use RichSingleValue::*;
+    match value {
+        Scalar(ScalarValue::String(text)) if text.trim().is_empty() => Ok(TypedValue::Null),
+        Scalar(scalar) => decode_scalar_value(scalar, format),
+        Currency(currency) => decode_currency_value(currency),
+        Image(image) => Ok(TypedValue::Image(image.clone())),
+        Person(person) => Ok(TypedValue::Person(person.clone())),
+        Url(url) => Ok(TypedValue::Url(url.clone())),
+        Row(reference) => Ok(TypedValue::Row(reference.clone())),
+    }
+}
+
+fn decode_scalar_value(scalar: &ScalarValue, format: &ColumnFormat) -> Result<TypedValue, DecodeValueError> {
+    use ColumnFormatType::*;
+    use DecodeValueError::*;
+    let format_type = format.format_type();
+    match (format_type, scalar) {
+        (Number | Slider | Scale, ScalarValue::Number(number)) => Ok(TypedValue::Number(*number)),
+        (Number | Slider | Scale, ScalarValue::String(text)) => parse_f64(text).map(TypedValue::Number),
+        (Percent, ScalarValue::Number(number)) => Ok(TypedValue::Percent(*number)),
+        (Percent, ScalarValue::String(text)) => parse_f64(text).map(TypedValue::Percent),
+        (Currency, ScalarValue::Number(number)) => Ok(TypedValue::Currency(TypedCurrency {
+            amount: *number,
+            currency: currency_code_hint(format),
+        })),
+        (Currency, ScalarValue::String(text)) => Ok(TypedValue::Currency(TypedCurrency {
+            amount: parse_f64(text)?,
+            currency: currency_code_hint(format),
+        })),
+        (Date, ScalarValue::String(text)) => parse_date(text).map(TypedValue::Date),
+        (DateTime, ScalarValue::String(text)) => parse_date_time(text).map(TypedValue::DateTime),
+        (Time, ScalarValue::String(text)) => parse_time(text).map(TypedValue::Time),
+        (Duration, ScalarValue::String(text)) => parse_duration(text),
+        (Checkbox, ScalarValue::Boolean(boolean)) => Ok(TypedValue::Boolean(*boolean)),
+        (Number | Slider | Scale | Percent | Currency | Date | DateTime | Time | Duration | Checkbox, scalar) => Err(ScalarValueUnexpected {
+            format_type,
+            scalar: scalar.clone(),
+        }),
+        (Button | Reaction | PackObject | Attachments, scalar) => Ok(TypedValue::Raw(RichValue::Single(RichSingleValue::Scalar(scalar.clone())))),
+        (_, ScalarValue::String(text)) => Ok(TypedValue::Text(normalize_rich_string(text))),
+        (_, ScalarValue::Number(number)) => Ok(TypedValue::Number(*number)),
+        (_, ScalarValue::Boolean(boolean)) => Ok(TypedValue::Boolean(*boolean)),
+    }
+}
+
+fn decode_currency_value(currency: &RichCurrencyValue) -> Result<TypedValue, DecodeValueError> {
+    let amount = match &currency.amount {
+        CurrencyAmount::Number(number) => *number,
+        CurrencyAmount::String(text) => parse_f64(text)?,
+    };
+    Ok(TypedValue::Currency(TypedCurrency {
+        amount,
+        currency: Some(currency.currency.clone()),
+    }))
+}
+
+fn parse_f64(text: &str) -> Result<f64, DecodeValueError> {
+    use DecodeValueError::*;
+    let trimmed = text.trim();
+    Ok(handle!(trimmed.parse::<f64>(), NumberParseFailed, value: trimmed))
+}
+
+/// Coda returns dates as RFC3339 timestamps at midnight in the doc timezone, so the date part is taken as-is
+fn parse_date(text: &str) -> Result<NaiveDate, DecodeValueError> {
+    use DecodeValueError::*;
+    let trimmed = text.trim();
+    match DateTime::parse_from_rfc3339(trimmed) {
+        Ok(date_time) => Ok(date_time.date_naive()),
+        Err(_) => Ok(handle!(NaiveDate::parse_from_str(trimmed, "%Y-%m-%d"), DateParseFailed, value: trimmed)),
+    }
+}
+
+fn parse_date_time(text: &str) -> Result<DateTime<FixedOffset>, DecodeValueError> {
+    use DecodeValueError::*;
+    let trimmed = text.trim();
+    Ok(handle!(DateTime::parse_from_rfc3339(trimmed), DateTimeParseFailed, value: trimmed))
+}
+
+/// Coda may return times either as RFC3339 timestamps (with a placeholder date) or as plain times
+fn parse_time(text: &str) -> Result<NaiveTime, DecodeValueError> {
+    use DecodeValueError::*;
+    Ok(handle!(parse_time_display(text, None), TimeParseFailed, value: text.trim()))
+}
+
+fn parse_duration(text: &str) -> Result<TypedValue, DecodeValueError> {
+    use DecodeValueError::*;
+    let seconds_opt = handle!(parse_duration_seconds(&text), DurationParseFailed, value: text);
+    match seconds_opt {
+        Some(seconds) => Ok(TypedValue::Duration(handle_opt!(TimeDelta::try_seconds(seconds), DurationOutOfRange, seconds))),
+        None => Ok(TypedValue::Null),
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DecodeValueError {
+    #[error("scalar value '{scalar}' is not valid for column format '{format_type}'")]
+    ScalarValueUnexpected { format_type: ColumnFormatType, scalar: ScalarValue },
+    #[error("failed to parse number '{value}'")]
+    NumberParseFailed { source: ParseFloatError, value: String },
+    #[error("failed to parse date '{value}'")]
+    DateParseFailed { source: chrono::ParseError, value: String },
+    #[error("failed to parse date-time '{value}'")]
+    DateTimeParseFailed { source: chrono::ParseError, value: String },
+    #[error("failed to parse time '{value}'")]
+    TimeParseFailed { source: ParseDisplayDateTimeError, value: String },
+    #[error("failed to parse duration '{value}'")]
+    DurationParseFailed { source: DurationValueParserError, value: String },
+    #[error("duration of '{seconds}' seconds is out of range")]
+    DurationOutOfRange { seconds: i64 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn format(value: serde_json::Value) -> ColumnFormat {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn rich(value: serde_json::Value) -> RichValue {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn must_decode_empty_string_as_null() {
+        let format = format(json!({"type": "number", "isArray": false}));
+        assert!(decode_value(&rich(json!("")), &format).unwrap().is_null());
+    }
+
+    #[test]
+    fn must_decode_percent() {
+        let format = format(json!({"type": "percent", "isArray": false}));
+        assert!(matches!(decode_value(&rich(json!(0.12)), &format), Ok(TypedValue::Percent(0.12))));
+    }
+
+    #[test]
+    fn must_decode_date() {
+        let format = format(json!({"type": "date", "isArray": false}));
+        let expected = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        assert!(matches!(decode_value(&rich(json!("2026-10-18T00:00:00.000-07:00")), &format), Ok(TypedValue::Date(date)) if date == expected));
+    }
+
+    #[test]
+    fn must_decode_duration() {
+        let format = format(json!({"type": "duration", "isArray": false}));
+        let expected = TimeDelta::minutes(150);
+        assert!(matches!(decode_value(&rich(json!("2 hrs 30 mins")), &format), Ok(TypedValue::Duration(duration)) if duration == expected));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::types::{CellValue, Value};
+use crate::{Client, DocId, Error, is_same_value, types};
+use errgonomic::handle;
+use futures_util::Stream;
+use futures_util::stream::unfold;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::time::sleep;
+
+/// A formula or a control to watch (by ID or name)
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum WatchTarget {
+    Formula(String),
+    Control(String),
+}
+
+/// A change of the value of a [`WatchTarget`]
+#[derive(Clone, Debug)]
+pub struct WatchEvent {
+    pub target: WatchTarget,
+    pub id: String,
+    pub name: String,
+    /// The value before the change (`None` for the first poll)
+    pub previous: Option<Value>,
+    pub current: Value,
+}
+
+impl WatchEvent {
+    /// Parses the current value with the cell value conversions (e.g. `event.parse::<String>()`)
+    pub fn parse<T: TryFrom<CellValue>>(&self) -> Result<T, T::Error> {
+        T::try_from(CellValue::Value(self.current.clone()))
+    }
+
+    /// Parses the previous value with the cell value conversions (returns `None` for the first poll)
+    pub fn parse_previous<T: TryFrom<CellValue>>(&self) -> Option<Result<T, T::Error>> {
+        self.previous
+            .clone()
+            .map(|previous| T::try_from(CellValue::Value(previous)))
+    }
+}
+
+/// Polls the formulas and the controls of a doc, and returns an event for every value that has changed since the previous poll.
+///
+/// The first poll returns an event for every target (with `previous` set to `None`), so that the caller can initialize its state. Every target is requested separately, so every poll costs one read request per target.
+///
+/// ```no_run
+/// # use coda_api::{Client, Watcher};
+/// # use futures_util::StreamExt;
+/// # use std::time::Duration;
+/// # async fn example(client: &Client) {
+/// let watcher = Watcher::new(client, "doc-id")
+///     .with_formula("Status")
+///     .with_poll_interval(Duration::from_secs(30));
+/// let mut events = Box::pin(watcher.into_stream());
+/// while let Some(Ok(event)) = events.next().await {
+///     println!("{} = {:?}", event.name, event.parse::<String>());
+/// }
+/// # }
+/// ```
+pub struct Watcher<'a> {
+    client: &'a Client,
+    doc_id: DocId,
+    targets: Vec<WatchTarget>,
+    poll_interval: Duration,
+    values: HashMap<WatchTarget, Value>,
+    polled: bool,
+    buffer: VecDeque<WatchEvent>,
+}
+
+impl<'a> Watcher<'a> {
+    pub fn new(client: &'a Client, doc_id: impl Into<DocId>) -> Self {
+        Self {
+            client,
+            doc_id: doc_id.into(),
+            targets: Vec::new(),
+            poll_interval: Duration::from_secs(10),
+            values: HashMap::new(),
+            polled: false,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    pub fn with_target(mut self, target: WatchTarget) -> Self {
+        if !self.targets.contains(&target) {
+            self.targets.push(target);
+        }
+        self
+    }
+
+    pub fn with_formula(self, formula_id_or_name: impl Into<String>) -> Self {
+        self.with_target(WatchTarget::Formula(formula_id_or_name.into()))
+    }
+
+    pub fn with_control(self, control_id_or_name: impl Into<String>) -> Self {
+        self.with_target(WatchTarget::Control(control_id_or_name.into()))
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn targets(&self) -> &[WatchTarget] {
+        &self.targets
+    }
+
+    /// Returns the value of the `target` as of the last poll
+    pub fn value(&self, target: &WatchTarget) -> Option<&Value> {
+        self.values.get(target)
+    }
+
+    /// Fetches every target and returns the events for the values that have changed since the previous poll (may be empty). The values are updated only if every target is fetched, so a failed poll doesn't lose any change.
+    pub async fn poll(&mut self) -> Result<Vec<WatchEvent>, WatcherPollError> {
+        // set before fetching, so that `next` waits before retrying a failed poll
+        self.polled = true;
+        let mut events = Vec::new();
+        let mut values = HashMap::new();
+        for target in &self.targets {
+            let (id, name, current) = self.fetch(target).await?;
+            let previous = self.values.get(target);
+            if previous.is_some_and(|previous| is_same_value(previous, &current)) {
+                continue;
+            }
+            events.push(WatchEvent {
+                target: target.clone(),
+                id,
+                name,
+                previous: previous.cloned(),
+                current: current.clone(),
+            });
+            values.insert(target.clone(), current);
+        }
+        self.values.extend(values);
+        Ok(events)
+    }
+
+    /// Waits for the next change (the first call returns the initial values without waiting, and every following poll waits for the poll interval, including the retries of a failed poll)
+    pub async fn next(&mut self) -> Result<WatchEvent, WatcherPollError> {
+        loop {
+            if let Some(event) = self.buffer.pop_front() {
+                return Ok(event);
+            }
+            if self.polled {
+                sleep(self.poll_interval).await;
+            }
+            let events = self.poll().await?;
+            self.buffer.extend(events);
+        }
+    }
+
+    /// Converts the watcher into an infinite stream of events. The stream yields an error if a poll fails, and continues with the same values on the next item.
+    pub fn into_stream(self) -> impl Stream<Item = Result<WatchEvent, WatcherPollError>> + 'a {
+        unfold(self, |mut watcher| async move {
+            let result = watcher.next().await;
+            Some((result, watcher))
+        })
+    }
+
+    async fn fetch(&self, target: &WatchTarget) -> Result<(String, String, Value), WatcherPollError> {
+        use WatcherPollError::*;
+        match target {
+            WatchTarget::Formula(formula_id_or_name) => {
+                let formula = handle!(self.client.get_formula(&self.doc_id, formula_id_or_name).await, GetFormulaFailed, formula_id_or_name: formula_id_or_name.clone()).into_inner();
+                Ok((formula.id, formula.name, formula.value))
+            }
+            WatchTarget::Control(control_id_or_name) => {
+                let control = handle!(self.client.get_control(&self.doc_id, control_id_or_name).await, GetControlFailed, control_id_or_name: control_id_or_name.clone()).into_inner();
+                Ok((control.id, control.name, control.value))
+            }
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum WatcherPollError {
+    #[error("failed to get formula '{formula_id_or_name}'")]
+    GetFormulaFailed { formula_id_or_name: String, source: Box<Error<types::GetFormulaResponse>> },
+    #[error("failed to get control '{control_id_or_name}'")]
+    GetControlFailed { control_id_or_name: String, source: Box<Error<types::GetControlResponse>> },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ValueVariant0;
+
+    #[test]
+    fn must_parse_event_values() {
+        let event = WatchEvent {
+            target: WatchTarget::Formula("Status".to_owned()),
+            id: "f-1".to_owned(),
+            name: "Status".to_owned(),
+            previous: Some(Value::Variant0(ValueVariant0::Variant0("Pending".to_owned()))),
+            current: Value::Variant0(ValueVariant0::Variant0("Done".to_owned())),
+        };
+        assert_eq!(event.parse::<String>().unwrap(), "Done");
+        assert_eq!(event.parse_previous::<String>().unwrap().unwrap(), "Pending");
+        assert!(event.parse::<f64>().is_err());
+        assert!(!is_same_value(&event.current, event.previous.as_ref().unwrap()));
+        assert!(is_same_value(&event.current, &event.current.clone()));
+    }
+}
@@ -0,0 +1,293 @@
+use crate::{DocId, RowId, TableId};
+use bytes::Bytes;
+use errgonomic::{handle, handle_bool, handle_opt};
+use futures_util::future::BoxFuture;
+use http::header::HeaderName;
+use http::{HeaderMap, Method, Request, Response, StatusCode};
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::convert::Infallible;
+use std::error::Error as StdError;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+use tower_service::Service;
+
+/// An event sent by a Coda automation with the "Make an HTTP request" action. The automation must send a JSON object with an `event` field, for example:
+///
+/// ```json
+/// {"event": "rowChanged", "docId": "AbCDeFGH", "tableId": "grid-pqRst-U", "rowId": "i-tuVwxYz", "values": {"Status": "Done"}}
+/// ```
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(tag = "event", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum CodaWebhookEvent {
+    RowAdded {
+        doc_id: DocId,
+        table_id: TableId,
+        row_id: RowId,
+        #[serde(default)]
+        values: Map<String, Value>,
+    },
+    RowChanged {
+        doc_id: DocId,
+        table_id: TableId,
+        row_id: RowId,
+        #[serde(default)]
+        values: Map<String, Value>,
+    },
+    ButtonPressed {
+        doc_id: DocId,
+        table_id: TableId,
+        row_id: RowId,
+        column_id: String,
+        #[serde(default)]
+        user_email: Option<String>,
+    },
+}
+
+impl CodaWebhookEvent {
+    pub const EVENT_NAMES: [&'static str; 3] = ["rowAdded", "rowChanged", "buttonPressed"];
+}
+
+/// A received webhook: either a known [`CodaWebhookEvent`], or any other JSON body
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReceivedWebhook {
+    Event(CodaWebhookEvent),
+    Other(Value),
+}
+
+impl ReceivedWebhook {
+    /// Parses the body. A body with a known `event` must have the shape of that event; any other JSON body is returned as [`ReceivedWebhook::Other`].
+    pub fn parse(body: &[u8]) -> Result<Self, WebhookReceiveError> {
+        use WebhookReceiveError::*;
+        let value: Value = handle!(serde_json::from_slice(body), BodyInvalid);
+        let is_known = value
+            .get("event")
+            .and_then(Value::as_str)
+            .is_some_and(|event| CodaWebhookEvent::EVENT_NAMES.contains(&event));
+        if is_known {
+            let event = handle!(serde_json::from_value(value), BodyInvalid);
+            Ok(Self::Event(event))
+        } else {
+            Ok(Self::Other(value))
+        }
+    }
+}
+
+pub type WebhookHandler = Arc<dyn Fn(ReceivedWebhook) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Validates the shared secret header of the incoming webhooks, parses them, and dispatches them to the handlers (in the order they were added).
+///
+/// Use [`WebhookReceiver::into_service`] to mount it in any `tower`-based server, or [`WebhookReceiver::into_router`] for axum.
+#[derive(Clone)]
+pub struct WebhookReceiver {
+    secret_header: HeaderName,
+    secret: String,
+    max_body_bytes: usize,
+    handlers: Vec<WebhookHandler>,
+}
+
+impl WebhookReceiver {
+    pub const DEFAULT_SECRET_HEADER: HeaderName = HeaderName::from_static("x-coda-webhook-secret");
+    pub const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+
+    /// The automation must send the `secret` in the [`WebhookReceiver::DEFAULT_SECRET_HEADER`] header (configurable with [`WebhookReceiver::with_secret_header`]). An empty `secret` rejects every webhook.
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self {
+            secret_header: Self::DEFAULT_SECRET_HEADER,
+            secret: secret.into(),
+            max_body_bytes: Self::DEFAULT_MAX_BODY_BYTES,
+            handlers: Vec::new(),
+        }
+    }
+
+    pub fn with_secret_header(mut self, secret_header: HeaderName) -> Self {
+        self.secret_header = secret_header;
+        self
+    }
+
+    pub fn with_max_body_bytes(mut self, max_body_bytes: usize) -> Self {
+        self.max_body_bytes = max_body_bytes;
+        self
+    }
+
+    pub fn on_webhook<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(ReceivedWebhook) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.handlers
+            .push(Arc::new(move |webhook| Box::pin(handler(webhook))));
+        self
+    }
+
+    /// Adds a handler for the known events only
+    pub fn on_event<F, Fut>(self, handler: F) -> Self
+    where
+        F: Fn(CodaWebhookEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        self.on_webhook(move |webhook| {
+            let handler = handler.clone();
+            async move {
+                if let ReceivedWebhook::Event(event) = webhook {
+                    handler(event).await;
+                }
+            }
+        })
+    }
+
+    /// Checks the secret header (an empty secret of the receiver fails with [`WebhookReceiveError::SecretInvalid`])
+    pub fn verify(&self, headers: &HeaderMap) -> Result<(), WebhookReceiveError> {
+        use WebhookReceiveError::*;
+        let value = handle_opt!(headers.get(&self.secret_header), SecretMissing, header: self.secret_header.clone());
+        let is_valid = !self.secret.is_empty() && bool::from(value.as_bytes().ct_eq(self.secret.as_bytes()));
+        handle_bool!(!is_valid, SecretInvalid, header: self.secret_header.clone());
+        Ok(())
+    }
+
+    /// Verifies the secret, parses the body and awaits every handler
+    pub async fn receive(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), WebhookReceiveError> {
+        self.verify(headers)?;
+        let webhook = ReceivedWebhook::parse(body)?;
+        for handler in &self.handlers {
+            handler(webhook.clone()).await;
+        }
+        Ok(())
+    }
+
+    pub fn into_service(self) -> WebhookService {
+        WebhookService {
+            receiver: Arc::new(self),
+        }
+    }
+
+    /// Returns an axum router that receives the webhooks at `path`
+    pub fn into_router(self, path: &str) -> axum::Router {
+        axum::Router::new().route_service(path, self.into_service())
+    }
+}
+
+/// A `tower` service that accepts `POST` requests and responds with:
+///
+/// - `204 No Content` if the webhook was handled
+/// - `401 Unauthorized` if the secret header is missing or invalid
+/// - `400 Bad Request` if the body is not a valid webhook
+/// - `413 Payload Too Large` if the body exceeds the limit
+/// - `405 Method Not Allowed` for other methods
+#[derive(Clone)]
+pub struct WebhookService {
+    receiver: Arc<WebhookReceiver>,
+}
+
+impl<B> Service<Request<B>> for WebhookService
+where
+    B: http_body::Body + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let receiver = self.receiver.clone();
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let status = if parts.method != Method::POST {
+                StatusCode::METHOD_NOT_ALLOWED
+            } else {
+                match Limited::new(body, receiver.max_body_bytes).collect().await {
+                    Ok(collected) => match receiver
+                        .receive(&parts.headers, &collected.to_bytes())
+                        .await
+                    {
+                        Ok(()) => StatusCode::NO_CONTENT,
+                        Err(error) => error.status_code(),
+                    },
+                    Err(error) if error.is::<LengthLimitError>() => StatusCode::PAYLOAD_TOO_LARGE,
+                    Err(_) => StatusCode::BAD_REQUEST,
+                }
+            };
+            let mut response = Response::new(Full::default());
+            *response.status_mut() = status;
+            Ok(response)
+        })
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum WebhookReceiveError {
+    #[error("webhook secret header '{header}' is missing")]
+    SecretMissing { header: HeaderName },
+    #[error("webhook secret header '{header}' is invalid")]
+    SecretInvalid { header: HeaderName },
+    #[error("webhook body is invalid")]
+    BodyInvalid { source: serde_json::Error },
+}
+
+impl WebhookReceiveError {
+    pub fn status_code(&self) -> StatusCode {
+        use WebhookReceiveError::*;
+        match self {
+            SecretMissing {
+                ..
+            }
+            | SecretInvalid {
+                ..
+            } => StatusCode::UNAUTHORIZED,
+            BodyInvalid {
+                ..
+            } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn must_dispatch_events_with_valid_secret() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+        let mut service = WebhookReceiver::new("s3cret")
+            .on_event(move |event| {
+                let counter = counter.clone();
+                async move {
+                    assert!(matches!(event, CodaWebhookEvent::ButtonPressed { ref column_id, .. } if column_id == "c-1"));
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+            .into_service();
+        let body = r#"{"event": "buttonPressed", "docId": "d", "tableId": "t", "rowId": "r", "columnId": "c-1"}"#;
+        let request = |secret: &str| {
+            Request::post("/")
+                .header(WebhookReceiver::DEFAULT_SECRET_HEADER, secret)
+                .body(Full::new(Bytes::from(body)))
+                .unwrap()
+        };
+        let response = service.call(request("s3cret")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = service.call(request("wrong")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn must_reject_empty_secret() {
+        let receiver = WebhookReceiver::new("");
+        let mut headers = HeaderMap::new();
+        headers.insert(WebhookReceiver::DEFAULT_SECRET_HEADER, HeaderValue::from_static(""));
+        assert!(matches!(receiver.verify(&headers), Err(WebhookReceiveError::SecretInvalid { .. })));
+    }
+}
@@ -0,0 +1,273 @@
+use crate::types::{MutationStatus, WebhookTriggerPayload};
+use crate::{Client, DocId, Error, WaitForMutationError, types};
+use errgonomic::{ErrVec, ItemError, handle, handle_opt};
+use futures_util::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::mem::take;
+use thiserror::Error;
+
+/// A typed payload of a webhook automation. The `NAME` is looked up in a [`WebhookRegistry`] to find the doc and the rule to trigger.
+///
+/// The payload must serialize to a JSON object (the automation reads its fields with `Step 1 Result.fieldName`).
+pub trait WebhookEvent: Serialize {
+    const NAME: &'static str;
+
+    /// Events with the same debounce key replace each other in [`WebhookTrigger::enqueue`], so only the last one is triggered. `None` means the event is never replaced.
+    fn debounce_key(&self) -> Option<String> {
+        None
+    }
+}
+
+/// The webhook automation rule of a doc (the rule ID is shown in the automation settings when the "Webhook invoked" trigger is selected)
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct WebhookRule {
+    pub doc_id: DocId,
+    pub rule_id: String,
+}
+
+/// Maps event names to the rules that handle them. Deserializes from a map like `{"order_created": {"doc_id": "...", "rule_id": "..."}}`.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(transparent)]
+pub struct WebhookRegistry {
+    pub rules: BTreeMap<String, WebhookRule>,
+}
+
+impl WebhookRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<E: WebhookEvent>(&mut self, doc_id: impl Into<DocId>, rule_id: impl Into<String>) -> &mut Self {
+        self.register_name(E::NAME, doc_id, rule_id)
+    }
+
+    pub fn register_name(&mut self, name: impl Into<String>, doc_id: impl Into<DocId>, rule_id: impl Into<String>) -> &mut Self {
+        let rule = WebhookRule {
+            doc_id: doc_id.into(),
+            rule_id: rule_id.into(),
+        };
+        self.rules.insert(name.into(), rule);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&WebhookRule> {
+        self.rules.get(name)
+    }
+}
+
+/// Converts a `Serialize` value into a webhook payload
+pub fn webhook_payload(value: &impl Serialize) -> Result<WebhookTriggerPayload, WebhookPayloadError> {
+    use WebhookPayloadError::*;
+    match handle!(serde_json::to_value(value), SerializeFailed) {
+        Value::Object(map) => Ok(WebhookTriggerPayload(map)),
+        value => Err(NotAnObject {
+            value,
+        }),
+    }
+}
+
+/// The result of a successful trigger
+#[derive(Serialize, Clone, Debug)]
+pub struct WebhookTriggerReceipt {
+    pub name: String,
+    pub rule: WebhookRule,
+    /// Can be passed to [`Client::wait_for_mutation`] to confirm that the automation has run
+    pub request_id: String,
+}
+
+#[derive(Clone, Debug)]
+struct PendingWebhook {
+    name: String,
+    debounce_key: Option<String>,
+    payload: WebhookTriggerPayload,
+}
+
+/// Triggers the webhook automations of typed events, either immediately ([`WebhookTrigger::trigger`]) or in batches ([`WebhookTrigger::enqueue`] + [`WebhookTrigger::flush`])
+pub struct WebhookTrigger<'a> {
+    client: &'a Client,
+    registry: WebhookRegistry,
+    pending: Vec<PendingWebhook>,
+}
+
+impl<'a> WebhookTrigger<'a> {
+    pub fn new(client: &'a Client, registry: WebhookRegistry) -> Self {
+        Self {
+            client,
+            registry,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn registry(&self) -> &WebhookRegistry {
+        &self.registry
+    }
+
+    pub async fn trigger<E: WebhookEvent>(&self, event: &E) -> Result<WebhookTriggerReceipt, WebhookTriggerError> {
+        use WebhookTriggerError::*;
+        let payload = handle!(webhook_payload(event), PayloadInvalid, name: E::NAME.to_string());
+        self.trigger_payload(E::NAME, &payload).await
+    }
+
+    /// Triggers the event and waits until the automation has run
+    pub async fn trigger_and_confirm<E: WebhookEvent>(&self, event: &E, max_attempts: usize, delay_secs: u64) -> Result<(WebhookTriggerReceipt, MutationStatus), WebhookTriggerAndConfirmError> {
+        use WebhookTriggerAndConfirmError::*;
+        let receipt = handle!(self.trigger(event).await, TriggerFailed);
+        let status = handle!(
+            self.client
+                .wait_for_mutation(&receipt.request_id, max_attempts, delay_secs)
+                .await,
+            WaitForMutationFailed,
+            request_id: receipt.request_id.clone()
+        );
+        Ok((receipt, status))
+    }
+
+    /// Adds the event to the batch. If a pending event has the same name and debounce key, it is replaced.
+    pub fn enqueue<E: WebhookEvent>(&mut self, event: &E) -> Result<(), WebhookTriggerError> {
+        use WebhookTriggerError::*;
+        let payload = handle!(webhook_payload(event), PayloadInvalid, name: E::NAME.to_string());
+        let debounce_key = event.debounce_key();
+        let pending = PendingWebhook {
+            name: E::NAME.to_string(),
+            debounce_key,
+            payload,
+        };
+        let existing_opt = self
+            .pending
+            .iter_mut()
+            .find(|existing| existing.name == pending.name && existing.debounce_key.is_some() && existing.debounce_key == pending.debounce_key);
+        match existing_opt {
+            Some(existing) => *existing = pending,
+            None => self.pending.push(pending),
+        }
+        Ok(())
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Triggers every pending event concurrently (the client limiter still applies) and clears the batch. The events that fail to trigger are put back into the batch, and the error contains the receipts of the events that were triggered.
+    pub async fn flush(&mut self) -> Result<Vec<WebhookTriggerReceipt>, WebhookFlushError> {
+        use WebhookFlushError::*;
+        let pending = take(&mut self.pending);
+        let trigger = &*self;
+        let responses = join_all(pending.iter().map(|pending| async move {
+            trigger
+                .trigger_payload(&pending.name, &pending.payload)
+                .await
+        }))
+        .await;
+        let mut receipts = Vec::new();
+        let mut errors = Vec::new();
+        let mut failed = Vec::new();
+        for (pending, result) in pending.into_iter().zip(responses) {
+            match result {
+                Ok(receipt) => receipts.push(receipt),
+                Err(source) => {
+                    errors.push(ItemError {
+                        item: pending.name.clone(),
+                        source,
+                    });
+                    failed.push(pending);
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(receipts)
+        } else {
+            self.pending = failed;
+            Err(TriggerFailed {
+                source: ErrVec::new(errors),
+                receipts,
+            })
+        }
+    }
+
+    async fn trigger_payload(&self, name: &str, payload: &WebhookTriggerPayload) -> Result<WebhookTriggerReceipt, WebhookTriggerError> {
+        use WebhookTriggerError::*;
+        let rule = handle_opt!(self.registry.get(name), RuleNotRegistered, name: name.to_string());
+        let result = handle!(
+            self.client
+                .trigger_webhook_automation_correct(&rule.doc_id, &rule.rule_id, payload)
+                .await,
+            TriggerWebhookAutomationFailed,
+            name: name.to_string()
+        );
+        Ok(WebhookTriggerReceipt {
+            name: name.to_string(),
+            rule: rule.clone(),
+            request_id: result.into_inner().request_id,
+        })
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum WebhookPayloadError {
+    #[error("failed to serialize webhook payload")]
+    SerializeFailed { source: serde_json::Error },
+    #[error("webhook payload must be a JSON object, but got '{value}'")]
+    NotAnObject { value: Value },
+}
+
+#[derive(Error, Debug)]
+pub enum WebhookTriggerError {
+    #[error("webhook event '{name}' is not registered")]
+    RuleNotRegistered { name: String },
+    #[error("payload of webhook event '{name}' is invalid")]
+    PayloadInvalid { name: String, source: WebhookPayloadError },
+    #[error("failed to trigger webhook automation for event '{name}'")]
+    TriggerWebhookAutomationFailed { name: String, source: Box<Error<types::TriggerWebhookAutomationResponse>> },
+}
+
+#[derive(Error, Debug)]
+pub enum WebhookTriggerAndConfirmError {
+    #[error("failed to trigger webhook event")]
+    TriggerFailed { source: WebhookTriggerError },
+    #[error("failed to confirm webhook request '{request_id}'")]
+    WaitForMutationFailed { request_id: String, source: WaitForMutationError },
+}
+
+#[derive(Error, Debug)]
+pub enum WebhookFlushError {
+    #[error("failed to trigger {} webhook events", source.len())]
+    TriggerFailed { source: ErrVec<ItemError<String, WebhookTriggerError>>, receipts: Vec<WebhookTriggerReceipt> },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct OrderUpdated {
+        order_id: u64,
+        status: &'static str,
+    }
+
+    impl WebhookEvent for OrderUpdated {
+        const NAME: &'static str = "order_updated";
+
+        fn debounce_key(&self) -> Option<String> {
+            Some(self.order_id.to_string())
+        }
+    }
+
+    #[test]
+    fn must_replace_pending_events_with_same_debounce_key() {
+        let client = Client::new_with_key("YOUR_API_KEY").unwrap();
+        let mut trigger = WebhookTrigger::new(&client, WebhookRegistry::new());
+        for (order_id, status) in [(1, "paid"), (2, "paid"), (1, "shipped")] {
+            trigger
+                .enqueue(&OrderUpdated {
+                    order_id,
+                    status,
+                })
+                .unwrap();
+        }
+        assert_eq!(trigger.pending_len(), 2);
+        assert_eq!(trigger.pending[0].payload.get("status"), Some(&Value::from("shipped")));
+        assert!(webhook_payload(&42).is_err());
+    }
+}
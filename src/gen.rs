@@ -1,9 +1,11 @@
 #![allow(unreachable_code)]
 #![allow(clippy::absolute_paths)]
+use crate::ext::{MiddlewareChain, ResponseCache};
 #[allow(unused_imports)]
 pub use progenitor_client::{ByteStream, ClientInfo, Error, ResponseValue};
 #[allow(unused_imports)]
 use progenitor_client::{ClientHooks, OperationInfo, RequestBuilderExt, encode_path};
+use std::sync::Arc;
 /// Types used as operation parameters and responses.
 #[allow(clippy::all)]
 pub mod types {
@@ -45793,6 +45795,8 @@ pub mod types {
 pub struct RawClient {
     pub(crate) baseurl: String,
     pub(crate) client: reqwest::Client,
+    pub(crate) middleware: MiddlewareChain,
+    pub(crate) cache: Option<Arc<ResponseCache>>,
 }
 
 impl RawClient {
@@ -45824,6 +45828,8 @@ impl RawClient {
         Self {
             baseurl: baseurl.to_string(),
             client,
+            middleware: MiddlewareChain::default(),
+            cache: None,
         }
     }
 }
@@ -45846,7 +45852,6 @@ impl ClientInfo<()> for RawClient {
     }
 }
 
-impl ClientHooks<()> for &RawClient {}
 #[allow(clippy::all)]
 impl RawClient {
     ///Get doc categories
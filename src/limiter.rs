@@ -1,6 +1,8 @@
+use crate::record_limiter_wait;
 use governor::{DefaultDirectRateLimiter, Quota};
 use std::num::NonZeroU32;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// The rate limits are slightly lower compared to official docs because I've seen "429 Too Many Requests" errors even with these rate limits
 #[derive(Debug)]
@@ -12,6 +14,62 @@ pub struct Limiter {
     pub read_analytics: DefaultDirectRateLimiter,
 }
 
+/// Identifies a rate limiter of [`Limiter`] (used as a label in traces and metrics)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum LimiterBucket {
+    Read,
+    Write,
+    WriteDocContent,
+    ListDocs,
+    ReadAnalytics,
+}
+
+impl LimiterBucket {
+    pub fn as_str(&self) -> &'static str {
+        use LimiterBucket::*;
+        match self {
+            Read => "read",
+            Write => "write",
+            WriteDocContent => "write_doc_content",
+            ListDocs => "list_docs",
+            ReadAnalytics => "read_analytics",
+        }
+    }
+}
+
+impl Limiter {
+    pub fn get(&self, bucket: LimiterBucket) -> &DefaultDirectRateLimiter {
+        use LimiterBucket::*;
+        match bucket {
+            Read => &self.read,
+            Write => &self.write,
+            WriteDocContent => &self.write_doc_content,
+            ListDocs => &self.list_docs,
+            ReadAnalytics => &self.read_analytics,
+        }
+    }
+}
+
+tokio::task_local! {
+    /// The bucket that [`Client`](crate::Client) must wait for before the current request is sent
+    static LIMITER_GATE: (Arc<Limiter>, LimiterBucket);
+}
+
+/// Runs the `future` (a request of [`Client`](crate::Client)) so that [`wait_for_limiter_gate`] waits for the `bucket` of the `limiter`
+pub(crate) async fn with_limiter_gate<F: Future>(limiter: Arc<Limiter>, bucket: LimiterBucket, future: F) -> F::Output {
+    LIMITER_GATE.scope((limiter, bucket), future).await
+}
+
+/// Waits for the bucket set by [`with_limiter_gate`] (returns immediately if the request is sent by [`RawClient`](crate::RawClient) directly)
+pub(crate) async fn wait_for_limiter_gate() {
+    let Ok((limiter, bucket)) = LIMITER_GATE.try_with(Clone::clone) else {
+        return;
+    };
+    let started = Instant::now();
+    limiter.get(bucket).until_ready().await;
+    record_limiter_wait(bucket, started.elapsed());
+}
+
 /// Buffer added to official rate limits
 const BUFFER: u64 = 1;
 
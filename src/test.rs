@@ -5,3 +5,10 @@ fn new_with_key() {
     let client = Client::new_with_key("YOUR_API_KEY").unwrap();
     assert_eq!(client.raw.baseurl, "https://coda.io/apis/v1");
 }
+
+#[test]
+fn try_new_with_key() {
+    let client = Client::try_new_with_key("YOUR_API_KEY").unwrap();
+    assert_eq!(client.raw.baseurl, "https://coda.io/apis/v1");
+    assert!(Client::try_new_with_key("invalid\nkey").is_err());
+}